@@ -0,0 +1,148 @@
+// File: build.rs
+//
+// Generates typed Rust bindings for every function defined in clarity_contracts/**.clar.
+// The output is included by src/clarity/bindings.rs.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[allow(dead_code)]
+#[path = "src/clarity/parser.rs"]
+mod parser;
+
+use parser::{FunctionKind, TypeSignature};
+
+const CONTRACTS_DIR: &str = "clarity_contracts";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", CONTRACTS_DIR);
+    println!("cargo:rerun-if-changed=src/clarity/parser.rs");
+
+    let mut sources = Vec::new();
+    collect_contracts(Path::new(CONTRACTS_DIR), &mut sources);
+    sources.sort();
+
+    let mut output = String::new();
+    for path in &sources {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source = fs::read_to_string(path).expect("Failed to read contract source");
+        let signatures = parser::contract_signatures(&source)
+            .unwrap_or_else(|err| panic!("Failed to parse {}: {}", path.display(), err));
+        generate_module(&mut output, path, &signatures);
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
+    fs::write(out_dir.join("contract_bindings.rs"), output).expect("Failed to write bindings");
+}
+
+fn collect_contracts(dir: &Path, sources: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_contracts(&path, sources);
+        } else if path.extension().is_some_and(|ext| ext == "clar") {
+            sources.push(path);
+        }
+    }
+}
+
+fn generate_module(output: &mut String, path: &Path, signatures: &[parser::FunctionSignature]) {
+    let relative = path.strip_prefix(CONTRACTS_DIR).unwrap_or(path);
+    let module = path.file_stem().unwrap().to_string_lossy().replace('-', "_");
+    let exported: Vec<_> = signatures
+        .iter()
+        .filter(|sig| sig.kind != FunctionKind::Private)
+        .collect();
+
+    writeln!(output, "/// Bindings for `clarity_contracts/{}`", relative.display()).unwrap();
+    writeln!(output, "pub mod {} {{", module).unwrap();
+    writeln!(output, "    use super::*;\n").unwrap();
+    writeln!(output, "    pub const SOURCE: &str = {:?};\n", relative.display().to_string()).unwrap();
+
+    writeln!(output, "    pub const FUNCTIONS: &[FunctionSpec] = &[").unwrap();
+    for sig in &exported {
+        let args: Vec<String> = sig
+            .args
+            .iter()
+            .map(|(name, ty)| format!("({:?}, {:?})", name, ty.to_string()))
+            .collect();
+        writeln!(
+            output,
+            "        FunctionSpec {{ name: {:?}, read_only: {}, args: &[{}] }},",
+            sig.name,
+            sig.kind == FunctionKind::ReadOnly,
+            args.join(", ")
+        )
+        .unwrap();
+    }
+    writeln!(output, "    ];").unwrap();
+
+    for sig in &exported {
+        let params: Vec<String> = sig
+            .args
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", rust_ident(name), rust_type(ty)))
+            .collect();
+        let values: Vec<String> = sig
+            .args
+            .iter()
+            .map(|(name, ty)| to_clarity_value(&rust_ident(name), ty))
+            .collect();
+        let define = if sig.kind == FunctionKind::ReadOnly { "define-read-only" } else { "define-public" };
+        let arg_docs: Vec<String> = sig.args.iter().map(|(name, ty)| format!(" ({} {})", name, ty)).collect();
+
+        writeln!(output).unwrap();
+        writeln!(output, "    /// `({} ({}{}))`", define, sig.name, arg_docs.concat()).unwrap();
+        writeln!(output, "    pub fn {}({}) -> ContractCall {{", rust_ident(&sig.name), params.join(", ")).unwrap();
+        writeln!(output, "        ContractCall {{").unwrap();
+        writeln!(output, "            function_name: {:?},", sig.name).unwrap();
+        writeln!(output, "            read_only: {},", sig.kind == FunctionKind::ReadOnly).unwrap();
+        writeln!(output, "            args: vec![{}],", values.join(", ")).unwrap();
+        writeln!(output, "        }}").unwrap();
+        writeln!(output, "    }}").unwrap();
+    }
+    writeln!(output, "}}\n").unwrap();
+}
+
+fn rust_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .filter(|c| *c != '?' && *c != '!')
+        .map(|c| if c == '-' { '_' } else { c })
+        .collect();
+    match ident.as_str() {
+        "type" | "match" | "let" | "fn" | "as" | "in" | "ref" | "self" | "use" | "mod" | "loop" => format!("{}_", ident),
+        _ => ident,
+    }
+}
+
+fn rust_type(ty: &TypeSignature) -> &'static str {
+    match ty {
+        TypeSignature::Int => "i128",
+        TypeSignature::UInt => "u128",
+        TypeSignature::Bool => "bool",
+        TypeSignature::Principal => "PrincipalData",
+        TypeSignature::Buffer(_) => "Vec<u8>",
+        TypeSignature::StringAscii(_) | TypeSignature::StringUtf8(_) => "String",
+        _ => "ClarityValue",
+    }
+}
+
+fn to_clarity_value(ident: &str, ty: &TypeSignature) -> String {
+    match ty {
+        TypeSignature::Int => format!("ClarityValue::Int({})", ident),
+        TypeSignature::UInt => format!("ClarityValue::UInt({})", ident),
+        TypeSignature::Bool => format!("ClarityValue::Bool({})", ident),
+        TypeSignature::Principal => format!("ClarityValue::Principal({})", ident),
+        TypeSignature::Buffer(_) => format!("ClarityValue::Buffer({})", ident),
+        TypeSignature::StringAscii(_) => format!("ClarityValue::StringAscii({})", ident),
+        TypeSignature::StringUtf8(_) => format!("ClarityValue::StringUtf8({})", ident),
+        _ => ident.to_string(),
+    }
+}
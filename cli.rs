@@ -1,6 +1,38 @@
 use clap::{App, Arg, SubCommand};
+use std::path::PathBuf;
 use std::process;
-use zook_network::{bridge, staking, governance, query}; // Import Zook modules
+use std::sync::{Arc, Mutex};
+use zook_network::{bridge, staking, query}; // Import Zook modules
+use zook_network::bridge::bridge_logic::BridgeLimits;
+use zook_network::bridge::state_anchoring::StateAnchoring;
+use zook_network::governance::parameters::ParameterRegistry;
+use zook_network::governance::proposal_engine::{ProposalEngine, VoteChoice};
+use zook_network::governance::validator_policies::GovernanceValidatorPolicies;
+use zook_network::governance::validator_rewards_tokenomics::ValidatorRewards;
+use zook_network::validator::node_registration::ValidatorRegistry;
+
+/// Opens the node's proposal store, so CLI and API share one proposal lifecycle
+fn open_governance() -> ProposalEngine {
+    let governance_policies = Arc::new(GovernanceValidatorPolicies::new(1_000_000, 80, 70));
+    let parameters = ParameterRegistry::new()
+        .register(Arc::new(StateAnchoring::new()))
+        .register(Arc::new(Mutex::new(ValidatorRewards::new(100, PathBuf::from("validator_rewards.json")))))
+        .register(governance_policies.clone())
+        .register(Arc::new(Mutex::new(BridgeLimits::default())));
+    ProposalEngine::new(
+        Arc::new(parameters),
+        Arc::new(ValidatorRegistry::new(1_000_000, PathBuf::from("validator_registry.json"))),
+        governance_policies,
+        PathBuf::from("governance_proposals.json"),
+    )
+}
+
+fn proposal_id(matches: &clap::ArgMatches) -> u64 {
+    matches.value_of("proposal-id").unwrap().parse().unwrap_or_else(|_| {
+        println!("Proposal ID must be a number");
+        process::exit(1);
+    })
+}
 
 fn main() {
     let matches = App::new("Zook CLI")
@@ -50,17 +82,47 @@ fn main() {
                     SubCommand::with_name("vote")
                         .about("Vote on a governance proposal")
                         .arg(Arg::with_name("proposal-id").required(true).help("Proposal ID to vote on"))
-                        .arg(Arg::with_name("vote").required(true).help("Vote 'yes' or 'no'")),
+                        .arg(Arg::with_name("validator").required(true).help("Voting validator address"))
+                        .arg(Arg::with_name("vote").required(true).help("Vote 'yes', 'no' or 'abstain'")),
                 )
                 .subcommand(
                     SubCommand::with_name("propose")
-                        .about("Create a governance proposal")
-                        .arg(Arg::with_name("title").required(true).help("Title of the proposal"))
+                        .about("Create a draft governance proposal")
+                        .arg(Arg::with_name("proposer").required(true).help("Address of the proposer"))
+                        .arg(Arg::with_name("param").required(true).help("Parameter to change"))
+                        .arg(Arg::with_name("value").required(true).help("New parameter value"))
                         .arg(Arg::with_name("details").required(true).help("Details of the proposal")),
                 )
+                .subcommand(
+                    SubCommand::with_name("transition")
+                        .about("Move a proposal through its lifecycle")
+                        .arg(
+                            Arg::with_name("action")
+                                .required(true)
+                                .possible_values(&["activate", "finalize", "execute"])
+                                .help("Lifecycle action"),
+                        )
+                        .arg(Arg::with_name("proposal-id").required(true).help("Proposal ID")),
+                )
+                .subcommand(
+                    SubCommand::with_name("cancel")
+                        .about("Cancel a proposal as its proposer or, during the timelock, as a guardian")
+                        .arg(Arg::with_name("proposal-id").required(true).help("Proposal ID"))
+                        .arg(Arg::with_name("canceller").required(true).help("Address of the proposer or guardian")),
+                )
+                .subcommand(
+                    SubCommand::with_name("cancel-vote")
+                        .about("Back cancelling a queued proposal; a supermajority cancels it")
+                        .arg(Arg::with_name("proposal-id").required(true).help("Proposal ID"))
+                        .arg(Arg::with_name("validator").required(true).help("Voting validator address")),
+                )
                 .subcommand(
                     SubCommand::with_name("status")
                         .about("Check current proposals and voting results"),
+                )
+                .subcommand(
+                    SubCommand::with_name("parameters")
+                        .about("List governable parameters with their bounds"),
                 ),
         )
         .subcommand(
@@ -130,27 +192,107 @@ fn main() {
         },
         ("governance", Some(governance_matches)) => match governance_matches.subcommand() {
             ("vote", Some(vote_matches)) => {
-                let proposal_id = vote_matches.value_of("proposal-id").unwrap();
+                let proposal_id = proposal_id(vote_matches);
+                let validator = vote_matches.value_of("validator").unwrap();
                 let vote = vote_matches.value_of("vote").unwrap();
-                match governance::vote(proposal_id.parse().unwrap(), vote) {
-                    Ok(_) => println!("Successfully voted '{}' on proposal {}.", vote, proposal_id),
+                let choice = match vote {
+                    "yes" => VoteChoice::Yes,
+                    "no" => VoteChoice::No,
+                    "abstain" => VoteChoice::Abstain,
+                    _ => {
+                        println!("Vote must be 'yes', 'no' or 'abstain'");
+                        process::exit(1);
+                    }
+                };
+                match open_governance().cast_vote(proposal_id, validator, choice) {
+                    Ok(tally) => println!("Successfully voted '{}' on proposal {}. Tally: {:?}", vote, proposal_id, tally),
                     Err(e) => println!("Error voting on proposal: {}", e),
                 }
             }
             ("propose", Some(propose_matches)) => {
-                let title = propose_matches.value_of("title").unwrap();
+                let proposer = propose_matches.value_of("proposer").unwrap();
+                let param = propose_matches.value_of("param").unwrap();
+                let value = propose_matches.value_of("value").unwrap();
                 let details = propose_matches.value_of("details").unwrap();
-                match governance::propose(title, details) {
-                    Ok(_) => println!("Proposal '{}' created successfully.", title),
+                let Ok(value) = value.parse() else {
+                    println!("Value must be a number");
+                    process::exit(1);
+                };
+                match open_governance().submit_proposal(proposer.to_string(), details.to_string(), param.to_string(), value) {
+                    Ok(proposal_id) => println!("Draft proposal {} created successfully.", proposal_id),
                     Err(e) => println!("Error creating proposal: {}", e),
                 }
             }
+            ("transition", Some(transition_matches)) => {
+                let proposal_id = proposal_id(transition_matches);
+                let governance = open_governance();
+                let result = match transition_matches.value_of("action").unwrap() {
+                    "activate" => governance.activate_proposal(proposal_id).map(|_| ()),
+                    "finalize" => governance.finalize_proposal(proposal_id).map(|_| ()),
+                    _ => governance.execute_proposal(proposal_id),
+                };
+                match result.and_then(|_| governance.get_proposal(proposal_id)) {
+                    Ok(proposal) => println!("Proposal {} is now {:?}.", proposal_id, proposal.state),
+                    Err(e) => println!("Error updating proposal: {}", e),
+                }
+            }
+            ("cancel", Some(cancel_matches)) => {
+                let proposal_id = proposal_id(cancel_matches);
+                let canceller = cancel_matches.value_of("canceller").unwrap();
+                match open_governance().cancel_proposal(proposal_id, canceller) {
+                    Ok(_) => println!("Proposal {} cancelled.", proposal_id),
+                    Err(e) => println!("Error cancelling proposal: {}", e),
+                }
+            }
+            ("cancel-vote", Some(cancel_vote_matches)) => {
+                let proposal_id = proposal_id(cancel_vote_matches);
+                let validator = cancel_vote_matches.value_of("validator").unwrap();
+                match open_governance().vote_to_cancel(proposal_id, validator) {
+                    Ok(true) => println!("Proposal {} cancelled by supermajority.", proposal_id),
+                    Ok(false) => println!("Cancellation support recorded for proposal {}.", proposal_id),
+                    Err(e) => println!("Error voting to cancel proposal: {}", e),
+                }
+            }
             ("status", _) => {
-                match governance::status() {
-                    Ok(status) => println!("Governance Status: {:?}", status),
+                match open_governance().list_proposals(None) {
+                    Ok(proposals) => {
+                        for proposal in proposals {
+                            println!(
+                                "#{} [{:?}] {} = {} ({}) yes {} / no {} / abstain {}{}",
+                                proposal.proposal_id,
+                                proposal.state,
+                                proposal.param,
+                                proposal.value,
+                                proposal.description,
+                                proposal.tally.yes,
+                                proposal.tally.no,
+                                proposal.tally.abstain,
+                                proposal.eta.map(|eta| format!(", executable after {}", eta)).unwrap_or_default()
+                            );
+                        }
+                    }
                     Err(e) => println!("Error fetching governance status: {}", e),
                 }
             }
+            ("parameters", _) => {
+                match open_governance().parameters.list() {
+                    Ok(parameters) => {
+                        for parameter in parameters {
+                            println!(
+                                "{} ({}): {} {} [{}..={}] - {}",
+                                parameter.name,
+                                parameter.module,
+                                parameter.value,
+                                parameter.unit,
+                                parameter.min,
+                                parameter.max,
+                                parameter.description
+                            );
+                        }
+                    }
+                    Err(e) => println!("Error fetching governance parameters: {}", e),
+                }
+            }
             _ => println!("Invalid governance command"),
         },
         ("query", Some(query_matches)) => match query_matches.subcommand() {
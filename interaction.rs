@@ -1,7 +1,22 @@
 use serde_json::json;
-use reqwest::Client as HttpClient;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use crate::clarity::bindings::{proposal_voting, transaction_bridge, validator_rewards, zbtcz, ContractCall};
+use crate::clarity::evaluator::{ClarityVm, CONTRACTS_DIR};
+use crate::clarity::post_condition::{PostCondition, PostConditionMode, PostConditionPrincipal};
+use crate::clarity::receipt::TransactionReceipt;
+use crate::clarity::transaction::{SpendingCondition, StacksSigner, StacksTransaction};
+use crate::clarity::value::{ClarityValue, PrincipalData};
+use crate::http_client::EndpointPool;
+
+const CLARINET_MANIFEST: &str = "Clarinet.toml"; // Contract names and sources for `embedded`
+const ZBTCZ_TOKEN: &str = "zbtcz-token"; // define-fungible-token in tokens/zbtcz.clar
+
+// Bridge calls users may submit for the sponsor to pay
+const SPONSORED_ZBTCZ_FUNCTIONS: [&str; 2] = ["mint-zbtcz", "burn-zbtcz"];
+const SPONSORED_BRIDGE_FUNCTIONS: [&str; 2] = ["lock-btcz", "unlock-btcz"];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Principal(String);
@@ -10,20 +25,24 @@ impl Principal {
     pub fn new(address: String) -> Self {
         Principal(address)
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Response {
     pub success: bool,
     pub message: String,
-    pub data: Option<Value>,
+    pub data: Option<ClarityValue>,
 }
 
 impl Response {
     /// Converts the `Response` to a `Result` for easier error handling.
-    pub fn to_result(self) -> Result<Value, String> {
+    pub fn into_result(self) -> Result<ClarityValue, String> {
         if self.success {
-            Ok(self.data.unwrap_or_else(|| Value::Null))
+            self.data.ok_or_else(|| "Contract call returned no result".to_string())
         } else {
             Err(self.message)
         }
@@ -33,12 +52,17 @@ impl Response {
 /// Interactor for Clarity contract interactions
 #[derive(Clone, Debug)]
 pub struct ClarityInteractor {
-    pub api_url: String,
     pub sender: Principal,
-    pub zbtcz_address: String,
-    pub gbtcz_address: String,
-    pub governance_address: String,
-    pub client: HttpClient,
+    pub zbtcz_address: String,      // tokens/zbtcz.clar
+    pub gbtcz_address: String,      // governance/staking_rewards.clar
+    pub governance_address: String, // governance/proposal_voting.clar
+    pub bridge_address: String,     // bridge/transaction_bridge.clar
+    pub rewards_address: String,    // governance/validator_rewards.clar
+    pub signer: Option<StacksSigner>, // Key used for state-changing calls
+    pub sponsor: Option<StacksSigner>, // Key that pays fees for user-signed bridge calls
+    pub fee: u64,                     // Fee per transaction in micro-STX
+    pub embedded: Option<Arc<Mutex<ClarityVm>>>, // Runs calls in-process instead of against a node
+    pub endpoints: EndpointPool, // Stacks node RPCs; each must also serve the /extended API for receipts
 }
 
 impl ClarityInteractor {
@@ -48,84 +72,378 @@ impl ClarityInteractor {
         zbtcz_address: &str,
         gbtcz_address: &str,
         governance_address: &str,
+        bridge_address: &str,
+        rewards_address: &str,
     ) -> Self {
         Self {
-            api_url: api_url.to_string(),
             sender,
             zbtcz_address: zbtcz_address.to_string(),
             gbtcz_address: gbtcz_address.to_string(),
             governance_address: governance_address.to_string(),
-            client: HttpClient::new(),
+            bridge_address: bridge_address.to_string(),
+            rewards_address: rewards_address.to_string(),
+            signer: None,
+            sponsor: None,
+            fee: 10_000,
+            embedded: None,
+            endpoints: EndpointPool::single("stacks", api_url),
         }
     }
 
-    pub async fn mint_zbtcz(&self, amount: u128) -> Response {
-        self.call_contract(&self.zbtcz_address, "mint-zbtcz", json!([amount])).await
+    /// Interactor backed by an embedded evaluator with every contract in Clarinet.toml
+    /// deployed under `deployer`, by the same names and from the same sources Clarinet uses
+    pub fn embedded(deployer: &str) -> Result<Self, String> {
+        let manifest = fs::read_to_string(CLARINET_MANIFEST)
+            .map_err(|e| format!("Failed to read {}: {}", CLARINET_MANIFEST, e))?;
+        let manifest: toml::Value =
+            toml::from_str(&manifest).map_err(|e| format!("Unreadable {}: {}", CLARINET_MANIFEST, e))?;
+        let contracts = manifest
+            .get("contracts")
+            .and_then(toml::Value::as_table)
+            .ok_or_else(|| format!("{} lists no contracts", CLARINET_MANIFEST))?;
+        let contract = |name: &str| match contracts.contains_key(name) {
+            true => Ok(format!("{}.{}", deployer, name)),
+            false => Err(format!("{} has no {} contract", CLARINET_MANIFEST, name)),
+        };
+        let interactor = Self::new(
+            "embedded",
+            Principal::new(deployer.to_string()),
+            &contract("zbtcz")?,
+            &contract("gbtcz")?,
+            &contract("governance")?,
+            &contract("bridge")?,
+            &contract("validator-rewards")?,
+        );
+
+        let mut vm = ClarityVm::new();
+        for (name, path) in contracts {
+            let path = path
+                .as_str()
+                .ok_or_else(|| format!("Contract {} in {} has no source path", name, CLARINET_MANIFEST))?;
+            // Sources are keyed relative to the contracts directory, as in the generated bindings
+            let source = path.strip_prefix(CONTRACTS_DIR).map_or(path, |rest| rest.trim_start_matches('/'));
+            vm.deploy_file(&format!("{}.{}", deployer, name), source)?;
+        }
+        Ok(interactor.with_embedded_vm(Arc::new(Mutex::new(vm))))
     }
 
-    pub async fn burn_zbtcz(&self, ids: Vec<u128>) -> Response {
-        self.call_contract(&self.zbtcz_address, "burn-zbtcz", json!([ids])).await
+    pub fn with_embedded_vm(mut self, vm: Arc<Mutex<ClarityVm>>) -> Self {
+        self.embedded = Some(vm);
+        self
     }
 
-    pub async fn stake_gbtcz(&self, amount: u128) -> Response {
-        self.call_contract(&self.gbtcz_address, "stake-gbtcz", json!([amount])).await
+    /// Replaces the single node given to `new` with a pool of failover endpoints
+    pub fn with_endpoints(mut self, endpoints: EndpointPool) -> Self {
+        self.endpoints = endpoints;
+        self
     }
 
-    pub async fn unstake_gbtcz(&self, amount: u128) -> Response {
-        self.call_contract(&self.gbtcz_address, "unstake-gbtcz", json!([amount])).await
+    /// Configures the key that signs state-changing calls; it also becomes the sender
+    pub fn with_signer(mut self, signer: StacksSigner) -> Self {
+        self.sender = Principal::new(signer.address());
+        self.signer = Some(signer);
+        self
     }
 
-    pub async fn lock_btcz(&self, tx_id: u128, amount: u128) -> Response {
-        self.call_contract(&self.governance_address, "lock-btcz", json!([tx_id, amount])).await
+    /// Configures the key that pays for sponsored transactions
+    pub fn with_sponsor(mut self, sponsor: StacksSigner) -> Self {
+        self.sponsor = Some(sponsor);
+        self
     }
 
-    pub async fn unlock_btcz(&self, tx_id: u128) -> Response {
-        self.call_contract(&self.governance_address, "unlock-btcz", json!([tx_id])).await
+    pub async fn mint_zbtcz(
+        &self,
+        amount: u128,
+        block_height: u128,
+        merkle_root: Vec<u8>,
+    ) -> Result<TransactionReceipt, String> {
+        // Minting moves nothing out of any account, so deny mode forbids every outflow
+        let call = zbtcz::mint_zbtcz(amount, block_height, merkle_root);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.zbtcz_address, zbtcz::SOURCE, call, post_conditions).await
     }
 
-    /// Distributes validator rewards.
-    pub async fn distribute_rewards(&self) -> Result<u64, String> {
-        let response = self
-            .call_contract(&self.governance_address, "distribute-rewards", json!({}))
-            .await;
+    /// Records a finalized L2 state so mints against its merkle root are accepted
+    pub async fn finalize_state(&self, block_height: u128, merkle_root: Vec<u8>) -> Result<TransactionReceipt, String> {
+        let call = zbtcz::finalize_state(block_height, merkle_root);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.zbtcz_address, zbtcz::SOURCE, call, post_conditions).await
+    }
 
-        response
-            .to_result() // Convert to Result for error handling
-            .and_then(|data| data.as_u64().ok_or_else(|| "Failed to parse rewards".to_string()))
+    pub async fn burn_zbtcz(&self, amount: u128, tx_id: u128) -> Result<TransactionReceipt, String> {
+        let call = zbtcz::burn_zbtcz(amount, tx_id);
+        let post_conditions = vec![
+            PostCondition::no_stx_sent(),
+            PostCondition::ft_sent_exactly(PostConditionPrincipal::Origin, &self.zbtcz_address, ZBTCZ_TOKEN, amount)?,
+        ];
+        self.submit_transaction(&self.zbtcz_address, zbtcz::SOURCE, call, post_conditions).await
+    }
+
+    pub async fn lock_btcz(&self, tx_id: u128, amount: u128) -> Result<TransactionReceipt, String> {
+        let call = transaction_bridge::lock_btcz(tx_id, amount);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.bridge_address, transaction_bridge::SOURCE, call, post_conditions).await
+    }
+
+    pub async fn unlock_btcz(&self, tx_id: u128) -> Result<TransactionReceipt, String> {
+        let call = transaction_bridge::unlock_btcz(tx_id);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.bridge_address, transaction_bridge::SOURCE, call, post_conditions).await
+    }
+
+    /// Opens a proposal in the governance contract, which holds one proposal at a time
+    pub async fn propose_parameter_change(&self, param_name: &str, value: u128) -> Result<TransactionReceipt, String> {
+        let call = proposal_voting::propose(param_name.to_string(), value);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.governance_address, proposal_voting::SOURCE, call, post_conditions).await
+    }
+
+    pub async fn vote_on_proposal(&self, vote_for: bool) -> Result<TransactionReceipt, String> {
+        let call = proposal_voting::vote(vote_for);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.governance_address, proposal_voting::SOURCE, call, post_conditions).await
+    }
+
+    /// Closes the contract's current proposal, applying it on-chain if approved
+    pub async fn finalize_proposal(&self) -> Result<TransactionReceipt, String> {
+        let call = proposal_voting::finalize_proposal();
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.governance_address, proposal_voting::SOURCE, call, post_conditions).await
+    }
+
+    /// Submits the reward payout for a validator; the amount is in the receipt once confirmed.
+    pub async fn distribute_rewards(&self, validator: &str) -> Result<TransactionReceipt, String> {
+        let call = validator_rewards::distribute_rewards(PrincipalData::parse(validator)?);
+        let post_conditions = vec![PostCondition::no_stx_sent()];
+        self.submit_transaction(&self.rewards_address, validator_rewards::SOURCE, call, post_conditions).await
     }
 
     /// Queries total rewards distributed.
     pub async fn query_rewards(&self) -> Result<u64, String> {
         let response = self
-            .call_contract(&self.governance_address, "query-rewards", json!({}))
+            .call_contract(&self.rewards_address, validator_rewards::get_total_rewards())
             .await;
 
         response
-            .to_result() // Convert to Result for error handling
-            .and_then(|data| data.as_u64().ok_or_else(|| "Failed to parse rewards".to_string()))
+            .into_result() // Convert to Result for error handling
+            .and_then(|data| data.expect_ok()?.expect_u64())
     }
 
-    async fn call_contract(
+    /// Fetches the latest status of a submitted transaction
+    pub async fn refresh_receipt(&self, receipt: &TransactionReceipt) -> Result<TransactionReceipt, String> {
+        if let Some(vm) = &self.embedded {
+            let vm = vm.lock().map_err(|_| "Mutex lock failed")?;
+            return Ok(vm.receipt(&receipt.txid).cloned().unwrap_or_else(|| receipt.clone()));
+        }
+
+        let resp = self
+            .endpoints
+            .send(|client, url| client.get(format!("{}/extended/v1/tx/0x{}", url, receipt.txid)))
+            .await?;
+
+        // The API only learns about a transaction once the node relays it
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(receipt.clone());
+        }
+        if !resp.status().is_success() {
+            return Err(format!("Error: {}", resp.status()));
+        }
+
+        let body = resp.json::<Value>().await.map_err(|err| format!("Invalid receipt: {}", err))?;
+        TransactionReceipt::from_api(&receipt.contract_source, &body)
+    }
+
+    /// Decodes a user-signed sponsored call to the zBTCZ or bridge contract and checks
+    /// the user's signature; anything else is refused before the sponsor pays for it
+    pub fn decode_sponsored(&self, raw: &[u8]) -> Result<StacksTransaction, String> {
+        let zbtcz_contract = PrincipalData::parse(&self.zbtcz_address)?;
+        let bridge_contract = PrincipalData::parse(&self.bridge_address)?;
+        let transaction = StacksTransaction::deserialize(raw, |contract, function| {
+            let allowed: &[&'static str] = if *contract == zbtcz_contract {
+                &SPONSORED_ZBTCZ_FUNCTIONS
+            } else if *contract == bridge_contract {
+                &SPONSORED_BRIDGE_FUNCTIONS
+            } else {
+                &[]
+            };
+            allowed.iter().copied().find(|name| *name == function)
+        })?;
+
+        if transaction.sponsor.is_none() {
+            return Err("Transaction is not marked as sponsored".to_string());
+        }
+        if let Some(sponsor) = &self.sponsor {
+            if transaction.network != sponsor.network {
+                return Err("Transaction targets a different Stacks network".to_string());
+            }
+        }
+        transaction.verify_origin()?;
+        Ok(transaction)
+    }
+
+    /// Signs as sponsor and broadcasts a transaction accepted by `decode_sponsored`
+    pub async fn submit_sponsored(&self, mut transaction: StacksTransaction) -> Result<TransactionReceipt, String> {
+        let contract_id = transaction.contract.to_string();
+        let contract_source = if contract_id == self.zbtcz_address {
+            zbtcz::SOURCE
+        } else {
+            transaction_bridge::SOURCE
+        };
+
+        if let Some(vm) = &self.embedded {
+            let mut vm = vm.lock().map_err(|_| "Mutex lock failed")?;
+            return vm.call_public_with_post_conditions(
+                &transaction.origin_address(),
+                &contract_id,
+                &transaction.call,
+                transaction.post_condition_mode,
+                &transaction.post_conditions,
+            );
+        }
+
+        let sponsor = self.sponsor.as_ref().ok_or("No sponsor key configured")?;
+        let nonce = self.account_nonce(&sponsor.address()).await?;
+        transaction.sign_sponsor(sponsor, nonce, self.fee)?;
+        transaction.verify()?;
+        self.broadcast(&transaction, contract_source).await
+    }
+
+    /// STX balance of `address` in micro-STX
+    pub async fn account_balance(&self, address: &str) -> Result<u128, String> {
+        let account = self.account(address).await?;
+        let balance = account["balance"].as_str().ok_or("Account response is missing balance")?;
+        u128::from_str_radix(balance.trim_start_matches("0x"), 16).map_err(|_| format!("Invalid balance {}", balance))
+    }
+
+    /// Signs and broadcasts a contract call in deny mode under `post_conditions`,
+    /// returning its pending receipt
+    async fn submit_transaction(
         &self,
-        contract_address: &str,
-        method: &str,
-        args: Value,
-    ) -> Response {
-        let endpoint = format!("{}/v2/contracts/call-read/{}/{}", self.api_url, contract_address, method);
+        contract_id: &str,
+        contract_source: &str,
+        call: ContractCall,
+        post_conditions: Vec<PostCondition>,
+    ) -> Result<TransactionReceipt, String> {
+        if let Some(vm) = &self.embedded {
+            let mut vm = vm.lock().map_err(|_| "Mutex lock failed")?;
+            return vm.call_public_with_post_conditions(
+                self.sender.as_str(),
+                contract_id,
+                &call,
+                PostConditionMode::Deny,
+                &post_conditions,
+            );
+        }
+
+        let signer = self.signer.as_ref().ok_or("No signing key configured for contract calls")?;
+        let nonce = self.account_nonce(&signer.address()).await?;
+
+        let origin = SpendingCondition::new(signer.hash160(), nonce, self.fee);
+        let mut transaction = StacksTransaction::contract_call(signer.network, origin, contract_id, call)?
+            .with_post_conditions(post_conditions);
+        transaction.sign(signer)?;
+        transaction.verify()?; // Never broadcast a transaction the node would reject as unsigned
+        self.broadcast(&transaction, contract_source).await
+    }
+
+    async fn broadcast(&self, transaction: &StacksTransaction, contract_source: &str) -> Result<TransactionReceipt, String> {
+        // Rebroadcasting the same signed bytes to another node is safe: the txid is fixed
+        let body = transaction.serialize();
+        let resp = self
+            .endpoints
+            .send(|client, url| {
+                client
+                    .post(format!("{}/v2/transactions", url))
+                    .header("Content-Type", "application/octet-stream")
+                    .body(body.clone())
+            })
+            .await?;
+
+        if !resp.status().is_success() {
+            let reason = resp.text().await.unwrap_or_default();
+            return Err(format!("Transaction rejected: {}", reason));
+        }
+
+        let accepted = resp
+            .json::<String>()
+            .await
+            .map_err(|err| format!("Invalid broadcast response: {}", err))?;
+        let txid = transaction.txid();
+        if accepted.trim_start_matches("0x") != txid {
+            return Err(format!("Node accepted unexpected txid {}", accepted));
+        }
+        Ok(TransactionReceipt::pending(txid, contract_source))
+    }
+
+    async fn account_nonce(&self, address: &str) -> Result<u64, String> {
+        let account = self.account(address).await?;
+        account["nonce"].as_u64().ok_or_else(|| "Account response is missing nonce".to_string())
+    }
+
+    async fn account(&self, address: &str) -> Result<Value, String> {
+        if self.embedded.is_some() {
+            return Err("The embedded evaluator does not track STX accounts".to_string());
+        }
+        self.endpoints
+            .send(|client, url| client.get(format!("{}/v2/accounts/{}?proof=0", url, address)))
+            .await?
+            .json::<Value>()
+            .await
+            .map_err(|err| format!("Invalid account response: {}", err))
+    }
+
+    async fn call_contract(&self, contract_id: &str, call: ContractCall) -> Response {
+        if let Some(vm) = &self.embedded {
+            let result = match vm.lock() {
+                Ok(vm) => vm.call_read_only(self.sender.as_str(), contract_id, &call),
+                Err(_) => Err("Mutex lock failed".to_string()),
+            };
+            return match result {
+                Ok(value) => Response {
+                    success: true,
+                    message: "Contract call successful".to_string(),
+                    data: Some(value),
+                },
+                Err(err) => Response {
+                    success: false,
+                    message: format!("Contract call rejected: {}", err),
+                    data: None,
+                },
+            };
+        }
+
+        let (contract_address, contract_name) = match contract_id.split_once('.') {
+            Some(parts) => parts,
+            None => {
+                return Response {
+                    success: false,
+                    message: format!("Invalid contract identifier: {}", contract_id),
+                    data: None,
+                }
+            }
+        };
+
         let payload = json!({
             "sender": self.sender.0,
-            "arguments": args,
+            "arguments": call.args.iter().map(ClarityValue::to_hex).collect::<Vec<_>>(),
         });
 
-        match self.client.post(&endpoint).json(&payload).send().await {
+        let resp = self
+            .endpoints
+            .send(|client, url| {
+                client
+                    .post(format!(
+                        "{}/v2/contracts/call-read/{}/{}/{}",
+                        url, contract_address, contract_name, call.function_name
+                    ))
+                    .json(&payload)
+            })
+            .await;
+        match resp {
             Ok(resp) => {
                 if resp.status().is_success() {
-                    let data = resp.json::<Value>().await.unwrap_or_default();
-                    Response {
-                        success: true,
-                        message: "Contract call successful".to_string(),
-                        data: Some(data),
-                    }
+                    let body = resp.json::<Value>().await.unwrap_or_default();
+                    Self::parse_call_result(&body)
                 } else {
                     Response {
                         success: false,
@@ -136,7 +454,39 @@ impl ClarityInteractor {
             }
             Err(err) => Response {
                 success: false,
-                message: format!("HTTP request failed: {}", err),
+                message: err,
+                data: None,
+            },
+        }
+    }
+
+    /// Decodes the `{"okay": .., "result": "0x.."}` body returned by the Stacks node
+    fn parse_call_result(body: &Value) -> Response {
+        if !body["okay"].as_bool().unwrap_or(false) {
+            return Response {
+                success: false,
+                message: format!(
+                    "Contract call rejected: {}",
+                    body["cause"].as_str().unwrap_or("unknown cause")
+                ),
+                data: None,
+            };
+        }
+
+        match body["result"].as_str().map(ClarityValue::from_hex) {
+            Some(Ok(value)) => Response {
+                success: true,
+                message: "Contract call successful".to_string(),
+                data: Some(value),
+            },
+            Some(Err(err)) => Response {
+                success: false,
+                message: format!("Failed to decode contract result: {}", err),
+                data: None,
+            },
+            None => Response {
+                success: false,
+                message: "Contract call returned no result".to_string(),
                 data: None,
             },
         }
@@ -17,7 +17,7 @@ struct StateResponse {
 }
 
 #[derive(Debug)]
-struct APIError(String);
+struct APIError(#[allow(dead_code)] String); // Only surfaces through Debug in warp's rejection logs
 
 impl warp::reject::Reject for APIError {}
 
@@ -31,7 +31,7 @@ impl AnchoringAPI {
         Self { cross_layer_sync }
     }
 
-    pub fn routes(&self) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl Reply,)> {
         let api = warp::path("anchoring");
 
         // Endpoint to anchor a state
@@ -49,6 +49,8 @@ impl AnchoringAPI {
                             total_transactions: state.merkle_proof.len() as u64, // Example calculation
                             timestamp: state.timestamp,
                             compliance: state.validator_compliance,
+                            governance_root: state.governance_root.clone(),
+                            treasury_balance: state.treasury_balance,
                         };
                         match cross_layer_sync.anchor_state(
                             state_summary,
@@ -83,6 +85,6 @@ impl AnchoringAPI {
         };
 
         // Combine endpoints
-        api.and(anchor_state.or(get_latest_state))
+        api.and(anchor_state.or(get_latest_state)).boxed()
     }
 }
@@ -1,3 +1,4 @@
+use serde::Deserialize;
 use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::Mutex as TokioMutex;
@@ -6,11 +7,13 @@ use warp::{Filter, Rejection, Reply};
 use crate::models::{ApiKey, BurnRequest, MintRequest};
 use crate::ledger::BridgeLedger;
 use crate::errors::CustomError;
+use crate::bridge::sponsorship::SponsorshipService;
 
 // Adapter Layer for Mutex
 #[derive(Clone)]
 pub enum MutexAdapter<T: Clone> {
     Tokio(Arc<TokioMutex<T>>),
+    #[allow(dead_code)] // The node only runs the bridge ledger behind a tokio mutex
     Std(Arc<StdMutex<T>>),
 }
 
@@ -19,6 +22,7 @@ impl<T: Clone> MutexAdapter<T> {
         Self::Tokio(Arc::new(TokioMutex::new(inner)))
     }
 
+    #[allow(dead_code)]
     pub fn new_std(inner: T) -> Self {
         Self::Std(Arc::new(StdMutex::new(inner)))
     }
@@ -32,9 +36,15 @@ impl<T: Clone> MutexAdapter<T> {
     }
 }
 
+#[derive(Deserialize)]
+struct SponsorRequest {
+    transaction: String, // Hex-encoded transaction signed by the user in sponsored mode
+}
+
 pub struct BridgeAPI {
     api_keys: HashSet<ApiKey>,
     bridge_ledger: MutexAdapter<BridgeLedger>,
+    sponsorship: Option<Arc<SponsorshipService>>,
 }
 
 impl BridgeAPI {
@@ -42,10 +52,17 @@ impl BridgeAPI {
         Self {
             api_keys,
             bridge_ledger,
+            sponsorship: None,
         }
     }
 
-    pub fn routes(&self) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    /// Enables `/bridge/sponsor` for user-signed transactions
+    pub fn with_sponsorship(mut self, sponsorship: Arc<SponsorshipService>) -> Self {
+        self.sponsorship = Some(sponsorship);
+        self
+    }
+
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl Reply,)> {
         let api_keys = self.api_keys.clone();
         let bridge_ledger = self.bridge_ledger.clone();
 
@@ -77,7 +94,49 @@ impl BridgeAPI {
                 }
             });
 
-        mint.or(burn)
+        let sponsorship = self.sponsorship.clone();
+        let with_sponsorship = warp::any().map(move || sponsorship.clone()).and_then(
+            |sponsorship: Option<Arc<SponsorshipService>>| async move {
+                sponsorship.ok_or_else(|| warp::reject::custom(CustomError("Sponsorship is not configured".to_string())))
+            },
+        );
+
+        let sponsor = warp::post()
+            .and(warp::path!("bridge" / "sponsor"))
+            .and(warp::body::json())
+            .and(Self::with_auth(api_keys.clone()))
+            .and(with_sponsorship.clone())
+            .and_then(|req: SponsorRequest, _auth: ApiKey, sponsorship: Arc<SponsorshipService>| async move {
+                let sponsored = sponsorship
+                    .sponsor(&req.transaction)
+                    .await
+                    .map_err(|e| warp::reject::custom(CustomError(e)))?;
+                Ok::<_, Rejection>(warp::reply::json(&sponsored))
+            });
+
+        let sponsor_status = warp::get()
+            .and(warp::path!("bridge" / "sponsor" / "status"))
+            .and(with_sponsorship.clone())
+            .and_then(|sponsorship: Arc<SponsorshipService>| async move {
+                let status = sponsorship.status().map_err(|e| warp::reject::custom(CustomError(e)))?;
+                Ok::<_, Rejection>(warp::reply::json(&serde_json::json!({
+                    "status": status,
+                    "quota": sponsorship.quota(),
+                })))
+            });
+
+        let sponsor_quota = warp::get()
+            .and(warp::path!("bridge" / "sponsor" / "quota" / String))
+            .and(with_sponsorship)
+            .and_then(|address: String, sponsorship: Arc<SponsorshipService>| async move {
+                let remaining = sponsorship.remaining_quota(&address).await;
+                Ok::<_, Rejection>(warp::reply::json(&serde_json::json!({
+                    "address": address,
+                    "remaining": remaining,
+                })))
+            });
+
+        mint.or(burn).or(sponsor).or(sponsor_status).or(sponsor_quota).boxed()
     }
 
     fn with_auth(
@@ -106,14 +165,16 @@ impl BridgeAPI {
         _auth: ApiKey,
         bridge_ledger: Arc<TokioMutex<BridgeLedger>>,
     ) -> Result<impl Reply, Rejection> {
+        let merkle_root = hex::decode(&request.merkle_root)
+            .map_err(|_| warp::reject::custom(CustomError("Invalid merkle root".to_string())))?;
         let mut ledger = bridge_ledger.lock().await;
 
-        ledger
-            .lock_btcz(request.address.clone(), request.amount)
+        let txid = ledger
+            .lock_btcz(request.address.clone(), request.amount, request.block_height, merkle_root)
             .await
             .map_err(|e| warp::reject::custom(CustomError(e.to_string())))?;
 
-        Ok(warp::reply::json(&"Mint successfully processed"))
+        Ok(warp::reply::json(&serde_json::json!({ "status": "Mint submitted", "txid": txid })))
     }
 
     async fn handle_burn(
@@ -123,7 +184,7 @@ impl BridgeAPI {
     ) -> Result<impl Reply, Rejection> {
         let mut ledger = bridge_ledger.lock().await;
 
-        ledger
+        let txid = ledger
             .burn_zbtcz(
                 request.address.clone(),
                 request.amount,
@@ -132,6 +193,6 @@ impl BridgeAPI {
             .await
             .map_err(|e| warp::reject::custom(CustomError(e.to_string())))?;
 
-        Ok(warp::reply::json(&"Burn successfully processed"))
+        Ok(warp::reply::json(&serde_json::json!({ "status": "Burn submitted", "txid": txid })))
     }
 }
@@ -0,0 +1,125 @@
+use warp::Filter;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::governance::delegation::{Delegation, DelegationRegistry};
+use crate::api::security::{with_auth, with_rate_limit, ApiKey, RateLimiter};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegateRequest {
+    pub delegator: String,
+    pub validator: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndelegateRequest {
+    pub delegator: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationResponse {
+    pub success: bool,
+    pub delegation: Option<Delegation>,
+    pub message: String,
+}
+
+/// Delegation of gBTCZ voting power to validators
+pub struct DelegationAPI {
+    delegations: Arc<DelegationRegistry>,
+    api_keys: Arc<HashMap<String, ApiKey>>,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl DelegationAPI {
+    pub fn new(
+        delegations: Arc<DelegationRegistry>,
+        api_keys: Arc<HashMap<String, ApiKey>>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            delegations,
+            api_keys,
+            rate_limiter,
+        }
+    }
+
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl warp::Reply,)> {
+        let delegations = self.delegations.clone();
+        let with_delegations = warp::any().map(move || delegations.clone());
+
+        let delegate = warp::post()
+            .and(warp::path!("governance" / "delegate"))
+            .and(warp::body::json())
+            .and(with_auth(self.api_keys.clone()))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_delegations.clone())
+            .map(|request: DelegateRequest, auth: ApiKey, _rate_limit: (), delegations: Arc<DelegationRegistry>| {
+                let result = auth
+                    .authorize(&request.delegator)
+                    .and_then(|_| delegations.delegate(&request.delegator, &request.validator))
+                    .and_then(|previous| Ok((previous, delegations.get_delegation(&request.delegator)?)));
+                let response = match result {
+                    Ok((previous, delegation)) => DelegationResponse {
+                        success: true,
+                        delegation,
+                        message: match previous {
+                            Some(previous) => format!("Redelegated from {}", previous),
+                            None => "Delegation recorded".to_string(),
+                        },
+                    },
+                    Err(err) => DelegationResponse {
+                        success: false,
+                        delegation: None,
+                        message: err,
+                    },
+                };
+                warp::reply::json(&response)
+            });
+
+        let undelegate = warp::post()
+            .and(warp::path!("governance" / "undelegate"))
+            .and(warp::body::json())
+            .and(with_auth(self.api_keys.clone()))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_delegations.clone())
+            .map(|request: UndelegateRequest, auth: ApiKey, _rate_limit: (), delegations: Arc<DelegationRegistry>| {
+                let result = auth.authorize(&request.delegator).and_then(|_| delegations.undelegate(&request.delegator));
+                let response = match result {
+                    Ok(delegation) => DelegationResponse {
+                        success: true,
+                        delegation: Some(delegation),
+                        message: "Delegation removed".to_string(),
+                    },
+                    Err(err) => DelegationResponse {
+                        success: false,
+                        delegation: None,
+                        message: err,
+                    },
+                };
+                warp::reply::json(&response)
+            });
+
+        let list = warp::get()
+            .and(warp::path!("governance" / "delegations"))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_delegations.clone())
+            .map(|_rate_limit: (), delegations: Arc<DelegationRegistry>| match delegations.list_delegations() {
+                Ok(delegations) => warp::reply::json(&json!({ "success": true, "delegations": delegations })),
+                Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+            });
+
+        let validator_power = warp::get()
+            .and(warp::path!("governance" / "delegations" / String))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_delegations)
+            .map(|validator: String, _rate_limit: (), delegations: Arc<DelegationRegistry>| {
+                match delegations.delegated_power(&validator, &Default::default(), None) {
+                    Ok(power) => warp::reply::json(&json!({ "success": true, "delegated": power })),
+                    Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+                }
+            });
+
+        delegate.or(undelegate).or(list).or(validator_power).boxed()
+    }
+}
@@ -0,0 +1,38 @@
+use crate::http_client::EndpointPool;
+use serde_json::json;
+use warp::{Filter, Reply};
+
+/// Reports the circuit-breaker state of every outbound endpoint pool
+#[derive(Clone)]
+pub struct EndpointHealthAPI {
+    pools: Vec<EndpointPool>,
+}
+
+impl EndpointHealthAPI {
+    pub fn new(pools: Vec<EndpointPool>) -> Self {
+        Self { pools }
+    }
+
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl Reply,)> {
+        let pools = self.pools.clone();
+        warp::path!("health" / "endpoints")
+            .and(warp::get())
+            .map(move || {
+                let reports: Result<Vec<_>, String> = pools.iter().map(EndpointPool::report).collect();
+                match reports {
+                    Ok(reports) => warp::reply::with_status(
+                        warp::reply::json(&json!({
+                            "healthy": reports.iter().all(|r| r.healthy),
+                            "pools": reports,
+                        })),
+                        warp::http::StatusCode::OK,
+                    ),
+                    Err(e) => warp::reply::with_status(
+                        warp::reply::json(&json!({ "error": e })),
+                        warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    ),
+                }
+            })
+            .boxed()
+    }
+}
@@ -0,0 +1,57 @@
+use crate::bridge::event_observer::StacksEventObserver;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use warp::{Filter, Rejection, Reply};
+
+/// Endpoints a Stacks node posts to when this service is configured as an `[[events_observer]]`
+#[derive(Clone)]
+pub struct EventObserverAPI {
+    observer: Arc<StacksEventObserver>,
+}
+
+impl EventObserverAPI {
+    pub fn new(observer: Arc<StacksEventObserver>) -> Self {
+        Self { observer }
+    }
+
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl Reply,)> {
+        let new_block = self.deliver(warp::path!("new_block").boxed(), "/new_block");
+        let new_burn_block = self.deliver(warp::path!("new_burn_block").boxed(), "/new_burn_block");
+        let new_mempool_tx = self.deliver(warp::path!("new_mempool_tx").boxed(), "/new_mempool_tx");
+        let drop_mempool_tx = self.deliver(warp::path!("drop_mempool_tx").boxed(), "/drop_mempool_tx");
+        let new_microblocks = self.deliver(warp::path!("new_microblocks").boxed(), "/new_microblocks");
+        let attachments = self.deliver(warp::path!("attachments" / "new").boxed(), "/attachments/new");
+
+        new_block
+            .or(new_burn_block)
+            .or(new_mempool_tx)
+            .or(drop_mempool_tx)
+            .or(new_microblocks)
+            .or(attachments)
+            .boxed()
+    }
+
+    fn deliver(
+        &self,
+        path: warp::filters::BoxedFilter<()>,
+        name: &'static str,
+    ) -> impl Filter<Extract = (warp::reply::Json,), Error = Rejection> + Clone {
+        let observer = self.observer.clone();
+        warp::post()
+            .and(path)
+            .and(warp::body::json())
+            .and_then(move |payload: Value| {
+                let observer = observer.clone();
+                async move {
+                    // Always acknowledge: the node retries a failed delivery forever and stalls its own progress
+                    match observer.handle(name, payload).await {
+                        Ok(outcome) => Ok::<_, Rejection>(warp::reply::json(&outcome)),
+                        Err(e) => {
+                            eprintln!("Failed to process {} delivery: {}", name, e);
+                            Ok(warp::reply::json(&json!({ "status": "error", "message": e })))
+                        }
+                    }
+                }
+            })
+    }
+}
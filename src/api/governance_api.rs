@@ -2,20 +2,64 @@ use warp::Filter;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::collections::HashMap;
-use crate::governance::cross_layer_governance::{CrossLayerGovernance, GovernanceProposal};
+use crate::bridge::state_anchoring::L2StateSummary;
+use crate::governance::audit_log::{self, GovernanceAuditLog};
+use crate::governance::dry_run::DryRunReport;
+use crate::governance::proposal_engine::{GovernanceProposal, ProposalAction, ProposalEngine, ProposalState, ProposalTally, VoteChoice};
 use crate::api::security::{with_auth, with_rate_limit, ApiKey};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposalRequest {
+    pub proposer: String,
     pub description: String,
-    pub param: String,
-    pub value: u64,
+    #[serde(default)]
+    pub param: Option<String>, // Single change; use `actions` for several
+    #[serde(default)]
+    pub value: Option<u64>,
+    #[serde(default)]
+    pub actions: Vec<ProposalAction>,
+    #[serde(default)]
+    pub tally_strategy: Option<String>, // Defaults to the strategy the changes require, if any
+}
+
+impl ProposalRequest {
+    /// The requested changes in order, with a single `param`/`value` change first
+    fn changes(&self) -> Result<Vec<ProposalAction>, String> {
+        let single = match (&self.param, self.value) {
+            (Some(param), Some(value)) => Some(ProposalAction::new(param.clone(), value)),
+            (None, None) => None,
+            _ => return Err("param and value must be given together".to_string()),
+        };
+        Ok(single.into_iter().chain(self.actions.iter().cloned()).collect())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposalResponse {
     pub success: bool,
     pub proposal_id: Option<u64>,
+    pub state: Option<ProposalState>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteRequest {
+    pub proposal_id: u64,
+    pub validator: String,
+    pub choice: VoteChoice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegatorVoteRequest {
+    pub proposal_id: u64,
+    pub delegator: String,
+    pub choice: VoteChoice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteResponse {
+    pub success: bool,
+    pub tally: Option<ProposalTally>,
     pub message: String,
 }
 
@@ -25,9 +69,70 @@ pub struct ProposalsListResponse {
     pub proposals: Vec<GovernanceProposal>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRequest {
+    pub canceller: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelVoteRequest {
+    pub proposal_id: u64,
+    pub validator: String,
+}
+
+/// Proposals waiting out the timelock and those already executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelockResponse {
+    pub success: bool,
+    pub queued: Vec<GovernanceProposal>,
+    pub executed: Vec<GovernanceProposal>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProposalsQuery {
+    pub state: Option<ProposalState>,
+}
+
+/// Governance root to prove against; defaults to the current one
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutcomeProofQuery {
+    pub root: Option<String>,
+}
+
+/// Changes to preview without submitting a proposal
+#[derive(Debug, Clone, Deserialize)]
+pub struct DryRunRequest {
+    pub actions: Vec<ProposalAction>,
+    #[serde(default)]
+    pub tally_strategy: Option<String>,
+}
+
+/// Audit log filters and export format: `json` (default), `jsonl` or `csv`
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditQuery {
+    pub proposal_id: Option<u64>,
+    pub actor: Option<String>,
+    pub format: Option<String>,
+}
+
+/// An anchored state summary to check a proposal's execution against
+#[derive(Debug, Clone, Deserialize)]
+pub struct VerifyOutcomeRequest {
+    pub proposal_id: u64,
+    pub state_summary: L2StateSummary,
+}
+
+/// Lifecycle actions exposed as `POST /governance/<action>/{id}`
+#[derive(Debug, Clone, Copy)]
+enum LifecycleAction {
+    Activate,
+    Finalize,
+    Execute,
+}
+
 /// Struct to encapsulate governance API functionality
 pub struct GovernanceAPI {
-    governance: Arc<CrossLayerGovernance>,
+    governance: Arc<ProposalEngine>,
     api_keys: Arc<HashMap<String, ApiKey>>,
     rate_limiter: Arc<crate::api::security::RateLimiter>,
 }
@@ -35,7 +140,7 @@ pub struct GovernanceAPI {
 impl GovernanceAPI {
     /// Creates a new GovernanceAPI instance
     pub fn new(
-        governance: Arc<CrossLayerGovernance>,
+        governance: Arc<ProposalEngine>,
         api_keys: Arc<HashMap<String, ApiKey>>,
         rate_limiter: Arc<crate::api::security::RateLimiter>,
     ) -> Self {
@@ -47,7 +152,7 @@ impl GovernanceAPI {
     }
 
     /// Returns the Warp filter for governance routes
-    pub fn routes(&self) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl warp::Reply,)> {
         let governance = self.governance.clone();
         let api_keys = self.api_keys.clone();
         let rate_limiter = self.rate_limiter.clone();
@@ -60,7 +165,7 @@ impl GovernanceAPI {
             .and(with_rate_limit(rate_limiter.clone()))
             .and(Self::with_governance(governance.clone()))
             .and_then(
-                |request: ProposalRequest, auth: ApiKey, _rate_limit: (), governance: Arc<CrossLayerGovernance>| async move {
+                |request: ProposalRequest, auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
                     Self::submit_proposal_handler(request, auth, governance).await
                 },
             );
@@ -68,46 +173,451 @@ impl GovernanceAPI {
         let list_proposals = warp::get()
             .and(warp::path("governance"))
             .and(warp::path("list"))
+            .and(warp::query::<ProposalsQuery>())
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |query: ProposalsQuery, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    Self::list_proposals_handler(query, governance).await
+                },
+            );
+
+        let get_proposal = warp::get()
+            .and(warp::path!("governance" / "proposal" / u64))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |proposal_id: u64, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let reply = match governance.get_proposal(proposal_id) {
+                        Ok(proposal) => warp::reply::with_status(
+                            warp::reply::json(&proposal),
+                            warp::http::StatusCode::OK,
+                        ),
+                        Err(err) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "error": err })),
+                            warp::http::StatusCode::NOT_FOUND,
+                        ),
+                    };
+                    Ok::<_, warp::Rejection>(reply)
+                },
+            );
+
+        let vote = warp::post()
+            .and(warp::path("governance"))
+            .and(warp::path("vote"))
+            .and(warp::body::json())
+            .and(with_auth(api_keys.clone()))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |request: VoteRequest, auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let result = auth
+                        .authorize(&request.validator)
+                        .and_then(|_| governance.cast_vote(request.proposal_id, &request.validator, request.choice));
+                    let response = match result {
+                        Ok(tally) => VoteResponse {
+                            success: true,
+                            tally: Some(tally),
+                            message: "Vote recorded".to_string(),
+                        },
+                        Err(err) => VoteResponse {
+                            success: false,
+                            tally: None,
+                            message: err,
+                        },
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&response))
+                },
+            );
+
+        let delegator_vote = warp::post()
+            .and(warp::path("governance"))
+            .and(warp::path("delegator-vote"))
+            .and(warp::body::json())
+            .and(with_auth(api_keys.clone()))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |request: DelegatorVoteRequest, auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let result = auth
+                        .authorize(&request.delegator)
+                        .and_then(|_| governance.cast_delegator_vote(request.proposal_id, &request.delegator, request.choice));
+                    let response = match result {
+                        Ok(tally) => VoteResponse {
+                            success: true,
+                            tally: Some(tally),
+                            message: "Vote recorded, overriding the delegated validator".to_string(),
+                        },
+                        Err(err) => VoteResponse {
+                            success: false,
+                            tally: None,
+                            message: err,
+                        },
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&response))
+                },
+            );
+
+        let lifecycle = |name: &'static str, action: LifecycleAction| {
+            warp::post()
+                .and(warp::path("governance"))
+                .and(warp::path(name))
+                .and(warp::path::param::<u64>())
+                .and(with_auth(api_keys.clone()))
+                .and(with_rate_limit(rate_limiter.clone()))
+                .and(Self::with_governance(governance.clone()))
+                .and_then(
+                    move |proposal_id: u64, _auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                        Self::lifecycle_handler(action, proposal_id, governance).await
+                    },
+                )
+        };
+
+        let cancel = warp::post()
+            .and(warp::path("governance"))
+            .and(warp::path("cancel"))
+            .and(warp::path::param::<u64>())
+            .and(warp::body::json())
+            .and(with_auth(api_keys.clone()))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |proposal_id: u64, request: CancelRequest, auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let result = auth
+                        .authorize(&request.canceller)
+                        .and_then(|_| governance.cancel_proposal(proposal_id, &request.canceller));
+                    Self::proposal_response(proposal_id, result, governance).await
+                },
+            );
+
+        let cancel_vote = warp::post()
+            .and(warp::path("governance"))
+            .and(warp::path("cancel-vote"))
+            .and(warp::body::json())
+            .and(with_auth(api_keys.clone()))
             .and(with_rate_limit(rate_limiter.clone()))
             .and(Self::with_governance(governance.clone()))
             .and_then(
-                |_rate_limit: (), governance: Arc<CrossLayerGovernance>| async move {
-                    Self::list_proposals_handler(governance).await
+                |request: CancelVoteRequest, auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let result = auth
+                        .authorize(&request.validator)
+                        .and_then(|_| governance.vote_to_cancel(request.proposal_id, &request.validator))
+                        .map(|_| ());
+                    Self::proposal_response(request.proposal_id, result, governance).await
                 },
             );
 
-        warp::any().and(submit_proposal.or(list_proposals))
+        let timelock = warp::get()
+            .and(warp::path!("governance" / "timelock"))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |_rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let listed = governance
+                        .list_proposals(Some(ProposalState::Queued))
+                        .and_then(|queued| Ok((queued, governance.list_proposals(Some(ProposalState::Executed))?)));
+                    let response = match listed {
+                        Ok((queued, executed)) => TimelockResponse { success: true, queued, executed },
+                        Err(_err) => TimelockResponse { success: false, queued: Vec::new(), executed: Vec::new() },
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&response))
+                },
+            );
+
+        let conflicts = warp::get()
+            .and(warp::path!("governance" / "conflicts"))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |_rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let response = match governance.list_conflicts() {
+                        Ok(proposals) => ProposalsListResponse { success: true, proposals },
+                        Err(_err) => ProposalsListResponse { success: false, proposals: Vec::new() },
+                    };
+                    Ok::<_, warp::Rejection>(warp::reply::json(&response))
+                },
+            );
+
+        let parameters = warp::get()
+            .and(warp::path!("governance" / "parameters"))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |_rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let reply = match governance.parameters.list() {
+                        Ok(parameters) => warp::reply::with_status(
+                            warp::reply::json(&parameters),
+                            warp::http::StatusCode::OK,
+                        ),
+                        Err(err) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "error": err })),
+                            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        ),
+                    };
+                    Ok::<_, warp::Rejection>(reply)
+                },
+            );
+
+        let parameter = warp::get()
+            .and(warp::path!("governance" / "parameters" / String))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |name: String, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let reply = match governance.parameters.get(&name) {
+                        Ok(parameter) => warp::reply::with_status(
+                            warp::reply::json(&parameter),
+                            warp::http::StatusCode::OK,
+                        ),
+                        Err(err) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "error": err })),
+                            warp::http::StatusCode::NOT_FOUND,
+                        ),
+                    };
+                    Ok::<_, warp::Rejection>(reply)
+                },
+            );
+
+        let outcome_proof = warp::get()
+            .and(warp::path!("governance" / "proposal" / u64 / "proof"))
+            .and(warp::query::<OutcomeProofQuery>())
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |proposal_id: u64, query: OutcomeProofQuery, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let reply = match governance.outcome_proof(proposal_id, query.root.as_deref()) {
+                        Ok(proof) => warp::reply::with_status(
+                            warp::reply::json(&proof),
+                            warp::http::StatusCode::OK,
+                        ),
+                        Err(err) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "error": err })),
+                            warp::http::StatusCode::NOT_FOUND,
+                        ),
+                    };
+                    Ok::<_, warp::Rejection>(reply)
+                },
+            );
+
+        let proposal_dry_run = warp::get()
+            .and(warp::path!("governance" / "proposal" / u64 / "dry-run"))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |proposal_id: u64, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    Ok::<_, warp::Rejection>(Self::dry_run_reply(governance.dry_run_proposal(proposal_id)))
+                },
+            );
+
+        let dry_run = warp::post()
+            .and(warp::path!("governance" / "dry-run"))
+            .and(warp::body::json())
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |request: DryRunRequest, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let report = governance.dry_run(&request.actions, request.tally_strategy.as_deref());
+                    Ok::<_, warp::Rejection>(Self::dry_run_reply(report))
+                },
+            );
+
+        let verify_outcome = warp::post()
+            .and(warp::path!("governance" / "verify"))
+            .and(warp::body::json())
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |request: VerifyOutcomeRequest, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let reply = match governance.validate_l1_proposal(&request.state_summary, request.proposal_id) {
+                        Ok(proof) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "verified": true, "proof": proof })),
+                            warp::http::StatusCode::OK,
+                        ),
+                        Err(err) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "verified": false, "error": err })),
+                            warp::http::StatusCode::UNPROCESSABLE_ENTITY,
+                        ),
+                    };
+                    Ok::<_, warp::Rejection>(reply)
+                },
+            );
+
+        let audit = warp::get()
+            .and(warp::path!("governance" / "audit"))
+            .and(warp::query::<AuditQuery>())
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |query: AuditQuery, _rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    Ok::<_, warp::Rejection>(Self::audit_export(governance.audit_log.as_deref(), &query))
+                },
+            );
+
+        let audit_verify = warp::get()
+            .and(warp::path!("governance" / "audit" / "verify"))
+            .and(with_rate_limit(rate_limiter.clone()))
+            .and(Self::with_governance(governance.clone()))
+            .and_then(
+                |_rate_limit: (), governance: Arc<ProposalEngine>| async move {
+                    let verified = governance
+                        .audit_log
+                        .as_ref()
+                        .ok_or_else(|| "Audit log is not enabled".to_string())
+                        .and_then(|audit_log| audit_log.verify());
+                    let reply = match verified {
+                        Ok(entries) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "verified": true, "entries": entries })),
+                            warp::http::StatusCode::OK,
+                        ),
+                        Err(err) => warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({ "verified": false, "error": err })),
+                            warp::http::StatusCode::CONFLICT,
+                        ),
+                    };
+                    Ok::<_, warp::Rejection>(reply)
+                },
+            );
+
+        warp::any().and(
+            submit_proposal
+                .or(list_proposals)
+                .or(get_proposal)
+                .or(outcome_proof)
+                .or(proposal_dry_run)
+                .or(dry_run)
+                .or(verify_outcome)
+                .or(vote)
+                .or(delegator_vote)
+                .or(lifecycle("activate", LifecycleAction::Activate))
+                .or(lifecycle("finalize", LifecycleAction::Finalize))
+                .or(lifecycle("execute", LifecycleAction::Execute))
+                .or(cancel)
+                .or(cancel_vote)
+                .or(timelock)
+                .or(conflicts)
+                .or(parameters)
+                .or(parameter)
+                .or(audit)
+                .or(audit_verify),
+        )
+        .boxed()
+    }
+
+    /// Filtered audit entries in the requested format
+    fn audit_export(audit_log: Option<&GovernanceAuditLog>, query: &AuditQuery) -> warp::reply::Response {
+        use warp::Reply;
+
+        let entries = audit_log
+            .ok_or_else(|| "Audit log is not enabled".to_string())
+            .and_then(|audit_log| audit_log.query(query.proposal_id, query.actor.as_deref()));
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(err) => {
+                return warp::reply::with_status(
+                    warp::reply::json(&serde_json::json!({ "error": err })),
+                    warp::http::StatusCode::SERVICE_UNAVAILABLE,
+                )
+                .into_response()
+            }
+        };
+        match query.format.as_deref().unwrap_or("json") {
+            "json" => warp::reply::json(&entries).into_response(),
+            "jsonl" => match audit_log::to_jsonl(&entries) {
+                Ok(body) => warp::reply::with_header(body, "content-type", "application/x-ndjson").into_response(),
+                Err(err) => warp::reply::with_status(err, warp::http::StatusCode::INTERNAL_SERVER_ERROR).into_response(),
+            },
+            "csv" => warp::reply::with_header(audit_log::to_csv(&entries), "content-type", "text/csv").into_response(),
+            other => warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": format!("Unknown format: {}", other) })),
+                warp::http::StatusCode::BAD_REQUEST,
+            )
+            .into_response(),
+        }
     }
 
     fn with_governance(
-        governance: Arc<CrossLayerGovernance>,
-    ) -> impl Filter<Extract = (Arc<CrossLayerGovernance>,), Error = std::convert::Infallible> + Clone {
+        governance: Arc<ProposalEngine>,
+    ) -> impl Filter<Extract = (Arc<ProposalEngine>,), Error = std::convert::Infallible> + Clone {
         warp::any().map(move || governance.clone())
     }
 
+    fn dry_run_reply(report: Result<DryRunReport, String>) -> warp::reply::WithStatus<warp::reply::Json> {
+        match report {
+            Ok(report) => warp::reply::with_status(warp::reply::json(&report), warp::http::StatusCode::OK),
+            Err(err) => warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "error": err })),
+                warp::http::StatusCode::BAD_REQUEST,
+            ),
+        }
+    }
+
     async fn submit_proposal_handler(
         request: ProposalRequest,
-        _auth: ApiKey,
-        governance: Arc<CrossLayerGovernance>,
+        auth: ApiKey,
+        governance: Arc<ProposalEngine>,
     ) -> Result<impl warp::Reply, warp::Rejection> {
-        match governance.submit_proposal(request.description, request.param, request.value) {
+        // The deposit is escrowed from the proposer, so only the proposer's own key may submit
+        let submitted = auth.authorize(&request.proposer).and_then(|_| request.changes()).and_then(|actions| match &request.tally_strategy {
+            Some(strategy) => governance.submit_proposal_with_strategy(request.proposer, request.description, actions, strategy),
+            None => governance.submit_proposal(request.proposer, request.description, actions),
+        });
+        match submitted {
             Ok(proposal_id) => Ok(warp::reply::json(&ProposalResponse {
                 success: true,
                 proposal_id: Some(proposal_id),
+                state: Some(ProposalState::Draft),
                 message: "Proposal submitted successfully".to_string(),
             })),
             Err(err) => Ok(warp::reply::json(&ProposalResponse {
                 success: false,
                 proposal_id: None,
+                state: None,
                 message: err,
             })),
         }
     }
 
+    async fn lifecycle_handler(
+        action: LifecycleAction,
+        proposal_id: u64,
+        governance: Arc<ProposalEngine>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let result = match action {
+            LifecycleAction::Activate => governance.activate_proposal(proposal_id).map(|_| ()),
+            LifecycleAction::Finalize => governance.finalize_proposal(proposal_id).map(|_| ()),
+            LifecycleAction::Execute => governance.execute_proposal(proposal_id),
+        };
+        Self::proposal_response(proposal_id, result, governance).await
+    }
+
+    /// Reports the proposal's state after an action, or the action's error
+    async fn proposal_response(
+        proposal_id: u64,
+        result: Result<(), String>,
+        governance: Arc<ProposalEngine>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let response = match result.and_then(|_| governance.get_proposal(proposal_id)) {
+            Ok(proposal) => ProposalResponse {
+                success: true,
+                proposal_id: Some(proposal_id),
+                state: Some(proposal.state),
+                message: format!("Proposal is now {:?}", proposal.state),
+            },
+            Err(err) => ProposalResponse {
+                success: false,
+                proposal_id: Some(proposal_id),
+                state: None,
+                message: err,
+            },
+        };
+        Ok(warp::reply::json(&response))
+    }
+
     async fn list_proposals_handler(
-        governance: Arc<CrossLayerGovernance>,
+        query: ProposalsQuery,
+        governance: Arc<ProposalEngine>,
     ) -> Result<impl warp::Reply, warp::Rejection> {
-        match governance.list_proposals() {
+        match governance.list_proposals(query.state) {
             Ok(proposals) => Ok(warp::reply::json(&ProposalsListResponse {
                 success: true,
                 proposals,
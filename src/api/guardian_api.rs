@@ -0,0 +1,129 @@
+use warp::Filter;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::governance::guardians::{emergency_digest, EmergencyAction, EmergencyRecord};
+use crate::governance::proposal_engine::ProposalEngine;
+use crate::api::security::{with_auth, with_rate_limit, ApiKey, RateLimiter};
+
+/// An emergency action for guardians to sign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestRequest {
+    pub action: EmergencyAction,
+    pub reason: String,
+}
+
+/// An emergency action with the guardians' hex-encoded recoverable signatures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyRequest {
+    pub action: EmergencyAction,
+    pub reason: String,
+    pub signatures: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyResponse {
+    pub success: bool,
+    pub record: Option<EmergencyRecord>,
+    pub message: String,
+}
+
+/// Multisig guardian powers: pausing the bridge, freezing rewards and vetoing proposals
+pub struct GuardianAPI {
+    governance: Arc<ProposalEngine>,
+    api_keys: Arc<HashMap<String, ApiKey>>,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl GuardianAPI {
+    pub fn new(
+        governance: Arc<ProposalEngine>,
+        api_keys: Arc<HashMap<String, ApiKey>>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            governance,
+            api_keys,
+            rate_limiter,
+        }
+    }
+
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl warp::Reply,)> {
+        let governance = self.governance.clone();
+        let with_governance = warp::any().map(move || governance.clone());
+
+        let status = warp::get()
+            .and(warp::path!("guardians"))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_governance.clone())
+            .map(|_rate_limit: (), governance: Arc<ProposalEngine>| {
+                let Some(guardians) = &governance.guardians else {
+                    return warp::reply::json(&json!({ "success": false, "message": "Guardians are not configured" }));
+                };
+                match guardians.list().and_then(|records| Ok((records, guardians.next_id()?))) {
+                    Ok((records, next_id)) => warp::reply::json(&json!({
+                        "success": true,
+                        "guardians": guardians.guardians(),
+                        "threshold": guardians.threshold,
+                        "ratification_window_secs": guardians.ratification_window_secs,
+                        "next_id": next_id,
+                        "records": records,
+                    })),
+                    Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+                }
+            });
+
+        let digest = warp::post()
+            .and(warp::path!("guardians" / "digest"))
+            .and(warp::body::json())
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_governance.clone())
+            .map(|request: DigestRequest, _rate_limit: (), governance: Arc<ProposalEngine>| {
+                let next_id = governance
+                    .guardians
+                    .as_ref()
+                    .ok_or_else(|| "Guardians are not configured".to_string())
+                    .and_then(|guardians| guardians.next_id());
+                match next_id {
+                    Ok(id) => warp::reply::json(&json!({
+                        "success": true,
+                        "id": id,
+                        "digest": hex::encode(emergency_digest(id, &request.action, &request.reason)),
+                    })),
+                    Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+                }
+            });
+
+        let emergency = warp::post()
+            .and(warp::path!("guardians" / "emergency"))
+            .and(warp::body::json())
+            .and(with_auth(self.api_keys.clone()))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_governance)
+            .map(|request: EmergencyRequest, _auth: ApiKey, _rate_limit: (), governance: Arc<ProposalEngine>| {
+                let result = match (request.action, &governance.guardians) {
+                    (_, None) => Err("Guardians are not configured".to_string()),
+                    (EmergencyAction::VetoProposal { proposal_id }, Some(_)) => {
+                        governance.guardian_veto(proposal_id, request.reason, &request.signatures)
+                    }
+                    (action, Some(guardians)) => guardians.authorize(action, request.reason, &request.signatures),
+                };
+                let response = match result {
+                    Ok(record) => EmergencyResponse {
+                        success: true,
+                        message: format!("Emergency action {} is in force until {} unless ratified", record.id, record.expires_at),
+                        record: Some(record),
+                    },
+                    Err(err) => EmergencyResponse {
+                        success: false,
+                        record: None,
+                        message: err,
+                    },
+                };
+                warp::reply::json(&response)
+            });
+
+        status.or(digest).or(emergency).boxed()
+    }
+}
@@ -1,5 +1,10 @@
 pub mod anchoring_api;
 pub mod bridge_api;
+pub mod delegation_api;
+pub mod endpoint_health_api;
+pub mod event_observer_api;
 pub mod governance_api;
+pub mod guardian_api;
+pub mod treasury_api;
 pub mod validator_rewards_api;
 pub mod security;
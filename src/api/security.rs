@@ -9,6 +9,19 @@ use std::time::{Duration, Instant};
 pub struct ApiKey {
     pub key: String,
     pub permissions: Vec<String>, // Changed from HashSet to Vec for compatibility
+    #[serde(default)]
+    pub principal: Option<String>, // Address the key acts for; keys without one cannot act for anyone
+}
+
+impl ApiKey {
+    /// Checks that the key may act as `principal`, e.g. vote for it or spend its gBTCZ
+    pub fn authorize(&self, principal: &str) -> Result<(), String> {
+        match &self.principal {
+            Some(bound) if bound == principal => Ok(()),
+            Some(bound) => Err(format!("API key acts for {}, not {}", bound, principal)),
+            None => Err(format!("API key is not bound to {}", principal)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -91,3 +104,20 @@ pub fn with_rate_limit(
             }
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_act_only_for_their_principal() {
+        let key = |principal: Option<&str>| ApiKey {
+            key: "key".to_string(),
+            permissions: Vec::new(),
+            principal: principal.map(String::from),
+        };
+        assert!(key(Some("alice")).authorize("alice").is_ok());
+        assert!(key(Some("alice")).authorize("bob").unwrap_err().contains("acts for alice"));
+        assert!(key(None).authorize("alice").is_err());
+    }
+}
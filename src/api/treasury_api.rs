@@ -0,0 +1,128 @@
+use warp::Filter;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::governance::parameters::GovernableModule;
+use crate::governance::treasury::{FlowKind, Grant, Treasury};
+use crate::api::security::{with_auth, with_rate_limit, ApiKey, RateLimiter};
+
+/// A gBTCZ grant for a spending proposal to approve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantRequest {
+    pub recipient: String,
+    pub amount: u64,
+    #[serde(default)]
+    pub vesting_secs: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantResponse {
+    pub success: bool,
+    pub grant: Option<Grant>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlowQuery {
+    pub kind: Option<FlowKind>,
+}
+
+/// Treasury balance, revenue and spending
+pub struct TreasuryAPI {
+    treasury: Arc<Treasury>,
+    api_keys: Arc<HashMap<String, ApiKey>>,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl TreasuryAPI {
+    pub fn new(
+        treasury: Arc<Treasury>,
+        api_keys: Arc<HashMap<String, ApiKey>>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            treasury,
+            api_keys,
+            rate_limiter,
+        }
+    }
+
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl warp::Reply,)> {
+        let treasury = self.treasury.clone();
+        let with_treasury = warp::any().map(move || treasury.clone());
+
+        let status = warp::get()
+            .and(warp::path!("treasury"))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_treasury.clone())
+            .map(|_rate_limit: (), treasury: Arc<Treasury>| {
+                let result = treasury.balance().and_then(|balance| {
+                    Ok((balance, treasury.reserved()?, treasury.governance_parameters()?))
+                });
+                match result {
+                    Ok((balance, reserved, parameters)) => warp::reply::json(&json!({
+                        "success": true,
+                        "balance": balance,
+                        "reserved": reserved,
+                        "parameters": parameters,
+                    })),
+                    Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+                }
+            });
+
+        let flows = warp::get()
+            .and(warp::path!("treasury" / "flows"))
+            .and(warp::query::<FlowQuery>())
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_treasury.clone())
+            .map(|query: FlowQuery, _rate_limit: (), treasury: Arc<Treasury>| {
+                match treasury.flows(query.kind) {
+                    Ok(flows) => warp::reply::json(&json!({ "success": true, "flows": flows })),
+                    Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+                }
+            });
+
+        let grants = warp::get()
+            .and(warp::path!("treasury" / "grants"))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_treasury.clone())
+            .map(|_rate_limit: (), treasury: Arc<Treasury>| {
+                match treasury.grants() {
+                    Ok(grants) => warp::reply::json(&json!({ "success": true, "grants": grants })),
+                    Err(err) => warp::reply::json(&json!({ "success": false, "message": err })),
+                }
+            });
+
+        let request_grant = warp::post()
+            .and(warp::path!("treasury" / "grants"))
+            .and(warp::body::json())
+            .and(with_auth(self.api_keys.clone()))
+            .and(with_rate_limit(self.rate_limiter.clone()))
+            .and(with_treasury)
+            .map(|request: GrantRequest, _auth: ApiKey, _rate_limit: (), treasury: Arc<Treasury>| {
+                let result = treasury.request_grant(
+                    request.recipient,
+                    request.amount,
+                    request.vesting_secs,
+                    request.description,
+                );
+                let response = match result {
+                    Ok(grant) => GrantResponse {
+                        success: true,
+                        message: format!("Grant {} awaits a proposal setting treasury_spend to {}", grant.id, grant.id),
+                        grant: Some(grant),
+                    },
+                    Err(err) => GrantResponse {
+                        success: false,
+                        grant: None,
+                        message: err,
+                    },
+                };
+                warp::reply::json(&response)
+            });
+
+        status.or(flows).or(grants).or(request_grant).boxed()
+    }
+}
@@ -1,5 +1,6 @@
 use crate::interaction::ClarityInteractor; // Adjusted path to match the file location
-use serde::Serialize;
+use crate::governance::guardians::GuardianCouncil;
+use serde::{Deserialize, Serialize};
 use warp::Filter;
 use std::sync::Arc;
 
@@ -8,40 +9,65 @@ struct RewardResponse {
     success: bool,
     message: String,
     rewards_distributed: Option<u64>,
+    txid: Option<String>, // Set when a payout transaction was submitted
+}
+
+#[derive(Deserialize)]
+struct DistributeRequest {
+    validator: String,
 }
 
 // Custom error type for Warp
 #[derive(Debug)]
-struct CustomError(String);
+struct CustomError(#[allow(dead_code)] String); // Only surfaces through Debug in warp's rejection logs
 
 impl warp::reject::Reject for CustomError {}
 
 #[derive(Clone)]
 pub struct ValidatorRewardsAPI {
     clarity: Arc<ClarityInteractor>,
+    guardians: Option<Arc<GuardianCouncil>>, // Can freeze distribution
 }
 
 impl ValidatorRewardsAPI {
     pub fn new(clarity: Arc<ClarityInteractor>) -> Self {
-        Self { clarity }
+        Self { clarity, guardians: None }
+    }
+
+    pub fn with_guardians(mut self, guardians: Arc<GuardianCouncil>) -> Self {
+        self.guardians = Some(guardians);
+        self
     }
 
-    pub fn routes(&self) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    pub fn routes(&self) -> warp::filters::BoxedFilter<(impl warp::Reply,)> {
         let api = warp::path("validator-rewards");
 
         // Distribute rewards endpoint
         let distribute_rewards = {
             let clarity = self.clarity.clone();
+            let guardians = self.guardians.clone();
             warp::path("distribute")
                 .and(warp::post())
-                .and_then(move || {
+                .and(warp::body::json())
+                .and_then(move |request: DistributeRequest| {
                     let clarity = clarity.clone();
+                    let guardians = guardians.clone();
                     async move {
-                        match clarity.distribute_rewards().await {
-                            Ok(total_rewards) => Ok::<_, warp::Rejection>(warp::reply::json(&RewardResponse {
+                        let frozen = match &guardians {
+                            Some(guardians) => guardians.are_rewards_frozen(),
+                            None => Ok(false),
+                        };
+                        match frozen {
+                            Ok(false) => {}
+                            Ok(true) => return Err(warp::reject::custom(CustomError("Reward distribution is frozen by the guardians".to_string()))),
+                            Err(e) => return Err(warp::reject::custom(CustomError(e))),
+                        }
+                        match clarity.distribute_rewards(&request.validator).await {
+                            Ok(receipt) => Ok::<_, warp::Rejection>(warp::reply::json(&RewardResponse {
                                 success: true,
-                                message: "Rewards distributed successfully".to_string(),
-                                rewards_distributed: Some(total_rewards),
+                                message: "Reward distribution submitted".to_string(),
+                                rewards_distributed: None,
+                                txid: Some(receipt.txid),
                             })),
                             Err(e) => Err(warp::reject::custom(CustomError(e))), // Use CustomError
                         }
@@ -62,6 +88,7 @@ impl ValidatorRewardsAPI {
                                 success: true,
                                 message: "Rewards queried successfully".to_string(),
                                 rewards_distributed: Some(total_rewards),
+                                txid: None,
                             })),
                             Err(e) => Err(warp::reject::custom(CustomError(e))), // Use CustomError
                         }
@@ -70,6 +97,6 @@ impl ValidatorRewardsAPI {
         };
 
         // Combine validator rewards endpoints
-        api.and(distribute_rewards.or(query_rewards))
+        api.and(distribute_rewards.or(query_rewards)).boxed()
     }
 }
@@ -1,19 +1,110 @@
 // File: bridge_logic.rs
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use crate::bridge::state_anchoring::{StateAnchoring, L2StateSummary};
 use crate::bridge::merkle::MerkleTree;
 use crate::bridge::btcz_integration::{BTCZIntegration, BTCZAnchorPayload};
+use crate::clarity::receipt::{ContractError, TransactionReceipt};
 use crate::clarity::ClarityInteractor;
+use crate::governance::guardians::GuardianCouncil;
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
 use chrono::Utc;
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex as TokioMutex;
+
+/// Bridge operation awaiting confirmation of its contract call
+#[derive(Debug, Clone)]
+pub enum BridgeOperation {
+    Lock { fee: u64 }, // Fee withheld from the zBTCZ minted
+    Burn { anchor: BTCZAnchorPayload },
+}
+
+/// Governable limits on individual bridge operations, in BTCZ base units
+#[derive(Debug, Clone)]
+pub struct BridgeLimits {
+    pub max_lock_amount: u64,
+    pub max_burn_amount: u64,
+    pub fee_basis_points: u64, // Withheld from the zBTCZ minted for a lock
+}
+
+impl Default for BridgeLimits {
+    fn default() -> Self {
+        Self {
+            max_lock_amount: 1_000_000_000_000_000, // 10M BTCZ
+            max_burn_amount: 1_000_000_000_000_000,
+            fee_basis_points: 0,
+        }
+    }
+}
+
+impl BridgeLimits {
+    pub fn lock_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_basis_points as u128 / 10_000) as u64
+    }
+}
+
+impl GovernableModule for Mutex<BridgeLimits> {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        let limits = self.lock().map_err(|_| "Mutex lock failed")?;
+        let cap = |name, description, value| ParameterSpec {
+            name,
+            module: "bridge",
+            kind: ParameterType::Amount,
+            min: 1,
+            max: 2_100_000_000_000_000_000,
+            unit: "BTCZ base units",
+            description,
+            value,
+        };
+        Ok(vec![
+            cap("bridge_max_lock", "Largest BTCZ lock accepted in one operation", limits.max_lock_amount),
+            cap("bridge_max_burn", "Largest zBTCZ burn accepted in one operation", limits.max_burn_amount),
+            ParameterSpec {
+                name: "bridge_fee",
+                module: "bridge",
+                kind: ParameterType::BasisPoints,
+                min: 0,
+                max: 500,
+                unit: "basis points",
+                description: "Fee withheld from zBTCZ minted for locked BTCZ",
+                value: limits.fee_basis_points,
+            },
+        ])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        let mut limits = self.lock().map_err(|_| "Mutex lock failed")?;
+        match name {
+            "bridge_max_lock" => limits.max_lock_amount = value,
+            "bridge_max_burn" => limits.max_burn_amount = value,
+            "bridge_fee" => limits.fee_basis_points = value,
+            _ => return Err(format!("Unknown bridge parameter: {}", name)),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingBridgeOp {
+    pub operation: BridgeOperation,
+    pub address: String,
+    pub amount: u64,
+    pub receipt: TransactionReceipt,
+}
 
 #[derive(Debug, Clone)]
 pub struct BridgeLedger {
-    pub locked_tokens: HashMap<String, u64>,
+    pub locked_tokens: HashMap<String, u64>, // BTCZ locked, fees included
+    pub minted_tokens: HashMap<String, u64>, // zBTCZ minted, net of fees
+    pub fees_paid: HashMap<String, u64>,     // Withheld from the mint; the BTCZ stays locked
     pub burned_tokens: HashMap<String, u64>,
+    pub pending: HashMap<String, PendingBridgeOp>, // Keyed by Stacks txid
+    pub settled: HashSet<String>,                  // Txids already reflected in the balances
     pub state_anchoring: Arc<StateAnchoring>,
     pub clarity_interactor: Arc<ClarityInteractor>,
     pub btcz_integration: Arc<BTCZIntegration>,
+    pub limits: Arc<Mutex<BridgeLimits>>,
+    pub guardians: Option<Arc<GuardianCouncil>>, // Can pause new locks and burns
 }
 
 impl BridgeLedger {
@@ -24,40 +115,81 @@ impl BridgeLedger {
     ) -> Self {
         Self {
             locked_tokens: HashMap::new(),
+            minted_tokens: HashMap::new(),
+            fees_paid: HashMap::new(),
             burned_tokens: HashMap::new(),
+            pending: HashMap::new(),
+            settled: HashSet::new(),
             state_anchoring,
             clarity_interactor,
             btcz_integration,
+            limits: Arc::new(Mutex::new(BridgeLimits::default())),
+            guardians: None,
+        }
+    }
+
+    pub fn with_limits(mut self, limits: Arc<Mutex<BridgeLimits>>) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_guardians(mut self, guardians: Arc<GuardianCouncil>) -> Self {
+        self.guardians = Some(guardians);
+        self
+    }
+
+    fn ensure_not_paused(&self) -> Result<(), String> {
+        match &self.guardians {
+            Some(guardians) if guardians.is_bridge_paused()? => Err("Bridge is paused by the guardians".to_string()),
+            _ => Ok(()),
         }
     }
 
-    pub async fn lock_btcz(&mut self, address: String, amount: u64) -> Result<(), String> {
+    /// Submits the zBTCZ mint; the lock is credited once the receipt confirms. Returns the txid.
+    pub async fn lock_btcz(
+        &mut self,
+        address: String,
+        amount: u64,
+        block_height: u64,
+        merkle_root: Vec<u8>,
+    ) -> Result<String, String> {
         if amount == 0 {
             return Err("Amount must be greater than zero".to_string());
         }
+        self.ensure_not_paused()?;
+        let fee = {
+            let limits = self.limits.lock().map_err(|_| "Mutex lock failed")?;
+            if amount > limits.max_lock_amount {
+                return Err(format!("Amount exceeds the bridge lock cap of {}", limits.max_lock_amount));
+            }
+            limits.lock_fee(amount)
+        };
 
-        let entry = self.locked_tokens.entry(address.clone()).or_insert(0);
-        *entry += amount;
-
-        self.clarity_interactor
-            .mint_zbtcz(amount.into())
+        let receipt = self
+            .clarity_interactor
+            .mint_zbtcz((amount - fee).into(), block_height.into(), merkle_root)
             .await
-            .to_result()
             .map_err(|err| format!("Failed to mint zBTCZ: {}", err))?;
 
-        println!("BTCZ locked and zBTCZ minted: {} -> {}", address, *entry);
-        Ok(())
+        println!("zBTCZ mint submitted for {} (fee {}): {}", address, fee, receipt.txid);
+        Ok(self.track(BridgeOperation::Lock { fee }, address, amount, receipt))
     }
 
+    /// Submits the zBTCZ burn; BTCZ is unlocked and anchored once the receipt confirms. Returns the txid.
     pub async fn burn_zbtcz(
         &mut self,
         address: String,
         amount: u64,
         transaction_hash: String,
-    ) -> Result<(), String> {
+    ) -> Result<String, String> {
         if amount == 0 {
             return Err("Amount must be greater than zero".to_string());
         }
+        self.ensure_not_paused()?;
+        let max_burn_amount = self.limits.lock().map_err(|_| "Mutex lock failed")?.max_burn_amount;
+        if amount > max_burn_amount {
+            return Err(format!("Amount exceeds the bridge burn cap of {}", max_burn_amount));
+        }
 
         let merkle_tree = MerkleTree::new(vec![transaction_hash.clone().into_bytes()]);
         let proof = self
@@ -71,10 +203,10 @@ impl BridgeLedger {
             return Err("Invalid Merkle proof".to_string());
         }
 
-        self.clarity_interactor
-            .burn_zbtcz(vec![amount.into()])
+        let receipt = self
+            .clarity_interactor
+            .burn_zbtcz(amount.into(), contract_tx_id(&transaction_hash))
             .await
-            .to_result()
             .map_err(|err| format!("Failed to burn zBTCZ: {}", err))?;
 
         let summary = L2StateSummary {
@@ -83,33 +215,156 @@ impl BridgeLedger {
             total_transactions: 1,
             compliance: true,
             timestamp: Utc::now(),
+            governance_root: None,
+            treasury_balance: None,
         };
 
-        let anchor_payload = BTCZAnchorPayload {
+        let anchor = BTCZAnchorPayload {
             block_height: summary.block_height,
             state_root: summary.state_root.clone(),
             merkle_proof: proof.clone(),
             validator_compliance: true,
             timestamp: summary.timestamp,
+            governance_root: None,
+            treasury_balance: None,
+        };
+
+        println!("zBTCZ burn submitted for {}: {}", address, receipt.txid);
+        Ok(self.track(BridgeOperation::Burn { anchor }, address, amount, receipt))
+    }
+
+    fn track(&mut self, operation: BridgeOperation, address: String, amount: u64, receipt: TransactionReceipt) -> String {
+        let txid = receipt.txid.clone();
+        self.pending.insert(
+            txid.clone(),
+            PendingBridgeOp {
+                operation,
+                address,
+                amount,
+                receipt,
+            },
+        );
+        txid
+    }
+
+    /// Applies a refreshed receipt to its pending operation.
+    /// Returns the operation once it is final: `Ok` if it was credited, `Err` with the contract error otherwise.
+    pub fn apply_receipt(
+        &mut self,
+        receipt: &TransactionReceipt,
+    ) -> Option<Result<PendingBridgeOp, ContractError>> {
+        if !receipt.status.is_final() {
+            if let Some(op) = self.pending.get_mut(&receipt.txid) {
+                op.receipt = receipt.clone();
+            }
+            return None;
+        }
+
+        let mut op = self.pending.remove(&receipt.txid)?;
+        op.receipt = receipt.clone();
+        self.settled.insert(receipt.txid.clone());
+        if let Err(err) = receipt.outcome() {
+            return Some(Err(err));
+        }
+
+        match op.operation {
+            BridgeOperation::Lock { fee } => {
+                *self.locked_tokens.entry(op.address.clone()).or_insert(0) += op.amount;
+                *self.minted_tokens.entry(op.address.clone()).or_insert(0) += op.amount - fee;
+                *self.fees_paid.entry(op.address.clone()).or_insert(0) += fee;
+                println!("BTCZ locked and zBTCZ minted: {} locked {}, minted {}, fee {}", op.address, op.amount, op.amount - fee, fee);
+            }
+            BridgeOperation::Burn { .. } => {
+                let entry = self.burned_tokens.entry(op.address.clone()).or_insert(0);
+                *entry += op.amount;
+                println!("zBTCZ burned and BTCZ unlocked: {} -> {}", op.address, op.amount);
+            }
+        }
+        Some(Ok(op))
+    }
+
+    /// Polls receipts for all pending operations and applies the final ones. The ledger is
+    /// only locked to snapshot and apply receipts, not while the node is queried.
+    pub async fn sync_pending(ledger: &TokioMutex<BridgeLedger>) {
+        let (clarity_interactor, receipts) = {
+            let ledger = ledger.lock().await;
+            let receipts: Vec<TransactionReceipt> = ledger.pending.values().map(|op| op.receipt.clone()).collect();
+            (ledger.clarity_interactor.clone(), receipts)
         };
 
-        tokio::spawn({
-            let integration = self.btcz_integration.clone();
-            async move {
-                if let Err(e) = integration.send_anchor(anchor_payload).await {
-                    eprintln!("Failed to synchronize state with BTCZ: {}", e);
+        for receipt in receipts {
+            let refreshed = match clarity_interactor.refresh_receipt(&receipt).await {
+                Ok(refreshed) => refreshed,
+                Err(e) => {
+                    eprintln!("Failed to refresh receipt {}: {}", receipt.txid, e);
+                    continue;
                 }
+            };
+
+            // Operations settled in the meantime, e.g. by the event observer, are skipped
+            let mut ledger = ledger.lock().await;
+            let outcome = ledger.apply_receipt(&refreshed);
+            ledger.finish_operation(&refreshed.txid, outcome);
+        }
+    }
+
+    /// Anchors confirmed burns on BTCZ and reports failed operations
+    pub fn finish_operation(&self, txid: &str, outcome: Option<Result<PendingBridgeOp, ContractError>>) {
+        match outcome {
+            Some(Ok(PendingBridgeOp {
+                operation: BridgeOperation::Burn { anchor },
+                ..
+            })) => {
+                tokio::spawn({
+                    let integration = self.btcz_integration.clone();
+                    async move {
+                        if let Err(e) = integration.send_anchor(anchor).await {
+                            eprintln!("Failed to synchronize state with BTCZ: {}", e);
+                        }
+                    }
+                });
             }
-        });
+            Some(Err(e)) => eprintln!("Bridge transaction {} failed: {}", txid, e),
+            _ => {}
+        }
+    }
 
-        println!("zBTCZ burned and BTCZ unlocked: {} -> {}", address, amount);
-        Ok(())
+    /// Credits a zBTCZ mint observed on-chain that this node did not submit
+    pub fn record_observed_mint(&mut self, txid: &str, address: String, amount: u64) -> bool {
+        if self.pending.contains_key(txid) || !self.settled.insert(txid.to_string()) {
+            return false;
+        }
+        // The fee of a mint this node did not submit is unknown, so it counts as none
+        *self.locked_tokens.entry(address.clone()).or_insert(0) += amount;
+        let entry = self.minted_tokens.entry(address.clone()).or_insert(0);
+        *entry += amount;
+        println!("Observed zBTCZ mint: {} -> {}", address, *entry);
+        true
+    }
+
+    /// Credits a zBTCZ burn observed on-chain that this node did not submit
+    pub fn record_observed_burn(&mut self, txid: &str, address: String, amount: u64) -> bool {
+        if self.pending.contains_key(txid) || !self.settled.insert(txid.to_string()) {
+            return false;
+        }
+        let entry = self.burned_tokens.entry(address.clone()).or_insert(0);
+        *entry += amount;
+        println!("Observed zBTCZ burn: {} -> {}", address, amount);
+        true
     }
 
     pub fn get_locked_balance(&self, address: &String) -> u64 {
         *self.locked_tokens.get(address).unwrap_or(&0)
     }
 
+    pub fn get_minted_balance(&self, address: &String) -> u64 {
+        *self.minted_tokens.get(address).unwrap_or(&0)
+    }
+
+    pub fn get_fees_paid(&self, address: &String) -> u64 {
+        *self.fees_paid.get(address).unwrap_or(&0)
+    }
+
     pub fn get_burned_balance(&self, address: &String) -> u64 {
         *self.burned_tokens.get(address).unwrap_or(&0)
     }
@@ -120,3 +375,174 @@ impl BridgeLedger {
         (total_locked, total_burned)
     }
 }
+
+/// Derives the `uint` tx-id recorded by zbtcz.clar from a BTCZ transaction hash
+fn contract_tx_id(transaction_hash: &str) -> u128 {
+    let digest = Sha256::digest(transaction_hash.as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    u128::from_be_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::bindings::zbtcz;
+    use crate::clarity::transaction::{StacksNetwork, StacksSigner};
+    use crate::governance::guardians::{emergency_digest, EmergencyAction};
+    use crate::clarity::receipt::TxStatus;
+    use crate::clarity::value::ClarityValue;
+    use crate::clarity::Principal;
+
+    fn ledger() -> BridgeLedger {
+        let clarity = ClarityInteractor::new(
+            "http://localhost:20443",
+            Principal::new("SP000000000000000000002Q6VF78".to_string()),
+            "SP000000000000000000002Q6VF78.zbtcz",
+            "SP000000000000000000002Q6VF78.gbtcz",
+            "SP000000000000000000002Q6VF78.governance",
+            "SP000000000000000000002Q6VF78.bridge",
+            "SP000000000000000000002Q6VF78.validator-rewards",
+        );
+        BridgeLedger::new(
+            Arc::new(StateAnchoring::new()),
+            Arc::new(clarity),
+            Arc::new(BTCZIntegration::new("http://localhost:8232".to_string())),
+        )
+    }
+
+    async fn synced(ledger: BridgeLedger) -> BridgeLedger {
+        let ledger = TokioMutex::new(ledger);
+        BridgeLedger::sync_pending(&ledger).await;
+        ledger.into_inner()
+    }
+
+    fn confirmed(txid: &str, status: TxStatus, result: ClarityValue) -> TransactionReceipt {
+        TransactionReceipt {
+            status,
+            result: Some(result),
+            ..TransactionReceipt::pending(txid.to_string(), zbtcz::SOURCE)
+        }
+    }
+
+    #[test]
+    fn test_lock_credited_only_on_confirmed_receipt() {
+        let mut ledger = ledger();
+        let pending = TransactionReceipt::pending("aa".to_string(), zbtcz::SOURCE);
+        ledger.track(BridgeOperation::Lock { fee: 0 }, "alice".to_string(), 50, pending.clone());
+
+        assert!(ledger.apply_receipt(&pending).is_none());
+        assert_eq!(ledger.get_locked_balance(&"alice".to_string()), 0);
+
+        let receipt = confirmed("aa", TxStatus::Success, ClarityValue::ok(ClarityValue::Bool(true)));
+        assert!(matches!(ledger.apply_receipt(&receipt), Some(Ok(_))));
+        assert_eq!(ledger.get_locked_balance(&"alice".to_string()), 50);
+        assert!(ledger.pending.is_empty());
+
+        // Replaying the receipt does not credit twice
+        assert!(ledger.apply_receipt(&receipt).is_none());
+        assert_eq!(ledger.get_locked_balance(&"alice".to_string()), 50);
+    }
+
+    #[tokio::test]
+    async fn test_lock_and_burn_against_embedded_contracts() {
+        let clarity = ClarityInteractor::embedded("SP000000000000000000002Q6VF78").unwrap();
+        let root = vec![3u8; 32];
+        clarity.finalize_state(5, root.clone()).await.unwrap();
+
+        let mut ledger = BridgeLedger::new(
+            Arc::new(StateAnchoring::new()),
+            Arc::new(clarity),
+            Arc::new(BTCZIntegration::new("http://localhost:8232".to_string())),
+        );
+        let alice = "alice".to_string();
+
+        ledger.lock_btcz(alice.clone(), 70, 5, root).await.unwrap();
+        ledger.lock_btcz(alice.clone(), 30, 6, vec![9u8; 32]).await.unwrap(); // Root never finalized
+        ledger = synced(ledger).await;
+        assert_eq!(ledger.get_locked_balance(&alice), 70);
+        assert!(ledger.pending.is_empty());
+
+        ledger.burn_zbtcz(alice.clone(), 20, "btcz-tx-1".to_string()).await.unwrap();
+        ledger.burn_zbtcz(alice.clone(), 500, "btcz-tx-2".to_string()).await.unwrap(); // Exceeds minted zBTCZ
+        ledger = synced(ledger).await;
+        assert_eq!(ledger.get_burned_balance(&alice), 20);
+        assert_eq!(ledger.audit_token_flow(), (70, 20));
+    }
+
+    #[tokio::test]
+    async fn test_locked_btcz_equals_minted_zbtcz_plus_fees() {
+        let clarity = ClarityInteractor::embedded("SP000000000000000000002Q6VF78").unwrap();
+        let root = vec![4u8; 32];
+        clarity.finalize_state(7, root.clone()).await.unwrap();
+        let mut ledger = BridgeLedger::new(
+            Arc::new(StateAnchoring::new()),
+            Arc::new(clarity),
+            Arc::new(BTCZIntegration::new("http://localhost:8232".to_string())),
+        );
+        ledger.limits.set_governance_parameter("bridge_fee", 100).unwrap();
+        let alice = "alice".to_string();
+
+        ledger.lock_btcz(alice.clone(), 1_000, 7, root.clone()).await.unwrap();
+        ledger.lock_btcz(alice.clone(), 250, 7, root).await.unwrap();
+        ledger = synced(ledger).await;
+
+        let (locked, minted, fees) = (
+            ledger.get_locked_balance(&alice),
+            ledger.get_minted_balance(&alice),
+            ledger.get_fees_paid(&alice),
+        );
+        assert_eq!((locked, minted, fees), (1_250, 1_238, 12));
+        assert_eq!(locked, minted + fees);
+    }
+
+    #[test]
+    fn test_aborted_lock_surfaces_contract_error() {
+        let mut ledger = ledger();
+        let pending = TransactionReceipt::pending("bb".to_string(), zbtcz::SOURCE);
+        ledger.track(BridgeOperation::Lock { fee: 0 }, "bob".to_string(), 10, pending);
+
+        let receipt = confirmed("bb", TxStatus::AbortByResponse, ClarityValue::err(ClarityValue::UInt(1002)));
+        match ledger.apply_receipt(&receipt) {
+            Some(Err(err)) => assert_eq!(err, ContractError::UnfinalizedMerkleRoot),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(ledger.get_locked_balance(&"bob".to_string()), 0);
+        assert!(ledger.pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_governed_caps_reject_large_operations() {
+        let mut ledger = ledger();
+        let limits = ledger.limits.clone();
+        limits.set_governance_parameter("bridge_max_lock", 100).unwrap();
+        limits.set_governance_parameter("bridge_max_burn", 50).unwrap();
+
+        let err = ledger.lock_btcz("alice".to_string(), 101, 1, vec![0u8; 32]).await.unwrap_err();
+        assert!(err.contains("lock cap"));
+        let err = ledger.burn_zbtcz("alice".to_string(), 51, "btcz-tx".to_string()).await.unwrap_err();
+        assert!(err.contains("burn cap"));
+        assert!(ledger.pending.is_empty());
+
+        limits.set_governance_parameter("bridge_fee", 25).unwrap();
+        assert_eq!(limits.lock().unwrap().lock_fee(10_000), 25);
+    }
+
+    #[tokio::test]
+    async fn test_guardian_pause_blocks_locks_and_burns() {
+        let key = StacksSigner::from_hex(&hex::encode([1u8; 32]), StacksNetwork::Testnet).unwrap();
+        let path = std::env::temp_dir().join(format!("zook_bridge_guardians_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let guardians = Arc::new(GuardianCouncil::new(vec![key.address()], 1, 3_600, path).unwrap());
+        let mut ledger = ledger().with_guardians(guardians.clone());
+
+        let digest = emergency_digest(guardians.next_id().unwrap(), &EmergencyAction::PauseBridge, "exploit");
+        let signature = hex::encode(key.sign(&digest).unwrap());
+        guardians.authorize(EmergencyAction::PauseBridge, "exploit".to_string(), &[signature]).unwrap();
+
+        let err = ledger.lock_btcz("alice".to_string(), 10, 1, vec![0u8; 32]).await.unwrap_err();
+        assert_eq!(err, "Bridge is paused by the guardians");
+        assert!(ledger.burn_zbtcz("alice".to_string(), 10, "btcz-tx".to_string()).await.is_err());
+        assert!(ledger.pending.is_empty());
+    }
+}
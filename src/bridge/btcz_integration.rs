@@ -1,8 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::bridge::merkle::MerkleTree;
-use reqwest::Client;
-use tokio::time::{sleep, Duration};
+use crate::governance::treasury::TreasuryBalance;
+use crate::http_client::EndpointPool;
 use hex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,56 +12,48 @@ pub struct BTCZAnchorPayload {
     pub merkle_proof: Vec<String>,
     pub validator_compliance: bool,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub governance_root: Option<String>, // Merkle root of executed governance outcomes
+    #[serde(default)]
+    pub treasury_balance: Option<TreasuryBalance>,
 }
 
 #[derive(Debug)]
 pub struct BTCZIntegration {
-    pub rpc_endpoint: String, // Endpoint to BTCZ Core RPC
+    pub endpoints: EndpointPool, // BTCZ Core RPC endpoints, tried in order
 }
 
 impl BTCZIntegration {
     pub fn new(rpc_endpoint: String) -> Self {
-        Self { rpc_endpoint }
+        Self::with_endpoints(EndpointPool::single("btcz", &rpc_endpoint))
+    }
+
+    pub fn with_endpoints(endpoints: EndpointPool) -> Self {
+        Self { endpoints }
     }
 
     pub async fn send_anchor(&self, payload: BTCZAnchorPayload) -> Result<(), String> {
         let serialized_payload = serde_json::to_string(&payload)
             .map_err(|err| format!("Serialization failed: {}", err))?;
 
-        let client = Client::new();
-        let max_retries = 3;
-        let mut retries = 0;
-
-        while retries < max_retries {
-            let response = client
-                .post(&self.rpc_endpoint)
-                .header("Content-Type", "application/json")
-                .body(serialized_payload.clone())
-                .send()
-                .await;
+        let resp = self
+            .endpoints
+            .send(|client, url| {
+                client
+                    .post(url)
+                    .header("Content-Type", "application/json")
+                    .body(serialized_payload.clone())
+            })
+            .await?;
 
-            match response {
-                Ok(resp) if resp.status().is_success() => {
-                    return Ok(());
-                }
-                Ok(resp) => {
-                    eprintln!(
-                        "HTTP Error {}: {:?}",
-                        resp.status(),
-                        resp.text().await.unwrap_or_default()
-                    );
-                }
-                Err(err) => {
-                    eprintln!("Request Error: {}", err);
-                }
-            }
-
-            retries += 1;
-            eprintln!("Retrying... attempt {}/{}", retries, max_retries);
-            sleep(Duration::from_secs(2_u64.pow(retries))).await;
+        if !resp.status().is_success() {
+            return Err(format!(
+                "HTTP Error {}: {}",
+                resp.status(),
+                resp.text().await.unwrap_or_default()
+            ));
         }
-
-        Err("Failed to send anchor after multiple attempts".to_string())
+        Ok(())
     }
 
     pub fn validate_merkle_proof(
@@ -85,9 +77,9 @@ impl BTCZIntegration {
         transaction_hash: &str,
     ) -> Vec<String> {
         merkle_tree
-            .get_proof(&transaction_hash.as_bytes().to_vec())
+            .get_proof(transaction_hash.as_bytes())
             .into_iter()
-            .map(|hash| hex::encode(hash))
+            .map(hex::encode)
             .collect()
     }
 }
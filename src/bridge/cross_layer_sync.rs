@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use crate::bridge::state_anchoring::L2StateSummary;
 use crate::bridge::merkle::MerkleTree;
 use crate::validator::node_registration::ValidatorRegistry;
+use crate::governance::treasury::TreasuryBalance;
 use crate::governance::validator_policies::GovernanceValidatorPolicies;
 use crate::bridge::btcz_integration::{BTCZIntegration, BTCZAnchorPayload};
 
@@ -17,6 +18,10 @@ pub struct AnchoredState {
     pub merkle_proof: Vec<String>, 
     pub timestamp: DateTime<Utc>,
     pub validator_compliance: bool, 
+    #[serde(default)]
+    pub governance_root: Option<String>,
+    #[serde(default)]
+    pub treasury_balance: Option<TreasuryBalance>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +86,8 @@ impl CrossLayerSync {
             merkle_proof: merkle_proof.clone(),
             timestamp: state_summary.timestamp,
             validator_compliance: compliance,
+            governance_root: state_summary.governance_root.clone(),
+            treasury_balance: state_summary.treasury_balance,
         };
         states.push(anchored_state);
 
@@ -91,6 +98,8 @@ impl CrossLayerSync {
             merkle_proof,
             validator_compliance: compliance,
             timestamp: Utc::now(),
+            governance_root: state_summary.governance_root,
+            treasury_balance: state_summary.treasury_balance,
         };
 
         tokio::spawn({
@@ -106,6 +115,7 @@ impl CrossLayerSync {
         Ok(())
     }
 
+    #[allow(dead_code)] // Not called by the node yet
     pub fn validate_state(&self, state_root: &str) -> bool {
         let states = self.anchored_states.lock().map(|guard| guard.clone()).unwrap_or_default();
         states.iter().any(|state| state.state_root == state_root)
@@ -116,6 +126,7 @@ impl CrossLayerSync {
         states.last().cloned()
     }
 
+    #[allow(dead_code)]
     pub fn generate_anchor_summary(
         merkle_tree: &MerkleTree,
         block_height: u64,
@@ -131,15 +142,18 @@ impl CrossLayerSync {
             total_transactions,
             timestamp: Utc::now(),
             compliance,
+            governance_root: None,
+            treasury_balance: None,
         }
     }
 
+    #[allow(dead_code)]
     pub fn generate_merkle_proof(
         merkle_tree: &MerkleTree,
         transaction_hash: &str,
     ) -> Vec<String> {
         merkle_tree
-            .get_proof(&transaction_hash.as_bytes().to_vec())
+            .get_proof(transaction_hash.as_bytes())
             .into_iter()
             .map(|hash| String::from_utf8_lossy(&hash).to_string())
             .collect()
@@ -147,9 +161,8 @@ impl CrossLayerSync {
 
     fn check_validator_compliance(
         validator_registry: &ValidatorRegistry,
-        governance_policies: &GovernanceValidatorPolicies,
+        _governance_policies: &GovernanceValidatorPolicies,
     ) -> bool {
-        let policy = governance_policies.get_policy().unwrap_or_default();
         let nodes = match validator_registry.nodes.lock() {
             Ok(guard) => guard.clone(),
             Err(_) => HashMap::new(),
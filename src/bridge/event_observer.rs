@@ -0,0 +1,557 @@
+// File: src/bridge/event_observer.rs
+//
+// Consumes the payloads a Stacks node pushes to its configured event observers
+// (`/new_block`, `/new_burn_block`) and feeds them into bridge and governance state.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Mutex as TokioMutex;
+use crate::bridge::bridge_logic::BridgeLedger;
+use crate::bridge::cross_layer_sync::{BurnEvent, CrossLayerSync, LockEvent};
+use crate::clarity::receipt::{ContractEvent, TransactionReceipt};
+use crate::governance::contract_sync::apply_governance_print;
+use crate::governance::proposal_engine::ProposalEngine;
+
+const DEDUP_WINDOW: usize = 10_000; // Block hashes and event ids remembered for deduplication
+const MAX_BUFFERED_BLOCKS: usize = 256; // Orphans kept while waiting for a missing parent
+
+/// A single delivery from the Stacks node, as recorded for replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservedDelivery {
+    pub path: String,
+    pub payload: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeliveryOutcome {
+    Applied { blocks: usize },
+    Duplicate,
+    Buffered, // Parent block not seen yet
+    Ignored,
+}
+
+/// Bounded set that forgets the oldest entries first
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "VecDeque<String>", into = "VecDeque<String>")]
+struct SeenSet {
+    order: VecDeque<String>,
+    set: HashSet<String>,
+}
+
+impl SeenSet {
+    fn contains(&self, key: &str) -> bool {
+        self.set.contains(key)
+    }
+
+    fn insert(&mut self, key: String) -> bool {
+        if !self.set.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > DEDUP_WINDOW {
+            if let Some(oldest) = self.order.pop_front() {
+                self.set.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+impl From<VecDeque<String>> for SeenSet {
+    fn from(order: VecDeque<String>) -> Self {
+        let set = order.iter().cloned().collect();
+        Self { order, set }
+    }
+}
+
+impl From<SeenSet> for VecDeque<String> {
+    fn from(seen: SeenSet) -> Self {
+        seen.order
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ObserverState {
+    pub tip_height: u64,
+    pub tip_hash: Option<String>,
+    pub burn_block_height: u64,
+    seen_blocks: SeenSet,
+    seen_events: SeenSet,
+    #[serde(skip)]
+    orphans: HashMap<String, Vec<Value>>, // Keyed by parent_index_block_hash
+}
+
+pub struct StacksEventObserver {
+    pub state: TokioMutex<ObserverState>,
+    pub bridge_ledger: Arc<TokioMutex<BridgeLedger>>,
+    pub cross_layer_sync: Arc<CrossLayerSync>,
+    pub governance: Arc<ProposalEngine>,
+    pub zbtcz_asset: String,         // e.g. SP...zbtcz::zbtcz-token
+    pub governance_contract: String, // Contract whose prints carry proposal outcomes
+    pub storage_path: PathBuf,
+    pub record_path: Option<PathBuf>, // Appends every delivery as JSONL when set
+}
+
+impl StacksEventObserver {
+    pub fn new(
+        bridge_ledger: Arc<TokioMutex<BridgeLedger>>,
+        cross_layer_sync: Arc<CrossLayerSync>,
+        governance: Arc<ProposalEngine>,
+        zbtcz_contract: &str,
+        governance_contract: &str,
+        storage_path: PathBuf,
+    ) -> Result<Self, String> {
+        // Forgetting which events were seen would apply them again when they are redelivered
+        let state = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable observer state in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ObserverState::default(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+
+        Ok(Self {
+            state: TokioMutex::new(state),
+            bridge_ledger,
+            cross_layer_sync,
+            governance,
+            zbtcz_asset: format!("{}::zbtcz-token", zbtcz_contract),
+            governance_contract: governance_contract.to_string(),
+            storage_path,
+            record_path: None,
+        })
+    }
+
+    pub fn with_recording(mut self, record_path: PathBuf) -> Self {
+        self.record_path = Some(record_path);
+        self
+    }
+
+    /// Entry point for payloads POSTed by the Stacks node
+    pub async fn handle(&self, path: &str, payload: Value) -> Result<DeliveryOutcome, String> {
+        if let Some(record_path) = &self.record_path {
+            let delivery = ObservedDelivery {
+                path: path.to_string(),
+                payload: payload.clone(),
+            };
+            let line = serde_json::to_string(&delivery).map_err(|_| "Serialization failed")?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(record_path)
+                .map_err(|_| "Failed to open recording file")?;
+            writeln!(file, "{}", line).map_err(|_| "Failed to write recording")?;
+        }
+        self.dispatch(path, payload).await
+    }
+
+    /// Re-applies deliveries recorded by `with_recording`, returning how many were read
+    pub async fn replay(&self, record_path: &Path) -> Result<usize, String> {
+        let content = fs::read_to_string(record_path).map_err(|_| "Failed to read recording")?;
+        let mut count = 0;
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let delivery: ObservedDelivery =
+                serde_json::from_str(line).map_err(|err| format!("Invalid recorded delivery: {}", err))?;
+            self.dispatch(&delivery.path, delivery.payload).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    async fn dispatch(&self, path: &str, payload: Value) -> Result<DeliveryOutcome, String> {
+        let outcome = match path.trim_start_matches('/') {
+            "new_block" => self.process_block(payload).await?,
+            "new_burn_block" => self.process_burn_block(&payload).await?,
+            _ => DeliveryOutcome::Ignored, // Mempool and attachment notifications
+        };
+        if outcome != DeliveryOutcome::Ignored && outcome != DeliveryOutcome::Duplicate {
+            self.save_to_disk().await?;
+        }
+        Ok(outcome)
+    }
+
+    pub async fn save_to_disk(&self) -> Result<(), String> {
+        let state = self.state.lock().await;
+        let serialized = serde_json::to_string(&*state).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+
+    async fn process_burn_block(&self, payload: &Value) -> Result<DeliveryOutcome, String> {
+        let height = payload["burn_block_height"].as_u64().ok_or("Burn block is missing its height")?;
+        let mut state = self.state.lock().await;
+        if height <= state.burn_block_height {
+            return Ok(DeliveryOutcome::Duplicate);
+        }
+        state.burn_block_height = height;
+        Ok(DeliveryOutcome::Applied { blocks: 1 })
+    }
+
+    async fn process_block(&self, payload: Value) -> Result<DeliveryOutcome, String> {
+        let hash = block_field(&payload, "index_block_hash")?;
+        let parent = block_field(&payload, "parent_index_block_hash")?;
+        let mut state = self.state.lock().await;
+
+        if state.seen_blocks.contains(&hash) || buffered(&state, &hash) {
+            return Ok(DeliveryOutcome::Duplicate);
+        }
+
+        // Deliveries can arrive out of order after node retries; hold children until their parent is applied
+        if state.tip_hash.is_some() && !state.seen_blocks.contains(&parent) {
+            state.orphans.entry(parent).or_default().push(payload);
+            if state.orphans.values().map(Vec::len).sum::<usize>() <= MAX_BUFFERED_BLOCKS {
+                return Ok(DeliveryOutcome::Buffered);
+            }
+            // The gap is not going to be filled; continue from the lowest buffered block
+            let payload = pop_lowest_orphan(&mut state).ok_or("Orphan buffer is empty")?;
+            let blocks = self.apply_with_descendants(&mut state, payload).await?;
+            return Ok(DeliveryOutcome::Applied { blocks });
+        }
+
+        let blocks = self.apply_with_descendants(&mut state, payload).await?;
+        Ok(DeliveryOutcome::Applied { blocks })
+    }
+
+    async fn apply_with_descendants(&self, state: &mut ObserverState, payload: Value) -> Result<usize, String> {
+        let mut queue = vec![payload];
+        let mut applied = 0;
+        while let Some(block) = queue.pop() {
+            let hash = block_field(&block, "index_block_hash")?;
+            self.apply_block(state, &block).await?;
+            applied += 1;
+            if let Some(children) = state.orphans.remove(&hash) {
+                queue.extend(children);
+            }
+        }
+        Ok(applied)
+    }
+
+    async fn apply_block(&self, state: &mut ObserverState, block: &Value) -> Result<(), String> {
+        let hash = block_field(block, "index_block_hash")?;
+        let height = block["block_height"].as_u64().ok_or("Block is missing its height")?;
+
+        let mut events_by_tx: HashMap<String, Vec<ContractEvent>> = HashMap::new();
+        let mut event_ids_by_tx: HashMap<String, HashSet<String>> = HashMap::new();
+        for event in block["events"].as_array().into_iter().flatten() {
+            if event["committed"] == false {
+                continue;
+            }
+            let txid = block_field(event, "txid")?;
+            let event_id = format!("{}:{}", txid, event["event_index"].as_u64().unwrap_or_default());
+            if state.seen_events.contains(&event_id) || !event_ids_by_tx.entry(txid.clone()).or_default().insert(event_id) {
+                continue; // Already applied from an earlier delivery or a sibling fork
+            }
+            if let Some(parsed) = ContractEvent::from_observer(event)? {
+                events_by_tx.entry(txid).or_default().push(parsed);
+            }
+        }
+
+        for transaction in block["transactions"].as_array().into_iter().flatten() {
+            let txid = block_field(transaction, "txid")?;
+            let events = events_by_tx.remove(&txid).unwrap_or_default();
+            self.apply_transaction(transaction, &txid, events).await?;
+            // Marked only once applied, so a failed transaction's events apply on redelivery
+            for event_id in event_ids_by_tx.remove(&txid).unwrap_or_default() {
+                state.seen_events.insert(event_id);
+            }
+        }
+
+        state.seen_blocks.insert(hash.clone());
+        if state.tip_hash.is_none() || height >= state.tip_height {
+            state.tip_height = height;
+            state.tip_hash = Some(hash);
+        }
+        println!("Observed Stacks block {} (height {})", block["block_hash"], height);
+        Ok(())
+    }
+
+    async fn apply_transaction(&self, transaction: &Value, txid: &str, events: Vec<ContractEvent>) -> Result<(), String> {
+        let mut ledger = self.bridge_ledger.lock().await;
+
+        // Calls submitted by this node settle through their receipt
+        if let Some(op) = ledger.pending.get(txid) {
+            let receipt = TransactionReceipt::from_observer(&op.receipt.contract_source, transaction, events.clone())?;
+            let outcome = ledger.apply_receipt(&receipt);
+            ledger.finish_operation(txid, outcome);
+        }
+
+        for event in events {
+            match event {
+                ContractEvent::FtMint { asset_identifier, recipient, amount } if asset_identifier == self.zbtcz_asset => {
+                    let amount = u64::try_from(amount).map_err(|_| "Mint amount out of range")?;
+                    ledger.record_observed_mint(txid, recipient.clone(), amount);
+                    self.cross_layer_sync.record_lock_event(LockEvent {
+                        tx_id: txid.to_string(),
+                        amount,
+                        from_address: asset_identifier,
+                        to_address: recipient,
+                        timestamp: Utc::now(),
+                    })?;
+                }
+                ContractEvent::FtBurn { asset_identifier, sender, amount } if asset_identifier == self.zbtcz_asset => {
+                    let amount = u64::try_from(amount).map_err(|_| "Burn amount out of range")?;
+                    ledger.record_observed_burn(txid, sender.clone(), amount);
+                    self.cross_layer_sync.record_burn_event(BurnEvent {
+                        tx_id: txid.to_string(),
+                        amount,
+                        from_address: sender,
+                        to_address: asset_identifier,
+                        timestamp: Utc::now(),
+                    })?;
+                }
+                ContractEvent::Print { contract_identifier, value } if contract_identifier == self.governance_contract => {
+                    apply_governance_print(&self.governance, &value)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+}
+
+fn block_field(value: &Value, field: &str) -> Result<String, String> {
+    value[field]
+        .as_str()
+        .map(|hash| hash.trim_start_matches("0x").to_string())
+        .ok_or_else(|| format!("Payload is missing {}", field))
+}
+
+fn buffered(state: &ObserverState, hash: &str) -> bool {
+    state
+        .orphans
+        .values()
+        .flatten()
+        .any(|block| block_field(block, "index_block_hash").ok().as_deref() == Some(hash))
+}
+
+fn pop_lowest_orphan(state: &mut ObserverState) -> Option<Value> {
+    let (parent, index) = state
+        .orphans
+        .iter()
+        .flat_map(|(parent, blocks)| blocks.iter().enumerate().map(move |(index, block)| (parent, index, block)))
+        .min_by_key(|(_, _, block)| block["block_height"].as_u64().unwrap_or_default())
+        .map(|(parent, index, _)| (parent.clone(), index))?;
+
+    let siblings = state.orphans.get_mut(&parent)?;
+    let block = siblings.remove(index);
+    if siblings.is_empty() {
+        state.orphans.remove(&parent);
+    }
+    Some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::btcz_integration::BTCZIntegration;
+    use crate::bridge::state_anchoring::StateAnchoring;
+    use crate::clarity::{ClarityInteractor, Principal};
+    use crate::clarity::value::ClarityValue;
+    use crate::governance::parameters::ParameterRegistry;
+    use crate::governance::proposal_engine::{ProposalAction, ProposalState};
+    use crate::governance::validator_policies::GovernanceValidatorPolicies;
+    use crate::governance::validator_rewards_tokenomics::ValidatorRewards;
+    use crate::validator::node_registration::ValidatorRegistry;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    const CONTRACTS: &str = "SP000000000000000000002Q6VF78";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zook_observer_{}_{}", std::process::id(), name))
+    }
+
+    fn observer(name: &str) -> StacksEventObserver {
+        let state_anchoring = Arc::new(StateAnchoring::new());
+        let clarity = ClarityInteractor::new(
+            "http://localhost:20443",
+            Principal::new(CONTRACTS.to_string()),
+            &format!("{}.zbtcz", CONTRACTS),
+            &format!("{}.gbtcz", CONTRACTS),
+            &format!("{}.governance", CONTRACTS),
+            &format!("{}.bridge", CONTRACTS),
+            &format!("{}.validator-rewards", CONTRACTS),
+        );
+        let ledger = BridgeLedger::new(
+            state_anchoring.clone(),
+            Arc::new(clarity),
+            Arc::new(BTCZIntegration::new("http://localhost:8232".to_string())),
+        );
+        let parameters = ParameterRegistry::new()
+            .register(state_anchoring)
+            .register(Arc::new(Mutex::new(ValidatorRewards::new(100, temp_path("rewards.json")))));
+        let governance = ProposalEngine::new(
+            Arc::new(parameters),
+            Arc::new(ValidatorRegistry::new(1_000, temp_path(&format!("{}_validators.json", name)))),
+            Arc::new(GovernanceValidatorPolicies::new(1_000, 80, 70)),
+            temp_path(&format!("{}_proposals.json", name)),
+        )
+        .unwrap();
+        let sync = CrossLayerSync::new(Arc::new(BTCZIntegration::new("http://localhost:8232".to_string())));
+
+        StacksEventObserver::new(
+            Arc::new(TokioMutex::new(ledger)),
+            Arc::new(sync),
+            Arc::new(governance),
+            &format!("{}.zbtcz", CONTRACTS),
+            &format!("{}.governance", CONTRACTS),
+            temp_path(&format!("{}_state.json", name)),
+        )
+        .unwrap()
+    }
+
+    fn block(height: u64, hash: &str, parent: &str, events: Vec<Value>) -> Value {
+        let txids: HashSet<String> = events.iter().map(|e| e["txid"].as_str().unwrap().to_string()).collect();
+        let transactions: Vec<Value> = txids
+            .into_iter()
+            .map(|txid| json!({ "txid": txid, "status": "success", "raw_result": ClarityValue::ok(ClarityValue::Bool(true)).to_hex() }))
+            .collect();
+        json!({
+            "block_hash": format!("0x{}", hash),
+            "block_height": height,
+            "index_block_hash": format!("0x{}", hash),
+            "parent_index_block_hash": format!("0x{}", parent),
+            "transactions": transactions,
+            "events": events,
+        })
+    }
+
+    fn mint(txid: &str, recipient: &str, amount: u64) -> Value {
+        json!({
+            "txid": txid, "event_index": 0, "committed": true, "type": "ft_mint_event",
+            "ft_mint_event": { "asset_identifier": format!("{}.zbtcz::zbtcz-token", CONTRACTS), "recipient": recipient, "amount": amount.to_string() }
+        })
+    }
+
+    fn burn(txid: &str, sender: &str, amount: u64) -> Value {
+        json!({
+            "txid": txid, "event_index": 0, "committed": true, "type": "ft_burn_event",
+            "ft_burn_event": { "asset_identifier": format!("{}.zbtcz::zbtcz-token", CONTRACTS), "sender": sender, "amount": amount.to_string() }
+        })
+    }
+
+    fn finalized(txid: &str, param: &str, value: u128, approved: bool) -> Value {
+        let print = ClarityValue::tuple([
+            ("event", ClarityValue::StringAscii("proposal-finalized".to_string())),
+            ("param-name", ClarityValue::StringAscii(param.to_string())),
+            ("value", ClarityValue::UInt(value)),
+            ("approved", ClarityValue::Bool(approved)),
+        ]);
+        json!({
+            "txid": txid, "event_index": 0, "committed": true, "type": "contract_event",
+            "contract_event": { "contract_identifier": format!("{}.governance", CONTRACTS), "topic": "print", "raw_value": print.to_hex() }
+        })
+    }
+
+    fn cleanup(name: &str) {
+        for suffix in ["validators.json", "proposals.json", "state.json", "recording.jsonl"] {
+            let _ = fs::remove_file(temp_path(&format!("{}_{}", name, suffix)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Unreadable observer state")]
+    fn test_unreadable_state_is_refused() {
+        fs::write(temp_path("damaged_state.json"), "{").unwrap();
+        observer("damaged");
+    }
+
+    #[tokio::test]
+    async fn test_block_events_update_bridge_and_governance() {
+        let observer = observer("events");
+        let actions = vec![ProposalAction::new("anchoring_frequency", 600)];
+        let proposal_id = observer
+            .governance
+            .submit_proposal("alice".to_string(), "Faster anchoring".to_string(), actions)
+            .unwrap();
+
+        let payload = block(
+            1,
+            "aa",
+            "00",
+            vec![mint("0x01", "alice", 100), burn("0x02", "bob", 40), finalized("0x03", "anchoring_frequency", 600, true)],
+        );
+        assert_eq!(observer.handle("/new_block", payload.clone()).await.unwrap(), DeliveryOutcome::Applied { blocks: 1 });
+
+        {
+            let ledger = observer.bridge_ledger.lock().await;
+            assert_eq!(ledger.get_locked_balance(&"alice".to_string()), 100);
+            assert_eq!(ledger.get_burned_balance(&"bob".to_string()), 40);
+        }
+        assert_eq!(observer.cross_layer_sync.lock_events.lock().unwrap().len(), 1);
+        assert_eq!(observer.cross_layer_sync.burn_events.lock().unwrap().len(), 1);
+        // Approved on-chain, the change still waits out the timelock
+        let proposal = observer.governance.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.state, ProposalState::Queued);
+
+        // Redelivery of the same block is a no-op
+        assert_eq!(observer.handle("/new_block", payload).await.unwrap(), DeliveryOutcome::Duplicate);
+        assert_eq!(observer.bridge_ledger.lock().await.get_locked_balance(&"alice".to_string()), 100);
+        assert_eq!(observer.cross_layer_sync.lock_events.lock().unwrap().len(), 1);
+
+        // Burn blocks only move forward
+        let burn_block = json!({ "burn_block_hash": "0xff", "burn_block_height": 10 });
+        assert_eq!(observer.handle("/new_burn_block", burn_block.clone()).await.unwrap(), DeliveryOutcome::Applied { blocks: 1 });
+        assert_eq!(observer.handle("/new_burn_block", burn_block).await.unwrap(), DeliveryOutcome::Duplicate);
+        cleanup("events");
+    }
+
+    #[tokio::test]
+    async fn test_failed_transaction_applies_on_redelivery() {
+        let observer = observer("redelivery");
+        let actions = vec![ProposalAction::new("anchoring_frequency", 600)];
+        let proposal_id = observer
+            .governance
+            .submit_proposal("alice".to_string(), "Faster anchoring".to_string(), actions)
+            .unwrap();
+
+        // A vote print without its voter fails the transaction carrying it
+        let mut malformed = finalized("0x22", "anchoring_frequency", 600, true);
+        let print = ClarityValue::tuple([
+            ("event", ClarityValue::StringAscii("vote-cast".to_string())),
+            ("param-name", ClarityValue::StringAscii("anchoring_frequency".to_string())),
+            ("value", ClarityValue::UInt(600)),
+        ]);
+        malformed["contract_event"]["raw_value"] = json!(print.to_hex());
+        let failing = block(1, "c1", "00", vec![mint("0x21", "dave", 9), malformed]);
+        assert!(observer.handle("/new_block", failing).await.is_err());
+        assert!(!observer.state.lock().await.seen_events.contains("22:0"));
+
+        let fixed = block(1, "c1", "00", vec![mint("0x21", "dave", 9), finalized("0x22", "anchoring_frequency", 600, true)]);
+        assert_eq!(observer.handle("/new_block", fixed).await.unwrap(), DeliveryOutcome::Applied { blocks: 1 });
+        assert_eq!(observer.bridge_ledger.lock().await.get_locked_balance(&"dave".to_string()), 9);
+        assert_eq!(observer.governance.get_proposal(proposal_id).unwrap().state, ProposalState::Queued);
+        cleanup("redelivery");
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_delivery_and_replay() {
+        let recording = temp_path("ordering_recording.jsonl");
+        let live = observer("ordering").with_recording(recording.clone());
+
+        live.handle("/new_block", block(1, "b1", "00", vec![mint("0x11", "carol", 5)])).await.unwrap();
+        let early = live.handle("/new_block", block(3, "b3", "b2", vec![mint("0x13", "carol", 7)])).await.unwrap();
+        assert_eq!(early, DeliveryOutcome::Buffered);
+        assert_eq!(live.bridge_ledger.lock().await.get_locked_balance(&"carol".to_string()), 5);
+
+        let filled = live.handle("/new_block", block(2, "b2", "b1", vec![mint("0x12", "carol", 6)])).await.unwrap();
+        assert_eq!(filled, DeliveryOutcome::Applied { blocks: 2 });
+        assert_eq!(live.bridge_ledger.lock().await.get_locked_balance(&"carol".to_string()), 18);
+        assert_eq!(live.state.lock().await.tip_height, 3);
+
+        // Replaying the recorded deliveries into a fresh node reproduces the same state
+        let replayed = observer("replay");
+        assert_eq!(replayed.replay(&recording).await.unwrap(), 3);
+        assert_eq!(replayed.bridge_ledger.lock().await.get_locked_balance(&"carol".to_string()), 18);
+        assert_eq!(replayed.state.lock().await.tip_hash.as_deref(), Some("b3"));
+        cleanup("ordering");
+        cleanup("replay");
+    }
+}
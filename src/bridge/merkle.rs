@@ -116,7 +116,17 @@ impl MerkleTree {
                         });
                     }
                     [left] => {
-                        next_layer.push(left.clone());
+                        // Paired with itself, as in `build_tree`
+                        if i == index.unwrap() / 2 {
+                            proof.push(left.hash.clone());
+                        }
+
+                        let mut hasher = Sha256::new();
+                        hasher.update(&left.hash);
+                        hasher.update(&left.hash);
+                        next_layer.push(MerkleNode {
+                            hash: hasher.finalize().to_vec(),
+                        });
                     }
                     _ => unreachable!(),
                 }
@@ -191,6 +201,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_odd_number_of_transactions() {
+        let transactions = vec![b"tx1".to_vec(), b"tx2".to_vec(), b"tx3".to_vec()];
+
+        let merkle_tree = MerkleTree::new(transactions.clone());
+        let root = merkle_tree.get_root();
+
+        for tx in &transactions {
+            let proof = merkle_tree.get_proof(tx);
+            assert!(MerkleTree::verify_proof(proof, root.clone(), tx.clone()));
+        }
+    }
+
     #[test]
     fn test_invalid_transaction() {
         let transactions = vec![
@@ -5,8 +5,11 @@ pub mod bridge_finalization;
 pub mod cross_layer_sync;
 pub mod state_anchoring;
 pub mod btcz_integration;
+#[allow(dead_code)] // Only BridgeModule and the tests use it so far
 pub mod validator;
 pub mod bridge_logic;
+pub mod event_observer;
+pub mod sponsorship;
 
 use bridge_finalization::{BridgeFinalization, LockRequest, BurnRequest};
 use cross_layer_sync::{CrossLayerSync, LockEvent, BurnEvent};
@@ -16,6 +19,7 @@ use crate::bridge::btcz_integration::BTCZIntegration;
 use crate::bridge::validator::ValidatorState;
 use std::sync::Arc;
 
+#[allow(dead_code)] // The node wires these components up individually in main.rs
 pub struct BridgeModule {
     pub merkle_tree: MerkleTree,
     pub finalization: BridgeFinalization,
@@ -23,6 +27,7 @@ pub struct BridgeModule {
     pub state_anchoring: StateAnchoring,
 }
 
+#[allow(dead_code)]
 impl BridgeModule {
     pub fn new(transactions: Vec<String>, validator_state: Arc<ValidatorState>, btcz_integration: Arc<BTCZIntegration>) -> Self {
         let transaction_hashes = transactions
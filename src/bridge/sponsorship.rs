@@ -0,0 +1,241 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Mutex as TokioMutex;
+use crate::clarity::ClarityInteractor;
+
+/// How many transactions each user may have sponsored per rolling window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SponsorQuota {
+    pub max_transactions: usize,
+    pub window_secs: i64,
+}
+
+impl Default for SponsorQuota {
+    fn default() -> Self {
+        Self {
+            max_transactions: 10,
+            window_secs: 86_400,
+        }
+    }
+}
+
+/// Last observed state of the sponsor account
+#[derive(Debug, Clone, Serialize)]
+pub struct SponsorStatus {
+    pub sponsor: String,
+    pub balance: Option<u128>, // micro-STX
+    pub min_balance: u128,
+    pub low_balance: bool,
+    pub checked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SponsoredTransaction {
+    pub txid: String,
+    pub origin: String,
+    pub function: String,
+    pub remaining_quota: usize,
+}
+
+/// Pays fees for user-signed bridge calls, within per-user quotas and only while the
+/// sponsor account holds at least `min_balance`
+pub struct SponsorshipService {
+    clarity: Arc<ClarityInteractor>,
+    quota: SponsorQuota,
+    // Held across the broadcast so quota checks and sponsor nonces stay sequential
+    usage: TokioMutex<HashMap<String, Vec<DateTime<Utc>>>>,
+    status: Mutex<SponsorStatus>,
+    storage_path: PathBuf,
+}
+
+impl SponsorshipService {
+    pub fn new(
+        clarity: Arc<ClarityInteractor>,
+        quota: SponsorQuota,
+        min_balance: u128,
+        storage_path: PathBuf,
+    ) -> Result<Self, String> {
+        let sponsor = clarity.sponsor.as_ref().ok_or("No sponsor key configured")?.address();
+        // Starting with no usage would hand every user a fresh quota
+        let usage = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable sponsorship usage in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Default::default(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+
+        Ok(Self {
+            clarity,
+            quota,
+            usage: TokioMutex::new(usage),
+            status: Mutex::new(SponsorStatus {
+                sponsor,
+                balance: None,
+                min_balance,
+                low_balance: false,
+                checked_at: None,
+                last_error: None,
+            }),
+            storage_path,
+        })
+    }
+
+    /// Verifies, pays for and broadcasts a hex-encoded user-signed transaction
+    pub async fn sponsor(&self, raw_hex: &str) -> Result<SponsoredTransaction, String> {
+        let raw = hex::decode(raw_hex.trim().trim_start_matches("0x")).map_err(|_| "Invalid transaction hex")?;
+        let transaction = self.clarity.decode_sponsored(&raw)?;
+        let origin = transaction.origin_address();
+        let function = transaction.call.function_name.to_string();
+
+        if self.status()?.low_balance {
+            return Err("Sponsorship is paused: sponsor balance is below the minimum".to_string());
+        }
+
+        let mut usage = self.usage.lock().await;
+        let window_start = Utc::now() - Duration::seconds(self.quota.window_secs);
+        let used = usage.entry(origin.clone()).or_default();
+        used.retain(|at| *at > window_start);
+        if used.len() >= self.quota.max_transactions {
+            return Err(format!(
+                "Sponsorship quota of {} transactions per {}s exhausted for {}",
+                self.quota.max_transactions, self.quota.window_secs, origin
+            ));
+        }
+
+        let receipt = self.clarity.submit_sponsored(transaction).await?;
+        used.push(Utc::now());
+        let remaining_quota = self.quota.max_transactions - used.len();
+        self.persist(&usage)?;
+        println!("Sponsored {} for {} in {}", function, origin, receipt.txid);
+
+        Ok(SponsoredTransaction {
+            txid: receipt.txid,
+            origin,
+            function,
+            remaining_quota,
+        })
+    }
+
+    /// Transactions `address` may still have sponsored in the current window
+    pub async fn remaining_quota(&self, address: &str) -> usize {
+        let window_start = Utc::now() - Duration::seconds(self.quota.window_secs);
+        let usage = self.usage.lock().await;
+        let used = usage
+            .get(address)
+            .map_or(0, |times| times.iter().filter(|at| **at > window_start).count());
+        self.quota.max_transactions.saturating_sub(used)
+    }
+
+    pub fn quota(&self) -> &SponsorQuota {
+        &self.quota
+    }
+
+    pub fn status(&self) -> Result<SponsorStatus, String> {
+        Ok(self.status.lock().map_err(|_| "Mutex lock failed")?.clone())
+    }
+
+    /// Refreshes the sponsor balance; sponsorship pauses while it is below the minimum
+    pub async fn check_balance(&self) -> Result<SponsorStatus, String> {
+        let sponsor = self.status()?.sponsor;
+        let balance = self.clarity.account_balance(&sponsor).await;
+
+        let mut status = self.status.lock().map_err(|_| "Mutex lock failed")?;
+        status.checked_at = Some(Utc::now());
+        match balance {
+            Ok(balance) => {
+                let low_balance = balance < status.min_balance;
+                if low_balance && !status.low_balance {
+                    eprintln!(
+                        "Sponsor {} balance {} is below the minimum {}; pausing sponsorship",
+                        sponsor, balance, status.min_balance
+                    );
+                }
+                status.balance = Some(balance);
+                status.low_balance = low_balance;
+                status.last_error = None;
+            }
+            Err(e) => status.last_error = Some(e),
+        }
+        Ok(status.clone())
+    }
+
+    fn persist(&self, usage: &HashMap<String, Vec<DateTime<Utc>>>) -> Result<(), String> {
+        let serialized = serde_json::to_string(usage).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::bindings::transaction_bridge;
+    use crate::clarity::transaction::{SpendingCondition, StacksNetwork, StacksSigner, StacksTransaction};
+
+    const USER_KEY: &str = "edf9aee84d9b7abc145504dde6726c64f369d37ee34ded868fabd876c26570bc01";
+
+    fn service(name: &str, max_transactions: usize) -> SponsorshipService {
+        let sponsor = StacksSigner::from_hex(&"22".repeat(32), StacksNetwork::Testnet).unwrap();
+        let clarity = ClarityInteractor::embedded(&sponsor.address()).unwrap().with_sponsor(sponsor);
+        let path = std::env::temp_dir().join(format!("zook_sponsor_{}_{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        let quota = SponsorQuota { max_transactions, window_secs: 3_600 };
+        SponsorshipService::new(Arc::new(clarity), quota, 1_000, path).unwrap()
+    }
+
+    fn signed_lock(service: &SponsorshipService, tx_id: u128, sponsored: bool) -> String {
+        let user = StacksSigner::from_hex(USER_KEY, StacksNetwork::Testnet).unwrap();
+        let mut tx = StacksTransaction::contract_call(
+            StacksNetwork::Testnet,
+            SpendingCondition::new(user.hash160(), tx_id as u64, 0),
+            &service.clarity.bridge_address,
+            transaction_bridge::lock_btcz(tx_id, 50),
+        )
+        .unwrap();
+        if sponsored {
+            tx = tx.sponsored();
+        }
+        tx.sign(&user).unwrap();
+        hex::encode(tx.serialize())
+    }
+
+    #[tokio::test]
+    async fn test_sponsors_within_quota() {
+        let service = service("quota", 2);
+        let user = StacksSigner::from_hex(USER_KEY, StacksNetwork::Testnet).unwrap().address();
+
+        let first = service.sponsor(&signed_lock(&service, 1, true)).await.unwrap();
+        assert_eq!(first.origin, user);
+        assert_eq!(first.function, "lock-btcz");
+        assert_eq!(first.remaining_quota, 1);
+        service.sponsor(&signed_lock(&service, 2, true)).await.unwrap();
+
+        let err = service.sponsor(&signed_lock(&service, 3, true)).await.unwrap_err();
+        assert!(err.contains("quota"));
+        assert_eq!(service.remaining_quota(&user).await, 0);
+
+        // Damaged usage is refused rather than resetting everyone's quota
+        fs::write(&service.storage_path, "{").unwrap();
+        let reopened = SponsorshipService::new(service.clarity.clone(), service.quota.clone(), 1_000, service.storage_path.clone());
+        assert!(reopened.err().unwrap().starts_with("Unreadable sponsorship usage"));
+        let _ = fs::remove_file(&service.storage_path);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unsponsored_and_tampered_transactions() {
+        let service = service("reject", 5);
+        assert!(service.sponsor(&signed_lock(&service, 1, false)).await.is_err());
+
+        // Flipping the amount argument invalidates the user's signature
+        let mut raw = hex::decode(signed_lock(&service, 1, true)).unwrap();
+        *raw.last_mut().unwrap() ^= 0x01;
+        let err = service.sponsor(&hex::encode(raw)).await.unwrap_err();
+        assert!(err.contains("Signature"));
+        assert_eq!(service.status().unwrap().balance, None);
+    }
+}
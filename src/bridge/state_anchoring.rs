@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::sync::{Arc, Mutex};
 use crate::bridge::merkle::MerkleTree;
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
+use crate::governance::treasury::TreasuryBalance;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct L2StateSummary {
@@ -12,12 +14,18 @@ pub struct L2StateSummary {
     pub total_transactions: u64,
     pub timestamp: DateTime<Utc>,
     pub compliance: bool,
+    #[serde(default)]
+    pub governance_root: Option<String>, // Merkle root of executed governance outcomes
+    #[serde(default)]
+    pub treasury_balance: Option<TreasuryBalance>,
 }
 
 #[derive(Debug)]
 pub struct StateAnchoring {
     pub anchored_states: Arc<Mutex<Vec<L2StateSummary>>>,
     pub anchoring_frequency: Mutex<u64>, // Anchoring frequency in seconds
+    pub governance_root: Mutex<Option<String>>, // Committed into every state anchored from now on
+    pub treasury_balance: Mutex<Option<TreasuryBalance>>, // Likewise
 }
 
 impl StateAnchoring {
@@ -25,10 +33,32 @@ impl StateAnchoring {
         Self {
             anchored_states: Arc::new(Mutex::new(Vec::new())),
             anchoring_frequency: Mutex::new(60), // Default frequency: 60 seconds
+            governance_root: Mutex::new(None),
+            treasury_balance: Mutex::new(None),
         }
     }
 
-    pub fn anchor_state(&self, summary: L2StateSummary) -> Result<(), String> {
+    /// Sets the governance outcome root included in subsequent anchors
+    pub fn commit_governance_root(&self, root: Option<String>) -> Result<(), String> {
+        let mut governance_root = self.governance_root.lock().map_err(|_| "Mutex lock failed")?;
+        *governance_root = root;
+        Ok(())
+    }
+
+    /// Sets the treasury balance included in subsequent anchors
+    pub fn commit_treasury_balance(&self, balance: Option<TreasuryBalance>) -> Result<(), String> {
+        let mut treasury_balance = self.treasury_balance.lock().map_err(|_| "Mutex lock failed")?;
+        *treasury_balance = balance;
+        Ok(())
+    }
+
+    pub fn anchor_state(&self, mut summary: L2StateSummary) -> Result<(), String> {
+        if summary.governance_root.is_none() {
+            summary.governance_root = self.governance_root.lock().map_err(|_| "Mutex lock failed")?.clone();
+        }
+        if summary.treasury_balance.is_none() {
+            summary.treasury_balance = *self.treasury_balance.lock().map_err(|_| "Mutex lock failed")?;
+        }
         let mut states = self.anchored_states.lock().map_err(|_| "Mutex lock failed")?;
         states.push(summary);
         println!("State anchored successfully");
@@ -48,6 +78,19 @@ impl StateAnchoring {
         }
     }
 
+    /// Whether exactly this summary, governance and treasury commitments included, has been anchored
+    pub fn is_anchored(&self, summary: &L2StateSummary) -> bool {
+        match self.anchored_states.lock() {
+            Ok(states) => states.iter().any(|state| {
+                state.block_height == summary.block_height
+                    && state.state_root == summary.state_root
+                    && state.governance_root == summary.governance_root
+                    && state.treasury_balance == summary.treasury_balance
+            }),
+            Err(_) => false,
+        }
+    }
+
     pub fn update_frequency(&self, new_frequency: u64) -> Result<(), String> {
         let mut frequency = self.anchoring_frequency.lock().map_err(|_| "Mutex lock failed")?;
         *frequency = new_frequency;
@@ -61,6 +104,28 @@ impl StateAnchoring {
     }
 }
 
+impl GovernableModule for StateAnchoring {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        Ok(vec![ParameterSpec {
+            name: "anchoring_frequency",
+            module: "anchoring",
+            kind: ParameterType::Duration,
+            min: 10,
+            max: 86_400,
+            unit: "seconds",
+            description: "Interval between L2 state anchors on BTCZ",
+            value: self.get_anchoring_frequency()?,
+        }])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        match name {
+            "anchoring_frequency" => self.update_frequency(value),
+            _ => Err(format!("Unknown anchoring parameter: {}", name)),
+        }
+    }
+}
+
 pub fn generate_state_summary(
     merkle_tree: &MerkleTree,
     block_height: u64,
@@ -73,5 +138,7 @@ pub fn generate_state_summary(
         total_transactions,
         timestamp: Utc::now(),
         compliance,
+        governance_root: None,
+        treasury_balance: None,
     }
 }
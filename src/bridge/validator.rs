@@ -87,7 +87,7 @@ mod tests {
         let validator = ValidatorState::new();
         let tx_id = "tx123".to_string();
 
-        assert_eq!(validator.validate_transaction(&tx_id), false);
+        assert!(!validator.validate_transaction(&tx_id));
 
         let record = BurnRecord {
             tx_id: tx_id.clone(),
@@ -96,7 +96,7 @@ mod tests {
         };
         validator.validate_burn(&record).unwrap();
 
-        assert_eq!(validator.validate_transaction(&tx_id), true);
+        assert!(validator.validate_transaction(&tx_id));
     }
 }
 
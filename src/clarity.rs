@@ -1,15 +1,25 @@
+pub mod bindings;
+pub mod evaluator;
+#[allow(dead_code)] // Shared with build.rs; only the tests use it from the crate
+pub mod parser;
+pub mod post_condition;
+pub mod receipt;
+pub mod transaction;
+pub mod value;
 
-
-pub use crate::interaction::{ClarityInteractor, Principal, Response}; // Adjusted path to re-export the required structs.
+pub use crate::interaction::{ClarityInteractor, Principal}; // Adjusted path to re-export the required structs.
+pub use receipt::TransactionReceipt;
 
 //use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+#[allow(dead_code)] // Callers use ClarityInteractor directly
 #[derive(Clone)]
 pub struct Clarity {
     interactor: Arc<ClarityInteractor>,
 }
 
+#[allow(dead_code)]
 impl Clarity {
     pub fn new(
         api_url: &str,
@@ -17,6 +27,8 @@ impl Clarity {
         zbtcz_address: &str,
         gbtcz_address: &str,
         governance_address: &str,
+        bridge_address: &str,
+        rewards_address: &str,
     ) -> Self {
         let interactor = ClarityInteractor::new(
             api_url,
@@ -24,33 +36,32 @@ impl Clarity {
             zbtcz_address,
             gbtcz_address,
             governance_address,
+            bridge_address,
+            rewards_address,
         );
         Self {
             interactor: Arc::new(interactor),
         }
     }
 
-    pub async fn mint_zbtcz(&self, amount: u128) -> Response {
-        self.interactor.mint_zbtcz(amount).await
-    }
-
-    pub async fn burn_zbtcz(&self, ids: Vec<u128>) -> Response {
-        self.interactor.burn_zbtcz(ids).await
-    }
-
-    pub async fn stake_gbtcz(&self, amount: u128) -> Response {
-        self.interactor.stake_gbtcz(amount).await
+    pub async fn mint_zbtcz(
+        &self,
+        amount: u128,
+        block_height: u128,
+        merkle_root: Vec<u8>,
+    ) -> Result<TransactionReceipt, String> {
+        self.interactor.mint_zbtcz(amount, block_height, merkle_root).await
     }
 
-    pub async fn unstake_gbtcz(&self, amount: u128) -> Response {
-        self.interactor.unstake_gbtcz(amount).await
+    pub async fn burn_zbtcz(&self, amount: u128, tx_id: u128) -> Result<TransactionReceipt, String> {
+        self.interactor.burn_zbtcz(amount, tx_id).await
     }
 
-    pub async fn lock_btcz(&self, tx_id: u128, amount: u128) -> Response {
+    pub async fn lock_btcz(&self, tx_id: u128, amount: u128) -> Result<TransactionReceipt, String> {
         self.interactor.lock_btcz(tx_id, amount).await
     }
 
-    pub async fn unlock_btcz(&self, tx_id: u128) -> Response {
+    pub async fn unlock_btcz(&self, tx_id: u128) -> Result<TransactionReceipt, String> {
         self.interactor.unlock_btcz(tx_id).await
     }
 }
@@ -0,0 +1,121 @@
+// File: src/clarity/bindings.rs
+
+#![allow(dead_code)] // Not every contract function is called from Rust yet
+
+use crate::clarity::value::{ClarityValue, PrincipalData};
+
+/// A contract function call built from the generated bindings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractCall {
+    pub function_name: &'static str,
+    pub read_only: bool,
+    pub args: Vec<ClarityValue>,
+}
+
+/// Signature of a contract function as declared in its `.clar` source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionSpec {
+    pub name: &'static str,
+    pub read_only: bool,
+    pub args: &'static [(&'static str, &'static str)], // (argument name, Clarity type)
+}
+
+// Generated by build.rs from clarity_contracts/**.clar
+include!(concat!(env!("OUT_DIR"), "/contract_bindings.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn public(name: &'static str, args: &'static [(&'static str, &'static str)]) -> FunctionSpec {
+        FunctionSpec { name, read_only: false, args }
+    }
+
+    const fn read_only(name: &'static str, args: &'static [(&'static str, &'static str)]) -> FunctionSpec {
+        FunctionSpec { name, read_only: true, args }
+    }
+
+    // Written out by hand from the .clar sources so a build.rs or parser regression shows up here
+    #[test]
+    fn test_bindings_match_declared_signatures() {
+        assert_eq!(
+            zbtcz::FUNCTIONS,
+            &[
+                public("mint-zbtcz", &[("amount", "uint"), ("block-height", "uint"), ("merkle-root", "(buff 32)")]),
+                public("burn-zbtcz", &[("amount", "uint"), ("tx-id", "uint")]),
+                public("finalize-state", &[("block-height", "uint"), ("merkle-root", "(buff 32)")]),
+            ]
+        );
+        assert_eq!(
+            zbtcz_smart_contract::FUNCTIONS,
+            &[
+                public("mint-zbtcz", &[("amount", "uint")]),
+                public("burn-zbtcz", &[("amount", "uint")]),
+                public("get-balance", &[("user", "principal")]),
+                read_only("get-locked-btcz", &[]),
+            ]
+        );
+        assert_eq!(
+            transaction_bridge::FUNCTIONS,
+            &[
+                public("lock-btcz", &[("tx-id", "uint"), ("amount", "uint")]),
+                public("unlock-btcz", &[("tx-id", "uint")]),
+                read_only("get-locked-transactions", &[]),
+            ]
+        );
+        assert_eq!(
+            aggregate_bridge::FUNCTIONS,
+            &[
+                public("lock-tokens", &[("amount", "uint"), ("sender", "principal")]),
+                public("burn-tokens", &[("amount", "uint"), ("sender", "principal")]),
+                read_only("get-locked-tokens", &[]),
+                read_only("get-burned-tokens", &[]),
+                read_only("get-total-supply", &[]),
+            ]
+        );
+        assert_eq!(
+            staking_rewards::FUNCTIONS,
+            &[
+                public("slash-validator", &[("validator", "principal")]),
+                public("check-validator-activity", &[("validator", "principal")]),
+                public("propose-slashing-penalty-change", &[("new-penalty", "uint")]),
+                public("execute-proposal", &[("proposal-id", "uint")]),
+                read_only("get-validator-info", &[("validator", "principal")]),
+                read_only("get-validator-reward-rate", &[]),
+                read_only("get-slashing-penalty", &[]),
+            ]
+        );
+        assert_eq!(
+            validator_rewards::FUNCTIONS,
+            &[
+                public("record-performance", &[("validator", "principal"), ("activity-score", "uint")]),
+                public("distribute-rewards", &[("validator", "principal")]),
+                read_only("get-total-rewards", &[]),
+                read_only("get-validator-reward", &[("validator", "principal")]),
+            ]
+        );
+        assert_eq!(
+            proposal_voting::FUNCTIONS,
+            &[
+                public("propose", &[("param-name", "(string-ascii 32)"), ("value", "uint")]),
+                public("vote", &[("vote-for", "bool")]),
+                public("finalize-proposal", &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generated_call_encodes_typed_arguments() {
+        let call = zbtcz::mint_zbtcz(500, 42, vec![0u8; 32]);
+        assert_eq!(call.function_name, "mint-zbtcz");
+        assert!(!call.read_only);
+        assert_eq!(
+            call.args,
+            vec![ClarityValue::UInt(500), ClarityValue::UInt(42), ClarityValue::Buffer(vec![0u8; 32])]
+        );
+
+        let call = validator_rewards::get_total_rewards();
+        assert!(call.read_only);
+        assert!(call.args.is_empty());
+    }
+}
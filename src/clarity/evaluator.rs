@@ -0,0 +1,1126 @@
+// File: src/clarity/evaluator.rs
+//
+// Embedded evaluator for the Clarity subset used by clarity_contracts/, so the
+// contract logic can be exercised from Rust without a Stacks devnet. It also
+// accepts the dialect those sources are written in: `(fn (x) ...)` lambdas,
+// `any?`, `get-or-default`, `(tx-sender)` and `proposal.field` access.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use crate::clarity::bindings::ContractCall;
+use crate::clarity::parser::{self, FunctionKind, SExpr, TypeSignature};
+use crate::clarity::post_condition::{self, PostCondition, PostConditionMode};
+use crate::clarity::receipt::{ContractEvent, TransactionReceipt, TxStatus};
+use crate::clarity::value::{ClarityValue, PrincipalData};
+
+pub const CONTRACTS_DIR: &str = "clarity_contracts";
+const MAX_CALL_DEPTH: usize = 64;
+
+/// Non-local exits while evaluating a function body
+enum Flow {
+    Return(ClarityValue), // Thrown by asserts!, unwrap!, try!
+    Runtime(String),      // Aborts the whole transaction
+}
+
+type EvalResult = Result<ClarityValue, Flow>;
+
+fn runtime<T>(message: impl Into<String>) -> Result<T, Flow> {
+    Err(Flow::Runtime(message.into()))
+}
+
+impl From<String> for Flow {
+    fn from(message: String) -> Self {
+        Flow::Runtime(message)
+    }
+}
+
+#[derive(Debug)]
+struct Function {
+    kind: FunctionKind,
+    params: Vec<String>,
+    body: Vec<SExpr>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FungibleToken {
+    supply: u128,
+    max_supply: Option<u128>,
+    balances: HashMap<String, u128>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ContractState {
+    constants: HashMap<String, ClarityValue>,
+    vars: HashMap<String, ClarityValue>,
+    maps: HashMap<String, HashMap<String, ClarityValue>>, // Keyed by the consensus hex of the map key
+    tokens: HashMap<String, FungibleToken>,
+}
+
+#[derive(Debug, Clone)]
+struct Contract {
+    principal: PrincipalData,
+    source: String,
+    functions: HashMap<String, Arc<Function>>,
+    state: ContractState,
+}
+
+impl Contract {
+    fn execute(
+        &mut self,
+        function: &str,
+        args: Vec<ClarityValue>,
+        sender: PrincipalData,
+        block_height: u64,
+        events: &mut Vec<ContractEvent>,
+    ) -> Result<ClarityValue, String> {
+        let mut executor = Executor {
+            principal: &self.principal,
+            functions: &self.functions,
+            state: &mut self.state,
+            events,
+            block_height,
+            depth: 0,
+        };
+        let env = Env {
+            caller: sender.clone(),
+            sender,
+            locals: HashMap::new(),
+        };
+        match executor.call_function(function, args, &env) {
+            Ok(value) | Err(Flow::Return(value)) => Ok(value),
+            Err(Flow::Runtime(message)) => Err(format!("{}: {}", function, message)),
+        }
+    }
+}
+
+/// In-memory chain holding deployed contracts and the receipts of executed calls
+#[derive(Debug, Default)]
+pub struct ClarityVm {
+    contracts: HashMap<String, Contract>,
+    receipts: HashMap<String, TransactionReceipt>,
+    block_height: u64,
+    tx_count: u64,
+}
+
+impl ClarityVm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deploys `code` as `contract_id`; `source` is the path under clarity_contracts/ used for error codes
+    pub fn deploy(&mut self, contract_id: &str, source: &str, code: &str) -> Result<(), String> {
+        let principal = PrincipalData::parse(contract_id)?;
+        let deployer = match &principal {
+            PrincipalData::Contract { version, hash160, .. } => PrincipalData::Standard {
+                version: *version,
+                hash160: *hash160,
+            },
+            PrincipalData::Standard { .. } => return Err(format!("Not a contract identifier: {}", contract_id)),
+        };
+        let exprs = parser::parse(code)?;
+
+        let mut contract = Contract {
+            principal,
+            source: source.to_string(),
+            functions: HashMap::new(),
+            state: ContractState::default(),
+        };
+        for expr in &exprs {
+            if let Some((name, function)) = parse_function(expr)? {
+                contract.functions.insert(name, Arc::new(function));
+            }
+        }
+
+        let mut events = Vec::new();
+        let mut executor = Executor {
+            principal: &contract.principal,
+            functions: &contract.functions,
+            state: &mut contract.state,
+            events: &mut events,
+            block_height: self.block_height,
+            depth: 0,
+        };
+        let env = Env {
+            sender: deployer.clone(),
+            caller: deployer,
+            locals: HashMap::new(),
+        };
+        for expr in &exprs {
+            executor.define(expr, &env).map_err(|flow| match flow {
+                Flow::Runtime(message) => format!("Failed to deploy {}: {}", contract_id, message),
+                Flow::Return(value) => format!("Failed to deploy {}: returned {}", contract_id, value),
+            })?;
+        }
+
+        self.contracts.insert(contract_id.to_string(), contract);
+        Ok(())
+    }
+
+    /// Deploys `clarity_contracts/<source>`
+    pub fn deploy_file(&mut self, contract_id: &str, source: &str) -> Result<(), String> {
+        let path = Path::new(CONTRACTS_DIR).join(source);
+        let code = fs::read_to_string(&path).map_err(|_| format!("Failed to read {}", path.display()))?;
+        self.deploy(contract_id, source, &code)
+    }
+
+    /// Executes a public function as a mined transaction. State changes and events are
+    /// kept only when the function returns `(ok ...)`; runtime errors reject the transaction.
+    pub fn call_public(&mut self, sender: &str, contract_id: &str, call: &ContractCall) -> Result<TransactionReceipt, String> {
+        self.call_public_with_post_conditions(sender, contract_id, call, PostConditionMode::Allow, &[])
+    }
+
+    /// Like `call_public`, but aborts the transaction when its token movements violate
+    /// the post-conditions
+    pub fn call_public_with_post_conditions(
+        &mut self,
+        sender: &str,
+        contract_id: &str,
+        call: &ContractCall,
+        mode: PostConditionMode,
+        post_conditions: &[PostCondition],
+    ) -> Result<TransactionReceipt, String> {
+        let origin = sender;
+        let sender = PrincipalData::parse(sender)?;
+        let contract = self
+            .contracts
+            .get_mut(contract_id)
+            .ok_or_else(|| format!("Contract not deployed: {}", contract_id))?;
+        if contract.functions.get(call.function_name).map(|f| f.kind) != Some(FunctionKind::Public) {
+            return Err(format!("{} is not a public function of {}", call.function_name, contract_id));
+        }
+
+        let snapshot = contract.state.clone();
+        let mut events = Vec::new();
+        let result = contract.execute(call.function_name, call.args.clone(), sender, self.block_height + 1, &mut events);
+        let status = match &result {
+            Ok(ClarityValue::Response(Ok(_))) => match post_condition::check(mode, post_conditions, origin, &events) {
+                Ok(()) => TxStatus::Success,
+                Err(_) => TxStatus::AbortByPostCondition,
+            },
+            Ok(ClarityValue::Response(Err(_))) => TxStatus::AbortByResponse,
+            Ok(other) => {
+                contract.state = snapshot;
+                return Err(format!("{} returned {} instead of a response", call.function_name, other));
+            }
+            Err(message) => {
+                contract.state = snapshot;
+                return Err(message.clone());
+            }
+        };
+        if status != TxStatus::Success {
+            contract.state = snapshot;
+            events.clear();
+        }
+
+        self.block_height += 1;
+        self.tx_count += 1;
+        let receipt = TransactionReceipt {
+            txid: format!("{:064x}", self.tx_count),
+            contract_source: contract.source.clone(),
+            status,
+            result: result.ok(),
+            events,
+        };
+        self.receipts.insert(receipt.txid.clone(), receipt.clone());
+        Ok(receipt)
+    }
+
+    /// Evaluates a function without committing any of its writes, like `call-read` on a node
+    pub fn call_read_only(&self, sender: &str, contract_id: &str, call: &ContractCall) -> Result<ClarityValue, String> {
+        let sender = PrincipalData::parse(sender)?;
+        let mut contract = self
+            .contracts
+            .get(contract_id)
+            .cloned()
+            .ok_or_else(|| format!("Contract not deployed: {}", contract_id))?;
+        contract.execute(call.function_name, call.args.clone(), sender, self.block_height, &mut Vec::new())
+    }
+
+    pub fn receipt(&self, txid: &str) -> Option<&TransactionReceipt> {
+        self.receipts.get(txid)
+    }
+
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    pub fn advance_blocks(&mut self, blocks: u64) {
+        self.block_height += blocks;
+    }
+
+    /// Current value of a data-var, for assertions in tests
+    pub fn data_var(&self, contract_id: &str, name: &str) -> Option<&ClarityValue> {
+        self.contracts.get(contract_id)?.state.vars.get(name)
+    }
+
+    pub fn ft_balance(&self, contract_id: &str, token: &str, owner: &str) -> u128 {
+        self.contracts
+            .get(contract_id)
+            .and_then(|contract| contract.state.tokens.get(token))
+            .and_then(|token| token.balances.get(owner).copied())
+            .unwrap_or_default()
+    }
+}
+
+fn parse_function(expr: &SExpr) -> Result<Option<(String, Function)>, String> {
+    let items = match expr.as_list() {
+        Some(items) => items,
+        None => return Ok(None),
+    };
+    let kind = match items.first().and_then(SExpr::as_atom) {
+        Some("define-public") => FunctionKind::Public,
+        Some("define-read-only") => FunctionKind::ReadOnly,
+        Some("define-private") => FunctionKind::Private,
+        _ => return Ok(None),
+    };
+    let header = items.get(1).and_then(SExpr::as_list).ok_or("Malformed function definition")?;
+    let name = header.first().and_then(SExpr::as_atom).ok_or("Function is missing its name")?;
+    let params = header[1..]
+        .iter()
+        .map(|param| match param.as_list() {
+            Some([SExpr::Atom(param), _]) => Ok(param.clone()),
+            _ => Err(format!("{}: malformed argument", name)),
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(Some((
+        name.to_string(),
+        Function {
+            kind,
+            params,
+            body: items[2..].to_vec(),
+        },
+    )))
+}
+
+/// Default value of a data-var declared without one
+fn default_value(ty: &TypeSignature, deployer: &PrincipalData) -> ClarityValue {
+    match ty {
+        TypeSignature::Int => ClarityValue::Int(0),
+        TypeSignature::UInt => ClarityValue::UInt(0),
+        TypeSignature::Bool => ClarityValue::Bool(false),
+        TypeSignature::Principal => ClarityValue::Principal(deployer.clone()),
+        TypeSignature::Buffer(_) => ClarityValue::Buffer(Vec::new()),
+        TypeSignature::StringAscii(_) => ClarityValue::StringAscii(String::new()),
+        TypeSignature::StringUtf8(_) => ClarityValue::StringUtf8(String::new()),
+        TypeSignature::Optional(_) => ClarityValue::none(),
+        TypeSignature::Response(ok, _) => ClarityValue::ok(default_value(ok, deployer)),
+        TypeSignature::List(..) => ClarityValue::List(Vec::new()),
+        TypeSignature::Tuple(fields) => ClarityValue::tuple(
+            fields
+                .iter()
+                .map(|(name, ty)| (name.clone(), default_value(ty, deployer))),
+        ),
+    }
+}
+
+#[derive(Clone)]
+struct Env {
+    sender: PrincipalData,
+    caller: PrincipalData,
+    locals: HashMap<String, ClarityValue>,
+}
+
+struct Executor<'a> {
+    principal: &'a PrincipalData,
+    functions: &'a HashMap<String, Arc<Function>>,
+    state: &'a mut ContractState,
+    events: &'a mut Vec<ContractEvent>,
+    block_height: u64,
+    depth: usize,
+}
+
+impl Executor<'_> {
+    /// Evaluates a top-level form at deploy time
+    fn define(&mut self, expr: &SExpr, env: &Env) -> Result<(), Flow> {
+        let items = match expr.as_list() {
+            Some(items) if !items.is_empty() => items,
+            _ => return self.eval(expr, env).map(|_| ()),
+        };
+        let name = || items.get(1).and_then(SExpr::as_atom).map(str::to_string).ok_or_else(|| Flow::Runtime("Definition is missing its name".into()));
+
+        match items[0].as_atom() {
+            Some("define-public" | "define-read-only" | "define-private" | "define-trait" | "impl-trait" | "use-trait") => {}
+            Some("define-constant") => {
+                let value = self.eval(arg(items, 2)?, env)?;
+                self.state.constants.insert(name()?, value);
+            }
+            Some("define-data-var") => {
+                let ty = arg(items, 2)?;
+                // Some contracts declare maps as `(define-data-var name (map key value) {})`
+                if ty.as_list().and_then(|ty| ty.first()).and_then(SExpr::as_atom) == Some("map") {
+                    self.state.maps.insert(name()?, HashMap::new());
+                    return Ok(());
+                }
+                let value = match items.get(3) {
+                    Some(initial) => self.eval(initial, env)?,
+                    None => default_value(&TypeSignature::parse(ty)?, &env.sender),
+                };
+                self.state.vars.insert(name()?, value);
+            }
+            Some("define-map") => {
+                self.state.maps.insert(name()?, HashMap::new());
+            }
+            Some("define-fungible-token") => {
+                let max_supply = match items.get(2) {
+                    Some(supply) => Some(self.eval(supply, env)?.expect_u128()?),
+                    None => None,
+                };
+                self.state.tokens.insert(
+                    name()?,
+                    FungibleToken {
+                        max_supply,
+                        ..FungibleToken::default()
+                    },
+                );
+            }
+            _ => {
+                self.eval(expr, env)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn eval(&mut self, expr: &SExpr, env: &Env) -> EvalResult {
+        match expr {
+            SExpr::Str(text) => Ok(ClarityValue::StringAscii(text.clone())),
+            SExpr::Atom(atom) => self.eval_atom(atom, env),
+            SExpr::Tuple(fields) => {
+                let mut tuple = BTreeMap::new();
+                for (name, value) in fields {
+                    tuple.insert(name.clone(), self.eval(value, env)?);
+                }
+                Ok(ClarityValue::Tuple(tuple))
+            }
+            SExpr::Sequence(items) => Ok(ClarityValue::List(self.eval_all(items, env)?)),
+            SExpr::List(items) => match items.split_first() {
+                Some((SExpr::Atom(name), args)) => self.apply(name, args, env),
+                Some(_) => runtime("Expression must start with a function name"),
+                None => runtime("Empty expression"),
+            },
+        }
+    }
+
+    fn eval_all(&mut self, exprs: &[SExpr], env: &Env) -> Result<Vec<ClarityValue>, Flow> {
+        exprs.iter().map(|expr| self.eval(expr, env)).collect()
+    }
+
+    fn eval_body(&mut self, body: &[SExpr], env: &Env) -> EvalResult {
+        let mut last = None;
+        for expr in body {
+            last = Some(self.eval(expr, env)?);
+        }
+        last.map_or_else(|| runtime("Empty body"), Ok)
+    }
+
+    fn eval_atom(&mut self, atom: &str, env: &Env) -> EvalResult {
+        if let Some(value) = env.locals.get(atom) {
+            return Ok(value.clone());
+        }
+        match atom {
+            "true" => return Ok(ClarityValue::Bool(true)),
+            "false" => return Ok(ClarityValue::Bool(false)),
+            "none" => return Ok(ClarityValue::none()),
+            "tx-sender" => return Ok(ClarityValue::Principal(env.sender.clone())),
+            "contract-caller" => return Ok(ClarityValue::Principal(env.caller.clone())),
+            "block-height" | "stacks-block-height" | "burn-block-height" => {
+                return Ok(ClarityValue::UInt(self.block_height.into()))
+            }
+            _ => {}
+        }
+        if let Some(value) = self.state.constants.get(atom) {
+            return Ok(value.clone());
+        }
+        if let Some(digits) = atom.strip_prefix('u').filter(|d| !d.is_empty() && d.bytes().all(|b| b.is_ascii_digit())) {
+            return digits.parse().map(ClarityValue::UInt).or_else(|_| runtime("uint literal out of range"));
+        }
+        if let Ok(int) = atom.parse::<i128>() {
+            return Ok(ClarityValue::Int(int));
+        }
+        if let Some(hex) = atom.strip_prefix("0x") {
+            return hex::decode(hex).map(ClarityValue::Buffer).or_else(|_| runtime("Invalid buffer literal"));
+        }
+        if let Some(principal) = atom.strip_prefix('\'') {
+            return Ok(ClarityValue::principal(principal)?);
+        }
+        if let Some(name) = atom.strip_prefix('.') {
+            return Ok(ClarityValue::principal(&format!("{}.{}", self.principal.address(), name))?);
+        }
+        // `proposal.param-name` reads a tuple field
+        if let Some((base, field)) = atom.split_once('.') {
+            if let Some(value) = env.locals.get(base) {
+                return Ok(value.get(field)?.clone());
+            }
+        }
+        runtime(format!("Unknown identifier '{}'", atom))
+    }
+
+    fn apply(&mut self, name: &str, args: &[SExpr], env: &Env) -> EvalResult {
+        match name {
+            "begin" => self.eval_body(args, env),
+            "let" => {
+                let bindings = arg(args, 0)?.as_list().ok_or_else(|| Flow::Runtime("Malformed let bindings".into()))?;
+                let mut scope = env.clone();
+                for binding in bindings {
+                    match binding.as_list() {
+                        Some([SExpr::Atom(local), value]) => {
+                            let value = self.eval(value, &scope)?;
+                            scope.locals.insert(local.clone(), value);
+                        }
+                        _ => return runtime("Malformed let binding"),
+                    }
+                }
+                self.eval_body(&args[1..], &scope)
+            }
+            "if" => {
+                if self.eval(arg(args, 0)?, env)?.expect_bool()? {
+                    self.eval(arg(args, 1)?, env)
+                } else {
+                    match args.get(2) {
+                        Some(otherwise) => self.eval(otherwise, env),
+                        None => Ok(ClarityValue::none()),
+                    }
+                }
+            }
+            "asserts!" => {
+                if self.eval(arg(args, 0)?, env)?.expect_bool()? {
+                    Ok(ClarityValue::Bool(true))
+                } else {
+                    Err(Flow::Return(self.eval(arg(args, 1)?, env)?))
+                }
+            }
+            "unwrap!" | "unwrap-panic" => match self.eval(arg(args, 0)?, env)? {
+                ClarityValue::Optional(Some(value)) | ClarityValue::Response(Ok(value)) => Ok(*value),
+                _ if name == "unwrap-panic" => runtime("unwrap-panic failed"),
+                _ => Err(Flow::Return(self.eval(arg(args, 1)?, env)?)),
+            },
+            "unwrap-err!" => match self.eval(arg(args, 0)?, env)? {
+                ClarityValue::Response(Err(value)) => Ok(*value),
+                _ => Err(Flow::Return(self.eval(arg(args, 1)?, env)?)),
+            },
+            "try!" => match self.eval(arg(args, 0)?, env)? {
+                ClarityValue::Optional(Some(value)) | ClarityValue::Response(Ok(value)) => Ok(*value),
+                ClarityValue::Optional(None) => Err(Flow::Return(ClarityValue::none())),
+                ClarityValue::Response(Err(value)) => Err(Flow::Return(ClarityValue::Response(Err(value)))),
+                other => runtime(format!("try! expects an optional or response, found {}", other)),
+            },
+            "match" => self.eval_match(args, env),
+            "ok" => Ok(ClarityValue::ok(self.eval(arg(args, 0)?, env)?)),
+            "err" => Ok(ClarityValue::err(self.eval(arg(args, 0)?, env)?)),
+            "some" => Ok(ClarityValue::some(self.eval(arg(args, 0)?, env)?)),
+            "default-to" | "get-or-default" => {
+                // `get-or-default` takes its arguments in the opposite order
+                let (default, optional) = if name == "default-to" { (0, 1) } else { (1, 0) };
+                match self.eval(arg(args, optional)?, env)? {
+                    ClarityValue::Optional(Some(value)) => Ok(*value),
+                    ClarityValue::Optional(None) => self.eval(arg(args, default)?, env),
+                    other => runtime(format!("{} expects an optional, found {}", name, other)),
+                }
+            }
+            "is-some" | "is-none" | "is-ok" | "is-err" => {
+                let value = self.eval(arg(args, 0)?, env)?;
+                let result = match (name, &value) {
+                    ("is-some", ClarityValue::Optional(inner)) => inner.is_some(),
+                    ("is-none", ClarityValue::Optional(inner)) => inner.is_none(),
+                    ("is-ok", ClarityValue::Response(inner)) => inner.is_ok(),
+                    ("is-err", ClarityValue::Response(inner)) => inner.is_err(),
+                    _ => return runtime(format!("{} cannot inspect {}", name, value)),
+                };
+                Ok(ClarityValue::Bool(result))
+            }
+            "and" => {
+                for expr in args {
+                    if !self.eval(expr, env)?.expect_bool()? {
+                        return Ok(ClarityValue::Bool(false));
+                    }
+                }
+                Ok(ClarityValue::Bool(true))
+            }
+            "or" => {
+                for expr in args {
+                    if self.eval(expr, env)?.expect_bool()? {
+                        return Ok(ClarityValue::Bool(true));
+                    }
+                }
+                Ok(ClarityValue::Bool(false))
+            }
+            "not" => Ok(ClarityValue::Bool(!self.eval(arg(args, 0)?, env)?.expect_bool()?)),
+            "+" | "-" | "*" | "/" | "mod" | "min" | "max" => {
+                let values = self.eval_all(args, env)?;
+                arithmetic(name, values)
+            }
+            "<" | ">" | "<=" | ">=" => {
+                let left = self.eval(arg(args, 0)?, env)?;
+                let right = self.eval(arg(args, 1)?, env)?;
+                compare(name, &left, &right)
+            }
+            "is-eq" => {
+                let values = self.eval_all(args, env)?;
+                Ok(ClarityValue::Bool(values.windows(2).all(|pair| pair[0] == pair[1])))
+            }
+            "to-uint" => match self.eval(arg(args, 0)?, env)? {
+                ClarityValue::Int(value) => u128::try_from(value).map(ClarityValue::UInt).or_else(|_| runtime("to-uint of a negative value")),
+                other => runtime(format!("to-uint expects an int, found {}", other)),
+            },
+            "to-int" => match self.eval(arg(args, 0)?, env)? {
+                ClarityValue::UInt(value) => i128::try_from(value).map(ClarityValue::Int).or_else(|_| runtime("to-int overflow")),
+                other => runtime(format!("to-int expects a uint, found {}", other)),
+            },
+            "var-get" => {
+                let var = atom_arg(args, 0)?;
+                self.state.vars.get(var).cloned().map_or_else(|| runtime(format!("Unknown data-var '{}'", var)), Ok)
+            }
+            "var-set" => {
+                let var = atom_arg(args, 0)?;
+                let value = self.eval(arg(args, 1)?, env)?;
+                match self.state.vars.get_mut(var) {
+                    Some(slot) => *slot = value,
+                    None => return runtime(format!("Unknown data-var '{}'", var)),
+                }
+                Ok(ClarityValue::Bool(true))
+            }
+            "map-get?" => {
+                let key = self.eval(arg(args, 1)?, env)?.to_hex();
+                let map = self.map(atom_arg(args, 0)?)?;
+                Ok(ClarityValue::Optional(map.get(&key).cloned().map(Box::new)))
+            }
+            "map-set" | "map-insert" => {
+                let key = self.eval(arg(args, 1)?, env)?.to_hex();
+                let value = self.eval(arg(args, 2)?, env)?;
+                let map = self.map(atom_arg(args, 0)?)?;
+                if name == "map-insert" && map.contains_key(&key) {
+                    return Ok(ClarityValue::Bool(false));
+                }
+                map.insert(key, value);
+                Ok(ClarityValue::Bool(true))
+            }
+            "map-delete" => {
+                let key = self.eval(arg(args, 1)?, env)?.to_hex();
+                let map = self.map(atom_arg(args, 0)?)?;
+                Ok(ClarityValue::Bool(map.remove(&key).is_some()))
+            }
+            "get" => {
+                let field = atom_arg(args, 0)?;
+                match self.eval(arg(args, 1)?, env)? {
+                    ClarityValue::Optional(Some(tuple)) => Ok(ClarityValue::some(tuple.get(field)?.clone())),
+                    ClarityValue::Optional(None) => Ok(ClarityValue::none()),
+                    tuple => Ok(tuple.get(field)?.clone()),
+                }
+            }
+            "merge" => match (self.eval(arg(args, 0)?, env)?, self.eval(arg(args, 1)?, env)?) {
+                (ClarityValue::Tuple(mut base), ClarityValue::Tuple(update)) => {
+                    base.extend(update);
+                    Ok(ClarityValue::Tuple(base))
+                }
+                _ => runtime("merge expects two tuples"),
+            },
+            "tuple" => {
+                let mut tuple = BTreeMap::new();
+                for field in args {
+                    match field.as_list() {
+                        Some([SExpr::Atom(key), value]) => {
+                            let value = self.eval(value, env)?;
+                            tuple.insert(key.clone(), value);
+                        }
+                        _ => return runtime("Malformed tuple field"),
+                    }
+                }
+                Ok(ClarityValue::Tuple(tuple))
+            }
+            "list" => Ok(ClarityValue::List(self.eval_all(args, env)?)),
+            "append" => {
+                let mut list = self.eval(arg(args, 0)?, env)?.expect_list()?.to_vec();
+                match arg(args, 1)? {
+                    // `(append items [item])` appends the literal's elements
+                    SExpr::Sequence(items) => list.extend(self.eval_all(items, env)?),
+                    item => list.push(self.eval(item, env)?),
+                }
+                Ok(ClarityValue::List(list))
+            }
+            "concat" => match (self.eval(arg(args, 0)?, env)?, self.eval(arg(args, 1)?, env)?) {
+                (ClarityValue::List(mut a), ClarityValue::List(b)) => {
+                    a.extend(b);
+                    Ok(ClarityValue::List(a))
+                }
+                (ClarityValue::Buffer(mut a), ClarityValue::Buffer(b)) => {
+                    a.extend(b);
+                    Ok(ClarityValue::Buffer(a))
+                }
+                (ClarityValue::StringAscii(a), ClarityValue::StringAscii(b)) => Ok(ClarityValue::StringAscii(a + &b)),
+                _ => runtime("concat expects two sequences of the same type"),
+            },
+            "len" => {
+                let length = match self.eval(arg(args, 0)?, env)? {
+                    ClarityValue::List(items) => items.len(),
+                    ClarityValue::Buffer(bytes) => bytes.len(),
+                    ClarityValue::StringAscii(text) | ClarityValue::StringUtf8(text) => text.chars().count(),
+                    other => return runtime(format!("len expects a sequence, found {}", other)),
+                };
+                Ok(ClarityValue::UInt(length as u128))
+            }
+            "filter" | "map" | "any?" => {
+                let items = self.eval(arg(args, 1)?, env)?.expect_list()?.to_vec();
+                let mut results = Vec::new();
+                for item in items {
+                    let result = self.call_callable(arg(args, 0)?, vec![item.clone()], env)?;
+                    match name {
+                        "map" => results.push(result),
+                        "filter" if result.expect_bool()? => results.push(item),
+                        "any?" if result.expect_bool()? => return Ok(ClarityValue::Bool(true)),
+                        _ => {}
+                    }
+                }
+                if name == "any?" {
+                    return Ok(ClarityValue::Bool(false));
+                }
+                Ok(ClarityValue::List(results))
+            }
+            "fold" => {
+                let items = self.eval(arg(args, 1)?, env)?.expect_list()?.to_vec();
+                let mut accumulator = self.eval(arg(args, 2)?, env)?;
+                for item in items {
+                    accumulator = self.call_callable(arg(args, 0)?, vec![item, accumulator], env)?;
+                }
+                Ok(accumulator)
+            }
+            "print" => {
+                let value = self.eval(arg(args, 0)?, env)?;
+                self.events.push(ContractEvent::Print {
+                    contract_identifier: self.principal.to_string(),
+                    value: value.clone(),
+                });
+                Ok(value)
+            }
+            "as-contract" => {
+                let scope = Env {
+                    sender: self.principal.clone(),
+                    caller: self.principal.clone(),
+                    locals: env.locals.clone(),
+                };
+                self.eval(arg(args, 0)?, &scope)
+            }
+            "ft-mint?" | "ft-burn?" | "ft-transfer?" | "ft-get-balance" | "ft-get-supply" => self.apply_token(name, args, env),
+            // `(tx-sender)` is accepted as a call of the keyword
+            "tx-sender" | "contract-caller" | "block-height" | "stacks-block-height" | "burn-block-height" if args.is_empty() => {
+                self.eval_atom(name, env)
+            }
+            _ => {
+                let values = self.eval_all(args, env)?;
+                self.call_function(name, values, env)
+            }
+        }
+    }
+
+    fn eval_match(&mut self, args: &[SExpr], env: &Env) -> EvalResult {
+        let target = self.eval(arg(args, 0)?, env)?;
+        let branches = &args[1..];
+
+        // Labelled form: `(match opt none <expr> some name <expr>)`
+        if matches!(branches.first().and_then(SExpr::as_atom), Some("none" | "some" | "ok" | "err")) {
+            let mut index = 0;
+            while index < branches.len() {
+                let label = branches[index].as_atom().unwrap_or_default();
+                let (binding, branch, next) = match label {
+                    "none" => (None, arg(branches, index + 1)?, index + 2),
+                    "some" | "ok" | "err" => (Some(atom_arg(branches, index + 1)?), arg(branches, index + 2)?, index + 3),
+                    _ => return runtime("Malformed match branch"),
+                };
+                let inner = match (label, &target) {
+                    ("none", ClarityValue::Optional(None)) => Some(None),
+                    ("some", ClarityValue::Optional(Some(value))) | ("ok", ClarityValue::Response(Ok(value))) | ("err", ClarityValue::Response(Err(value))) => {
+                        Some(Some(value.as_ref().clone()))
+                    }
+                    _ => None,
+                };
+                if let Some(inner) = inner {
+                    let mut scope = env.clone();
+                    if let (Some(binding), Some(value)) = (binding, inner) {
+                        scope.locals.insert(binding.to_string(), value);
+                    }
+                    return self.eval(branch, &scope);
+                }
+                index = next;
+            }
+            return runtime("No match branch applies");
+        }
+
+        // Case form: `(match value "literal" <expr> ... <default>)`
+        if matches!(branches.first(), Some(SExpr::Str(_))) {
+            for pair in branches.chunks(2) {
+                match pair {
+                    [pattern, branch] if self.eval(pattern, env)? == target => return self.eval(branch, env),
+                    [default] => return self.eval(default, env),
+                    _ => {}
+                }
+            }
+            return runtime("No match branch applies");
+        }
+
+        match (&target, branches) {
+            (ClarityValue::Optional(value), [SExpr::Atom(binding), some_branch, none_branch]) => match value {
+                Some(value) => {
+                    let mut scope = env.clone();
+                    scope.locals.insert(binding.clone(), value.as_ref().clone());
+                    self.eval(some_branch, &scope)
+                }
+                None => self.eval(none_branch, env),
+            },
+            (ClarityValue::Response(result), [SExpr::Atom(ok_binding), ok_branch, SExpr::Atom(err_binding), err_branch]) => {
+                let mut scope = env.clone();
+                match result {
+                    Ok(value) => {
+                        scope.locals.insert(ok_binding.clone(), value.as_ref().clone());
+                        self.eval(ok_branch, &scope)
+                    }
+                    Err(value) => {
+                        scope.locals.insert(err_binding.clone(), value.as_ref().clone());
+                        self.eval(err_branch, &scope)
+                    }
+                }
+            }
+            _ => runtime("Malformed match expression"),
+        }
+    }
+
+    fn apply_token(&mut self, name: &str, args: &[SExpr], env: &Env) -> EvalResult {
+        let token_name = atom_arg(args, 0)?;
+        let asset_identifier = format!("{}::{}", self.principal, token_name);
+        let values = self.eval_all(&args[1..], env)?;
+        let token = self
+            .state
+            .tokens
+            .get_mut(token_name)
+            .ok_or_else(|| Flow::Runtime(format!("Unknown fungible token '{}'", token_name)))?;
+        let principal = |index: usize| -> Result<String, Flow> {
+            Ok(values.get(index).ok_or_else(|| Flow::Runtime("Missing argument".into()))?.expect_principal()?.to_string())
+        };
+        let error = |code: u128| Ok(ClarityValue::err(ClarityValue::UInt(code)));
+
+        match name {
+            "ft-get-balance" => Ok(ClarityValue::UInt(token.balances.get(&principal(0)?).copied().unwrap_or_default())),
+            "ft-get-supply" => Ok(ClarityValue::UInt(token.supply)),
+            "ft-mint?" => {
+                let amount = values[0].expect_u128()?;
+                let recipient = principal(1)?;
+                if amount == 0 {
+                    return error(1);
+                }
+                let supply = token.supply.checked_add(amount).ok_or_else(|| Flow::Runtime("Token supply overflow".into()))?;
+                if token.max_supply.is_some_and(|max| supply > max) {
+                    return runtime(format!("Minting exceeds the {} supply cap", token_name));
+                }
+                token.supply = supply;
+                *token.balances.entry(recipient.clone()).or_default() += amount;
+                self.events.push(ContractEvent::FtMint { asset_identifier, recipient, amount });
+                Ok(ClarityValue::ok(ClarityValue::Bool(true)))
+            }
+            "ft-burn?" => {
+                let amount = values[0].expect_u128()?;
+                let sender = principal(1)?;
+                if amount == 0 {
+                    return error(3);
+                }
+                let balance = token.balances.entry(sender.clone()).or_default();
+                if *balance < amount {
+                    return error(1);
+                }
+                *balance -= amount;
+                token.supply -= amount;
+                self.events.push(ContractEvent::FtBurn { asset_identifier, sender, amount });
+                Ok(ClarityValue::ok(ClarityValue::Bool(true)))
+            }
+            _ => {
+                let amount = values[0].expect_u128()?;
+                let sender = principal(1)?;
+                let recipient = principal(2)?;
+                if amount == 0 {
+                    return error(3);
+                }
+                if sender == recipient {
+                    return error(2);
+                }
+                let balance = token.balances.entry(sender.clone()).or_default();
+                if *balance < amount {
+                    return error(1);
+                }
+                *balance -= amount;
+                *token.balances.entry(recipient.clone()).or_default() += amount;
+                self.events.push(ContractEvent::FtTransfer { asset_identifier, sender, recipient, amount });
+                Ok(ClarityValue::ok(ClarityValue::Bool(true)))
+            }
+        }
+    }
+
+    fn map(&mut self, name: &str) -> Result<&mut HashMap<String, ClarityValue>, Flow> {
+        self.state
+            .maps
+            .get_mut(name)
+            .ok_or_else(|| Flow::Runtime(format!("Unknown map '{}'", name)))
+    }
+
+    /// Calls a named function or an inline `(fn (args) body)` lambda
+    fn call_callable(&mut self, callable: &SExpr, values: Vec<ClarityValue>, env: &Env) -> EvalResult {
+        match callable {
+            SExpr::Atom(name) => self.call_function(name, values, env),
+            SExpr::List(items) if matches!(items.first().and_then(SExpr::as_atom), Some("fn" | "lambda")) => {
+                let params = arg(items, 1)?.as_list().ok_or_else(|| Flow::Runtime("Malformed lambda".into()))?;
+                let mut scope = env.clone();
+                for (param, value) in params.iter().zip(values) {
+                    let param = param.as_atom().ok_or_else(|| Flow::Runtime("Malformed lambda parameter".into()))?;
+                    scope.locals.insert(param.to_string(), value);
+                }
+                self.eval_body(&items[2..], &scope)
+            }
+            _ => runtime("Expected a function"),
+        }
+    }
+
+    fn call_function(&mut self, name: &str, values: Vec<ClarityValue>, env: &Env) -> EvalResult {
+        let function = match self.functions.get(name) {
+            Some(function) => function.clone(),
+            None => return runtime(format!("Unknown function '{}'", name)),
+        };
+        if function.params.len() != values.len() {
+            return runtime(format!("{} expects {} arguments, got {}", name, function.params.len(), values.len()));
+        }
+        if self.depth >= MAX_CALL_DEPTH {
+            return runtime("Maximum call depth exceeded");
+        }
+
+        let scope = Env {
+            sender: env.sender.clone(),
+            caller: env.caller.clone(),
+            locals: function.params.iter().cloned().zip(values).collect(),
+        };
+        self.depth += 1;
+        let result = self.eval_body(&function.body, &scope);
+        self.depth -= 1;
+        match result {
+            Err(Flow::Return(value)) => Ok(value), // Early exits end the enclosing function
+            other => other,
+        }
+    }
+}
+
+fn arg(args: &[SExpr], index: usize) -> Result<&SExpr, Flow> {
+    args.get(index).ok_or_else(|| Flow::Runtime("Missing argument".into()))
+}
+
+fn atom_arg(args: &[SExpr], index: usize) -> Result<&str, Flow> {
+    arg(args, index)?.as_atom().ok_or_else(|| Flow::Runtime("Expected a name".into()))
+}
+
+fn arithmetic(op: &str, values: Vec<ClarityValue>) -> EvalResult {
+    let mut values = values.into_iter();
+    let first = values.next().ok_or_else(|| Flow::Runtime(format!("{} expects arguments", op)))?;
+    values.try_fold(first, |acc, value| {
+        let overflow = || Flow::Runtime(format!("Arithmetic error in ({} ...)", op));
+        match (acc, value) {
+            (ClarityValue::UInt(a), ClarityValue::UInt(b)) => Ok(ClarityValue::UInt(
+                match op {
+                    "+" => a.checked_add(b),
+                    "-" => a.checked_sub(b),
+                    "*" => a.checked_mul(b),
+                    "/" => a.checked_div(b),
+                    "mod" => a.checked_rem(b),
+                    "min" => Some(a.min(b)),
+                    _ => Some(a.max(b)),
+                }
+                .ok_or_else(overflow)?,
+            )),
+            (ClarityValue::Int(a), ClarityValue::Int(b)) => Ok(ClarityValue::Int(
+                match op {
+                    "+" => a.checked_add(b),
+                    "-" => a.checked_sub(b),
+                    "*" => a.checked_mul(b),
+                    "/" => a.checked_div(b),
+                    "mod" => a.checked_rem(b),
+                    "min" => Some(a.min(b)),
+                    _ => Some(a.max(b)),
+                }
+                .ok_or_else(overflow)?,
+            )),
+            (a, b) => runtime(format!("{} expects matching int or uint arguments, found {} and {}", op, a, b)),
+        }
+    })
+}
+
+fn compare(op: &str, left: &ClarityValue, right: &ClarityValue) -> EvalResult {
+    let ordering = match (left, right) {
+        (ClarityValue::UInt(a), ClarityValue::UInt(b)) => a.cmp(b),
+        (ClarityValue::Int(a), ClarityValue::Int(b)) => a.cmp(b),
+        _ => return runtime(format!("{} expects matching int or uint arguments, found {} and {}", op, left, right)),
+    };
+    Ok(ClarityValue::Bool(match op {
+        "<" => ordering.is_lt(),
+        ">" => ordering.is_gt(),
+        "<=" => ordering.is_le(),
+        _ => ordering.is_ge(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::bindings::{proposal_voting, staking_rewards, transaction_bridge, validator_rewards, zbtcz};
+    use crate::clarity::post_condition::PostConditionPrincipal;
+    use crate::clarity::receipt::ContractError;
+
+    const DEPLOYER: &str = "SP000000000000000000002Q6VF78";
+    const USER: &str = "ST000000000000000000002AMW42H";
+
+    fn deployed(name: &str, source: &str) -> (ClarityVm, String) {
+        let contract_id = format!("{}.{}", DEPLOYER, name);
+        let mut vm = ClarityVm::new();
+        vm.deploy_file(&contract_id, source).unwrap();
+        (vm, contract_id)
+    }
+
+    #[test]
+    fn test_zbtcz_mint_requires_finalized_root() {
+        let (mut vm, zbtcz_id) = deployed("zbtcz", zbtcz::SOURCE);
+        let root = vec![7u8; 32];
+
+        let rejected = vm.call_public(USER, &zbtcz_id, &zbtcz::mint_zbtcz(500, 10, root.clone())).unwrap();
+        assert_eq!(rejected.outcome().unwrap_err(), ContractError::UnfinalizedMerkleRoot);
+        assert!(rejected.events.is_empty());
+        assert_eq!(vm.data_var(&zbtcz_id, "locked-btcz"), Some(&ClarityValue::UInt(0)));
+
+        vm.call_public(DEPLOYER, &zbtcz_id, &zbtcz::finalize_state(10, root.clone())).unwrap();
+        let minted = vm.call_public(USER, &zbtcz_id, &zbtcz::mint_zbtcz(500, 10, root)).unwrap();
+        assert!(minted.is_confirmed());
+        assert_eq!(
+            minted.events,
+            vec![ContractEvent::FtMint {
+                asset_identifier: format!("{}::zbtcz-token", zbtcz_id),
+                recipient: USER.to_string(),
+                amount: 500,
+            }]
+        );
+        assert_eq!(vm.ft_balance(&zbtcz_id, "zbtcz-token", USER), 500);
+        assert_eq!(vm.data_var(&zbtcz_id, "locked-btcz"), Some(&ClarityValue::UInt(500)));
+        assert_eq!(vm.receipt(&minted.txid), Some(&minted));
+    }
+
+    #[test]
+    fn test_zbtcz_burn_checks_balance_and_rolls_back() {
+        let (mut vm, zbtcz_id) = deployed("zbtcz", zbtcz::SOURCE);
+        let root = vec![1u8; 32];
+        vm.call_public(DEPLOYER, &zbtcz_id, &zbtcz::finalize_state(1, root.clone())).unwrap();
+        vm.call_public(USER, &zbtcz_id, &zbtcz::mint_zbtcz(100, 1, root)).unwrap();
+
+        let rejected = vm.call_public(USER, &zbtcz_id, &zbtcz::burn_zbtcz(101, 9)).unwrap();
+        assert_eq!(rejected.outcome().unwrap_err(), ContractError::InsufficientZbtczBalance);
+        assert_eq!(vm.data_var(&zbtcz_id, "burned-records"), Some(&ClarityValue::List(Vec::new())));
+
+        let burned = vm.call_public(USER, &zbtcz_id, &zbtcz::burn_zbtcz(40, 9)).unwrap();
+        assert!(burned.is_confirmed());
+        assert!(matches!(burned.events[0], ContractEvent::FtBurn { amount: 40, .. }));
+        assert_eq!(vm.ft_balance(&zbtcz_id, "zbtcz-token", USER), 60);
+        assert_eq!(vm.data_var(&zbtcz_id, "locked-btcz"), Some(&ClarityValue::UInt(60)));
+    }
+
+    #[test]
+    fn test_post_conditions_abort_and_roll_back() {
+        let (mut vm, zbtcz_id) = deployed("zbtcz", zbtcz::SOURCE);
+        let root = vec![2u8; 32];
+        vm.call_public(DEPLOYER, &zbtcz_id, &zbtcz::finalize_state(1, root.clone())).unwrap();
+        vm.call_public(USER, &zbtcz_id, &zbtcz::mint_zbtcz(100, 1, root)).unwrap();
+        let burn_exactly = |amount| {
+            vec![PostCondition::ft_sent_exactly(PostConditionPrincipal::Origin, &zbtcz_id, "zbtcz-token", amount).unwrap()]
+        };
+
+        let burn = zbtcz::burn_zbtcz(40, 9);
+        let aborted = vm
+            .call_public_with_post_conditions(USER, &zbtcz_id, &burn, PostConditionMode::Deny, &burn_exactly(30))
+            .unwrap();
+        assert_eq!(aborted.outcome().unwrap_err(), ContractError::PostConditionFailed);
+        assert!(aborted.events.is_empty());
+        assert_eq!(vm.ft_balance(&zbtcz_id, "zbtcz-token", USER), 100);
+
+        // Deny mode without a covering condition forbids the burn as well
+        let uncovered = vm.call_public_with_post_conditions(USER, &zbtcz_id, &burn, PostConditionMode::Deny, &[]).unwrap();
+        assert_eq!(uncovered.status, TxStatus::AbortByPostCondition);
+
+        let burned = vm
+            .call_public_with_post_conditions(USER, &zbtcz_id, &burn, PostConditionMode::Deny, &burn_exactly(40))
+            .unwrap();
+        assert!(burned.is_confirmed());
+        assert_eq!(vm.ft_balance(&zbtcz_id, "zbtcz-token", USER), 60);
+    }
+
+    #[test]
+    fn test_proposal_voting_lifecycle() {
+        let (mut vm, governance_id) = deployed("governance", proposal_voting::SOURCE);
+        let propose = proposal_voting::propose("minimum-stake".to_string(), 2_000);
+
+        let proposed = vm.call_public(USER, &governance_id, &propose).unwrap();
+        assert!(proposed.is_confirmed());
+        match &proposed.events[..] {
+            [ContractEvent::Print { value, .. }] => assert_eq!(value.get("proposer"), Ok(&ClarityValue::Principal(PrincipalData::parse(USER).unwrap()))),
+            other => panic!("unexpected events: {:?}", other),
+        }
+        let second = vm.call_public(USER, &governance_id, &propose).unwrap();
+        assert!(matches!(second.outcome(), Err(ContractError::Other { .. })));
+
+        vm.call_public(USER, &governance_id, &proposal_voting::vote(true)).unwrap();
+        let finalized = vm.call_public(USER, &governance_id, &proposal_voting::finalize_proposal()).unwrap();
+        assert_eq!(finalized.outcome().unwrap(), &ClarityValue::StringAscii("Proposal approved".to_string()));
+        assert_eq!(vm.data_var(&governance_id, "minimum-stake"), Some(&ClarityValue::UInt(2_000)));
+        match &finalized.events[..] {
+            [ContractEvent::Print { value, .. }] => assert_eq!(value.get("approved"), Ok(&ClarityValue::Bool(true))),
+            other => panic!("unexpected events: {:?}", other),
+        }
+        assert_eq!(vm.data_var(&governance_id, "current-proposal"), Some(&ClarityValue::none()));
+    }
+
+    #[test]
+    fn test_staking_and_bridge_error_codes() {
+        let (mut vm, staking_id) = deployed("gbtcz", staking_rewards::SOURCE);
+        let bridge_id = format!("{}.bridge", DEPLOYER);
+        vm.deploy_file(&bridge_id, transaction_bridge::SOURCE).unwrap();
+
+        let unknown_validator = staking_rewards::slash_validator(PrincipalData::parse(USER).unwrap());
+        let slashed = vm.call_public(USER, &staking_id, &unknown_validator).unwrap();
+        assert_eq!(slashed.result, Some(ClarityValue::err(ClarityValue::UInt(3001))));
+        let info = vm
+            .call_read_only(USER, &staking_id, &staking_rewards::get_validator_info(PrincipalData::parse(USER).unwrap()))
+            .unwrap();
+        assert_eq!(info, ClarityValue::ok(ClarityValue::none()));
+
+        let propose = staking_rewards::propose_slashing_penalty_change(10);
+        assert!(vm.call_public(USER, &staking_id, &propose).unwrap().is_confirmed());
+        assert!(vm.call_public(USER, &staking_id, &staking_rewards::execute_proposal(0)).unwrap().is_confirmed());
+        assert_eq!(vm.data_var(&staking_id, "slashing-penalty"), Some(&ClarityValue::UInt(10)));
+        let executed = vm.call_public(USER, &staking_id, &staking_rewards::execute_proposal(0)).unwrap();
+        assert_eq!(executed.result, Some(ClarityValue::err(ClarityValue::UInt(2012))));
+
+        assert!(vm.call_public(USER, &bridge_id, &transaction_bridge::lock_btcz(1, 50)).unwrap().is_confirmed());
+        let duplicate = vm.call_public(USER, &bridge_id, &transaction_bridge::lock_btcz(1, 50)).unwrap();
+        assert_eq!(duplicate.outcome().unwrap_err(), ContractError::DuplicateLock);
+        let unknown = vm.call_public(USER, &bridge_id, &transaction_bridge::unlock_btcz(2)).unwrap();
+        assert_eq!(unknown.outcome().unwrap_err(), ContractError::UnknownLock);
+        assert!(vm.call_public(USER, &bridge_id, &transaction_bridge::unlock_btcz(1)).unwrap().is_confirmed());
+    }
+
+    #[test]
+    fn test_validator_rewards_and_runtime_errors() {
+        let (mut vm, rewards_id) = deployed("validator-rewards", validator_rewards::SOURCE);
+        let validator = PrincipalData::parse(USER).unwrap();
+
+        vm.call_public(DEPLOYER, &rewards_id, &validator_rewards::record_performance(validator.clone(), 80))
+            .unwrap();
+        let total = vm.call_read_only(DEPLOYER, &rewards_id, &validator_rewards::get_total_rewards()).unwrap();
+        assert_eq!(total, ClarityValue::ok(ClarityValue::UInt(800)));
+
+        let distributed = vm.call_public(DEPLOYER, &rewards_id, &validator_rewards::distribute_rewards(validator)).unwrap();
+        assert_eq!(distributed.outcome().unwrap().get("distributed-reward"), Ok(&ClarityValue::UInt(800)));
+
+        // Read-only calls never commit, and unknown contracts or functions are rejected outright
+        assert!(vm.call_public(DEPLOYER, &rewards_id, &validator_rewards::get_total_rewards()).is_err());
+        assert!(vm.call_public(DEPLOYER, "SP000000000000000000002Q6VF78.missing", &zbtcz::burn_zbtcz(1, 1)).is_err());
+        assert!(vm.deploy(&format!("{}.broken", DEPLOYER), "broken.clar", "(define-data-var x uint (+ u1 1))").is_err());
+    }
+}
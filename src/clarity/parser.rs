@@ -0,0 +1,374 @@
+// File: src/clarity/parser.rs
+//
+// Also compiled into build.rs to generate the contract bindings, so this file
+// must only depend on std.
+
+use std::fmt;
+
+/// A parsed Clarity expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SExpr {
+    Atom(String),                 // Symbols and numeric, buffer and principal literals
+    Str(String),                  // "..." string literal
+    List(Vec<SExpr>),             // ( ... )
+    Tuple(Vec<(String, SExpr)>),  // { key: value, ... }
+    Sequence(Vec<SExpr>),         // [ ... ] list literal
+}
+
+impl SExpr {
+    pub fn as_atom(&self) -> Option<&str> {
+        match self {
+            SExpr::Atom(atom) => Some(atom),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[SExpr]> {
+        match self {
+            SExpr::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionKind {
+    Public,
+    ReadOnly,
+    Private,
+}
+
+/// Declared type of a Clarity function argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSignature {
+    Int,
+    UInt,
+    Bool,
+    Principal,
+    Buffer(u32),
+    StringAscii(u32),
+    StringUtf8(u32),
+    Optional(Box<TypeSignature>),
+    Response(Box<TypeSignature>, Box<TypeSignature>),
+    List(u32, Box<TypeSignature>),
+    Tuple(Vec<(String, TypeSignature)>),
+}
+
+impl TypeSignature {
+    pub fn parse(expr: &SExpr) -> Result<Self, String> {
+        match expr {
+            SExpr::Atom(atom) => match atom.as_str() {
+                "int" => Ok(TypeSignature::Int),
+                "uint" => Ok(TypeSignature::UInt),
+                "bool" => Ok(TypeSignature::Bool),
+                "principal" => Ok(TypeSignature::Principal),
+                other => Err(format!("Unknown type '{}'", other)),
+            },
+            SExpr::Tuple(fields) => Ok(TypeSignature::Tuple(
+                fields
+                    .iter()
+                    .map(|(name, ty)| Ok((name.clone(), TypeSignature::parse(ty)?)))
+                    .collect::<Result<_, String>>()?,
+            )),
+            SExpr::List(items) => {
+                let head = items.first().and_then(SExpr::as_atom).unwrap_or_default();
+                let length = |index: usize| -> Result<u32, String> {
+                    items
+                        .get(index)
+                        .and_then(SExpr::as_atom)
+                        .and_then(|n| n.parse().ok())
+                        .ok_or_else(|| format!("Missing length in ({} ...)", head))
+                };
+                match head {
+                    "buff" => Ok(TypeSignature::Buffer(length(1)?)),
+                    "string-ascii" => Ok(TypeSignature::StringAscii(length(1)?)),
+                    "string-utf8" => Ok(TypeSignature::StringUtf8(length(1)?)),
+                    "optional" => Ok(TypeSignature::Optional(Box::new(Self::parse_nth(items, 1)?))),
+                    "response" => Ok(TypeSignature::Response(
+                        Box::new(Self::parse_nth(items, 1)?),
+                        Box::new(Self::parse_nth(items, 2)?),
+                    )),
+                    "list" => Ok(TypeSignature::List(length(1)?, Box::new(Self::parse_nth(items, 2)?))),
+                    "tuple" => Ok(TypeSignature::Tuple(
+                        items[1..]
+                            .iter()
+                            .map(|field| match field.as_list() {
+                                Some([SExpr::Atom(name), ty]) => Ok((name.clone(), TypeSignature::parse(ty)?)),
+                                _ => Err("Malformed tuple type field".to_string()),
+                            })
+                            .collect::<Result<_, String>>()?,
+                    )),
+                    other => Err(format!("Unknown type '({} ...)'", other)),
+                }
+            }
+            _ => Err("Malformed type signature".to_string()),
+        }
+    }
+
+    fn parse_nth(items: &[SExpr], index: usize) -> Result<Self, String> {
+        items
+            .get(index)
+            .ok_or_else(|| "Incomplete type signature".to_string())
+            .and_then(TypeSignature::parse)
+    }
+}
+
+impl fmt::Display for TypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeSignature::Int => write!(f, "int"),
+            TypeSignature::UInt => write!(f, "uint"),
+            TypeSignature::Bool => write!(f, "bool"),
+            TypeSignature::Principal => write!(f, "principal"),
+            TypeSignature::Buffer(len) => write!(f, "(buff {})", len),
+            TypeSignature::StringAscii(len) => write!(f, "(string-ascii {})", len),
+            TypeSignature::StringUtf8(len) => write!(f, "(string-utf8 {})", len),
+            TypeSignature::Optional(inner) => write!(f, "(optional {})", inner),
+            TypeSignature::Response(ok, err) => write!(f, "(response {} {})", ok, err),
+            TypeSignature::List(len, inner) => write!(f, "(list {} {})", len, inner),
+            TypeSignature::Tuple(fields) => {
+                write!(f, "(tuple")?;
+                for (name, ty) in fields {
+                    write!(f, " ({} {})", name, ty)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Signature of a `define-public` / `define-read-only` / `define-private` function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSignature {
+    pub name: String,
+    pub kind: FunctionKind,
+    pub args: Vec<(String, TypeSignature)>,
+}
+
+/// Extracts the signatures of all functions defined in a contract source
+pub fn contract_signatures(source: &str) -> Result<Vec<FunctionSignature>, String> {
+    let mut signatures = Vec::new();
+
+    for expr in parse(source)? {
+        let items = match expr.as_list() {
+            Some(items) => items,
+            None => continue,
+        };
+        let kind = match items.first().and_then(SExpr::as_atom) {
+            Some("define-public") => FunctionKind::Public,
+            Some("define-read-only") => FunctionKind::ReadOnly,
+            Some("define-private") => FunctionKind::Private,
+            _ => continue,
+        };
+
+        let header = items
+            .get(1)
+            .and_then(SExpr::as_list)
+            .ok_or("Function definition is missing its signature")?;
+        let name = header
+            .first()
+            .and_then(SExpr::as_atom)
+            .ok_or("Function definition is missing its name")?
+            .to_string();
+
+        let args = header[1..]
+            .iter()
+            .map(|arg| match arg.as_list() {
+                Some([SExpr::Atom(arg_name), ty]) => TypeSignature::parse(ty)
+                    .map(|ty| (arg_name.clone(), ty))
+                    .map_err(|err| format!("{}: argument '{}': {}", name, arg_name, err)),
+                _ => Err(format!("{}: malformed argument", name)),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        signatures.push(FunctionSignature { name, kind, args });
+    }
+
+    Ok(signatures)
+}
+
+/// Parses a contract source into its top-level expressions
+pub fn parse(source: &str) -> Result<Vec<SExpr>, String> {
+    let tokens = tokenize(source)?;
+    let mut pos = 0;
+    let mut exprs = Vec::new();
+    while pos < tokens.len() {
+        exprs.push(parse_expr(&tokens, &mut pos)?);
+    }
+    Ok(exprs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open(char),
+    Close(char),
+    Atom(String),
+    Str(String),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // `;;` is the Clarity comment; some contract headers use `//`
+            ';' | '/' if c == ';' || chars.peek() == Some(&'/') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' | '{' | '[' => tokens.push(Token::Open(c)),
+            ')' | '}' | ']' => tokens.push(Token::Close(c)),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(other) => text.push(other),
+                            None => return Err("Unterminated string literal".to_string()),
+                        },
+                        Some(other) => text.push(other),
+                        None => return Err("Unterminated string literal".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(text));
+            }
+            c if c.is_whitespace() || c == ',' => {}
+            _ => {
+                let mut atom = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "(){}[],\"".contains(next) {
+                        break;
+                    }
+                    atom.push(next);
+                    chars.next();
+                }
+                // `u"..."` is a string-utf8 literal
+                if atom == "u" && chars.peek() == Some(&'"') {
+                    continue;
+                }
+                tokens.push(Token::Atom(atom));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_expr(tokens: &[Token], pos: &mut usize) -> Result<SExpr, String> {
+    let token = tokens.get(*pos).ok_or("Unexpected end of input")?.clone();
+    *pos += 1;
+
+    match token {
+        Token::Atom(atom) => Ok(SExpr::Atom(atom)),
+        Token::Str(text) => Ok(SExpr::Str(text)),
+        Token::Close(c) => Err(format!("Unexpected '{}'", c)),
+        Token::Open('{') => {
+            let mut fields = Vec::new();
+            loop {
+                match tokens.get(*pos) {
+                    Some(Token::Close('}')) => {
+                        *pos += 1;
+                        return Ok(SExpr::Tuple(fields));
+                    }
+                    Some(Token::Atom(key)) => {
+                        let key = key.trim_end_matches(':').to_string();
+                        *pos += 1;
+                        if let Some(Token::Atom(colon)) = tokens.get(*pos) {
+                            if colon == ":" {
+                                *pos += 1;
+                            }
+                        }
+                        fields.push((key, parse_expr(tokens, pos)?));
+                    }
+                    Some(other) => return Err(format!("Unexpected {:?} in tuple literal", other)),
+                    None => return Err("Unterminated tuple literal".to_string()),
+                }
+            }
+        }
+        Token::Open(open) => {
+            let close = if open == '(' { ')' } else { ']' };
+            let mut items = Vec::new();
+            loop {
+                match tokens.get(*pos) {
+                    Some(Token::Close(c)) if *c == close => {
+                        *pos += 1;
+                        return Ok(if open == '(' {
+                            SExpr::List(items)
+                        } else {
+                            SExpr::Sequence(items)
+                        });
+                    }
+                    Some(Token::Close(c)) => return Err(format!("Mismatched '{}'", c)),
+                    Some(_) => items.push(parse_expr(tokens, pos)?),
+                    None => return Err(format!("Unterminated '{}'", open)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tuples_sequences_and_comments() {
+        let source = r#"
+            // File: header
+            ;; comment
+            (var-set records (append (var-get records) [{tx-id: tx-id, amount: amount}]))
+            (print u"hi")
+        "#;
+        let exprs = parse(source).unwrap();
+        assert_eq!(exprs.len(), 2);
+
+        let append = &exprs[0].as_list().unwrap()[2].as_list().unwrap()[2];
+        match append {
+            SExpr::Sequence(items) => match &items[0] {
+                SExpr::Tuple(fields) => {
+                    assert_eq!(fields[0].0, "tx-id");
+                    assert_eq!(fields[1], ("amount".to_string(), SExpr::Atom("amount".to_string())));
+                }
+                other => panic!("Expected tuple, found {:?}", other),
+            },
+            other => panic!("Expected sequence, found {:?}", other),
+        }
+        assert_eq!(exprs[1].as_list().unwrap()[1], SExpr::Str("hi".to_string()));
+    }
+
+    #[test]
+    fn test_contract_signatures() {
+        let source = r#"
+            (define-public (mint-zbtcz (amount uint) (block-height uint) (merkle-root (buff 32)))
+              (ok true))
+            (define-read-only (get-info (who principal) (memo (optional (string-ascii 34))))
+              (ok none))
+            (define-private (helper) true)
+        "#;
+        let signatures = contract_signatures(source).unwrap();
+        assert_eq!(signatures.len(), 3);
+
+        assert_eq!(signatures[0].name, "mint-zbtcz");
+        assert_eq!(signatures[0].kind, FunctionKind::Public);
+        assert_eq!(signatures[0].args[2].1, TypeSignature::Buffer(32));
+
+        assert_eq!(signatures[1].kind, FunctionKind::ReadOnly);
+        assert_eq!(signatures[1].args[1].1.to_string(), "(optional (string-ascii 34))");
+        assert!(signatures[2].args.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("(define-public (f)").is_err());
+        assert!(parse("(a ]").is_err());
+        assert!(parse("\"open").is_err());
+        assert!(contract_signatures("(define-public (f (x blob)) (ok x))").is_err());
+    }
+}
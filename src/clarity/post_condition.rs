@@ -0,0 +1,297 @@
+// File: src/clarity/post_condition.rs
+
+use std::collections::HashMap;
+use crate::clarity::receipt::ContractEvent;
+use crate::clarity::value::{PrincipalData, Reader};
+
+const POST_CONDITION_STX: u8 = 0x00;
+const POST_CONDITION_FUNGIBLE: u8 = 0x01;
+const PRINCIPAL_ORIGIN: u8 = 0x01;
+const PRINCIPAL_STANDARD: u8 = 0x02;
+const PRINCIPAL_CONTRACT: u8 = 0x03;
+
+/// In `Deny` mode any asset movement not covered by a post-condition aborts the transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostConditionMode {
+    Allow = 0x01,
+    Deny = 0x02,
+}
+
+/// Comparison between the amount actually sent and the post-condition amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionCode {
+    Eq = 0x01,
+    Gt = 0x02,
+    Ge = 0x03,
+    Lt = 0x04,
+    Le = 0x05,
+}
+
+impl ConditionCode {
+    fn from_u8(code: u8) -> Result<Self, String> {
+        match code {
+            0x01 => Ok(ConditionCode::Eq),
+            0x02 => Ok(ConditionCode::Gt),
+            0x03 => Ok(ConditionCode::Ge),
+            0x04 => Ok(ConditionCode::Lt),
+            0x05 => Ok(ConditionCode::Le),
+            other => Err(format!("Unknown post-condition code {:#04x}", other)),
+        }
+    }
+
+    pub fn holds(self, sent: u128, amount: u64) -> bool {
+        let amount = amount as u128;
+        match self {
+            ConditionCode::Eq => sent == amount,
+            ConditionCode::Gt => sent > amount,
+            ConditionCode::Ge => sent >= amount,
+            ConditionCode::Lt => sent < amount,
+            ConditionCode::Le => sent <= amount,
+        }
+    }
+}
+
+/// Account whose outflow a post-condition constrains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostConditionPrincipal {
+    Origin, // The transaction signer
+    Principal(PrincipalData),
+}
+
+impl PostConditionPrincipal {
+    fn resolve(&self, origin: &str) -> String {
+        match self {
+            PostConditionPrincipal::Origin => origin.to_string(),
+            PostConditionPrincipal::Principal(principal) => principal.to_string(),
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        match reader.read_u8()? {
+            PRINCIPAL_ORIGIN => Ok(PostConditionPrincipal::Origin),
+            PRINCIPAL_STANDARD => Ok(PostConditionPrincipal::Principal(PrincipalData::Standard {
+                version: reader.read_u8()?,
+                hash160: reader.read_hash160()?,
+            })),
+            PRINCIPAL_CONTRACT => Ok(PostConditionPrincipal::Principal(PrincipalData::Contract {
+                version: reader.read_u8()?,
+                hash160: reader.read_hash160()?,
+                name: reader.read_name()?,
+            })),
+            other => Err(format!("Unknown post-condition principal {:#04x}", other)),
+        }
+    }
+
+    fn serialize_into(&self, out: &mut Vec<u8>) {
+        match self {
+            PostConditionPrincipal::Origin => out.push(PRINCIPAL_ORIGIN),
+            PostConditionPrincipal::Principal(PrincipalData::Standard { version, hash160 }) => {
+                out.push(PRINCIPAL_STANDARD);
+                out.push(*version);
+                out.extend_from_slice(hash160);
+            }
+            PostConditionPrincipal::Principal(PrincipalData::Contract { version, hash160, name }) => {
+                out.push(PRINCIPAL_CONTRACT);
+                out.push(*version);
+                out.extend_from_slice(hash160);
+                out.push(name.len() as u8);
+                out.extend_from_slice(name.as_bytes());
+            }
+        }
+    }
+}
+
+/// Limit on the STX or fungible tokens a principal may send in a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostCondition {
+    Stx {
+        principal: PostConditionPrincipal,
+        code: ConditionCode,
+        amount: u64,
+    },
+    Fungible {
+        principal: PostConditionPrincipal,
+        contract: PrincipalData,
+        asset_name: String,
+        code: ConditionCode,
+        amount: u64,
+    },
+}
+
+impl PostCondition {
+    /// The signer sends no STX
+    pub fn no_stx_sent() -> Self {
+        PostCondition::Stx {
+            principal: PostConditionPrincipal::Origin,
+            code: ConditionCode::Eq,
+            amount: 0,
+        }
+    }
+
+    /// `principal` sends (transfers or burns) exactly `amount` of `contract_id::asset_name`
+    pub fn ft_sent_exactly(
+        principal: PostConditionPrincipal,
+        contract_id: &str,
+        asset_name: &str,
+        amount: u128,
+    ) -> Result<Self, String> {
+        let contract = PrincipalData::parse(contract_id)?;
+        if !matches!(contract, PrincipalData::Contract { .. }) {
+            return Err(format!("Not a contract identifier: {}", contract_id));
+        }
+        Ok(PostCondition::Fungible {
+            principal,
+            contract,
+            asset_name: asset_name.to_string(),
+            code: ConditionCode::Eq,
+            amount: u64::try_from(amount).map_err(|_| format!("Amount {} exceeds post-condition range", amount))?,
+        })
+    }
+
+    /// Decodes one post-condition; non-fungible conditions are not supported
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, String> {
+        match reader.read_u8()? {
+            POST_CONDITION_STX => Ok(PostCondition::Stx {
+                principal: PostConditionPrincipal::read(reader)?,
+                code: ConditionCode::from_u8(reader.read_u8()?)?,
+                amount: reader.read_u64()?,
+            }),
+            POST_CONDITION_FUNGIBLE => Ok(PostCondition::Fungible {
+                principal: PostConditionPrincipal::read(reader)?,
+                contract: PrincipalData::Contract {
+                    version: reader.read_u8()?,
+                    hash160: reader.read_hash160()?,
+                    name: reader.read_name()?,
+                },
+                asset_name: reader.read_name()?,
+                code: ConditionCode::from_u8(reader.read_u8()?)?,
+                amount: reader.read_u64()?,
+            }),
+            other => Err(format!("Unsupported post-condition type {:#04x}", other)),
+        }
+    }
+
+    pub fn serialize_into(&self, out: &mut Vec<u8>) {
+        match self {
+            PostCondition::Stx { principal, code, amount } => {
+                out.push(POST_CONDITION_STX);
+                principal.serialize_into(out);
+                out.push(*code as u8);
+                out.extend_from_slice(&amount.to_be_bytes());
+            }
+            PostCondition::Fungible { principal, contract, asset_name, code, amount } => {
+                out.push(POST_CONDITION_FUNGIBLE);
+                principal.serialize_into(out);
+                if let PrincipalData::Contract { version, hash160, name } = contract {
+                    out.push(*version);
+                    out.extend_from_slice(hash160);
+                    out.push(name.len() as u8);
+                    out.extend_from_slice(name.as_bytes());
+                }
+                out.push(asset_name.len() as u8);
+                out.extend_from_slice(asset_name.as_bytes());
+                out.push(*code as u8);
+                out.extend_from_slice(&amount.to_be_bytes());
+            }
+        }
+    }
+}
+
+/// Applies post-conditions to the events of an executed transaction the way a Stacks node
+/// does: every condition must hold, and in `Deny` mode every token outflow must be covered.
+pub fn check(
+    mode: PostConditionMode,
+    conditions: &[PostCondition],
+    origin: &str,
+    events: &[ContractEvent],
+) -> Result<(), String> {
+    let mut sent: HashMap<(String, String), u128> = HashMap::new();
+    for event in events {
+        match event {
+            ContractEvent::FtBurn { asset_identifier, sender, amount }
+            | ContractEvent::FtTransfer { asset_identifier, sender, amount, .. } => {
+                *sent.entry((sender.clone(), asset_identifier.clone())).or_default() += amount;
+            }
+            ContractEvent::FtMint { .. } | ContractEvent::Print { .. } => {}
+        }
+    }
+
+    let mut covered = Vec::new();
+    for condition in conditions {
+        match condition {
+            // STX transfers do not surface as contract events, so nothing was sent
+            PostCondition::Stx { code, amount, .. } => {
+                if !code.holds(0, *amount) {
+                    return Err(format!("STX post-condition {:?} {} not met", code, amount));
+                }
+            }
+            PostCondition::Fungible { principal, contract, asset_name, code, amount } => {
+                let key = (principal.resolve(origin), format!("{}::{}", contract, asset_name));
+                let total = sent.get(&key).copied().unwrap_or_default();
+                if !code.holds(total, *amount) {
+                    return Err(format!(
+                        "{} sent {} {}, violating {:?} {}",
+                        key.0, total, key.1, code, amount
+                    ));
+                }
+                covered.push(key);
+            }
+        }
+    }
+
+    if mode == PostConditionMode::Deny {
+        if let Some(((principal, asset), amount)) = sent.iter().find(|(key, _)| !covered.contains(key)) {
+            return Err(format!("{} sent {} {} without a covering post-condition", principal, amount, asset));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: &str = "ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM";
+    const TOKEN: &str = "ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM.zbtcz";
+
+    fn burn(amount: u128) -> ContractEvent {
+        ContractEvent::FtBurn {
+            asset_identifier: format!("{}::zbtcz-token", TOKEN),
+            sender: ORIGIN.to_string(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn test_exact_burn_condition() {
+        let conditions = vec![
+            PostCondition::no_stx_sent(),
+            PostCondition::ft_sent_exactly(PostConditionPrincipal::Origin, TOKEN, "zbtcz-token", 40).unwrap(),
+        ];
+        assert!(check(PostConditionMode::Deny, &conditions, ORIGIN, &[burn(40)]).is_ok());
+        assert!(check(PostConditionMode::Deny, &conditions, ORIGIN, &[burn(41)]).is_err());
+        assert!(check(PostConditionMode::Deny, &conditions, ORIGIN, &[]).is_err());
+    }
+
+    #[test]
+    fn test_deny_mode_rejects_uncovered_outflow() {
+        let conditions = vec![PostCondition::no_stx_sent()];
+        assert!(check(PostConditionMode::Allow, &conditions, ORIGIN, &[burn(5)]).is_ok());
+        let err = check(PostConditionMode::Deny, &conditions, ORIGIN, &[burn(5)]).unwrap_err();
+        assert!(err.contains("without a covering post-condition"));
+    }
+
+    #[test]
+    fn test_serializes_origin_fungible_condition() {
+        let mut out = Vec::new();
+        PostCondition::ft_sent_exactly(PostConditionPrincipal::Origin, TOKEN, "zbtcz-token", 7)
+            .unwrap()
+            .serialize_into(&mut out);
+        assert_eq!(&out[..3], &[POST_CONDITION_FUNGIBLE, PRINCIPAL_ORIGIN, 26]);
+        assert_eq!(out[23], 5); // contract name length
+        assert_eq!(&out[24..29], b"zbtcz");
+        assert_eq!(out[29], 11); // asset name length
+        assert_eq!(out[41], ConditionCode::Eq as u8);
+        assert_eq!(&out[42..], &7u64.to_be_bytes());
+    }
+}
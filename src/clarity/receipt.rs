@@ -0,0 +1,388 @@
+// File: src/clarity/receipt.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use crate::clarity::bindings::{transaction_bridge, zbtcz, zbtcz_smart_contract};
+use crate::clarity::value::ClarityValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStatus {
+    Pending,
+    Success,
+    AbortByResponse,
+    AbortByPostCondition,
+    Dropped,
+}
+
+impl TxStatus {
+    fn parse(status: &str) -> Self {
+        match status {
+            "success" => TxStatus::Success,
+            "abort_by_response" => TxStatus::AbortByResponse,
+            "abort_by_post_condition" => TxStatus::AbortByPostCondition,
+            s if s.starts_with("dropped") => TxStatus::Dropped,
+            _ => TxStatus::Pending,
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        *self != TxStatus::Pending
+    }
+}
+
+/// Event emitted by a confirmed contract call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContractEvent {
+    FtMint {
+        asset_identifier: String,
+        recipient: String,
+        amount: u128,
+    },
+    FtBurn {
+        asset_identifier: String,
+        sender: String,
+        amount: u128,
+    },
+    FtTransfer {
+        asset_identifier: String,
+        sender: String,
+        recipient: String,
+        amount: u128,
+    },
+    Print {
+        contract_identifier: String,
+        value: ClarityValue,
+    },
+}
+
+/// Tracked outcome of a state-changing contract call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub txid: String,
+    pub contract_source: String, // Contract source the call targeted, used to decode error codes
+    pub status: TxStatus,
+    pub result: Option<ClarityValue>,
+    pub events: Vec<ContractEvent>,
+}
+
+impl TransactionReceipt {
+    pub fn pending(txid: String, contract_source: &str) -> Self {
+        Self {
+            txid,
+            contract_source: contract_source.to_string(),
+            status: TxStatus::Pending,
+            result: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Builds a receipt from a Stacks API `/extended/v1/tx/{txid}` response
+    pub fn from_api(contract_source: &str, body: &Value) -> Result<Self, String> {
+        let txid = body["tx_id"]
+            .as_str()
+            .ok_or("Transaction response is missing tx_id")?
+            .trim_start_matches("0x")
+            .to_string();
+        let status = TxStatus::parse(body["tx_status"].as_str().unwrap_or("pending"));
+        let result = match body["tx_result"]["hex"].as_str() {
+            Some(hex) if status != TxStatus::Pending => Some(ClarityValue::from_hex(hex)?),
+            _ => None,
+        };
+
+        let mut events = Vec::new();
+        for event in body["events"].as_array().into_iter().flatten() {
+            if let Some(event) = Self::parse_api_event(event)? {
+                events.push(event);
+            }
+        }
+
+        Ok(Self {
+            txid,
+            contract_source: contract_source.to_string(),
+            status,
+            result,
+            events,
+        })
+    }
+
+    fn parse_api_event(event: &Value) -> Result<Option<ContractEvent>, String> {
+        let amount = |value: &Value| -> Result<u128, String> {
+            value["amount"]
+                .as_str()
+                .and_then(|amount| amount.parse().ok())
+                .ok_or_else(|| "Invalid event amount".to_string())
+        };
+        let text = |value: &Value, field: &str| value[field].as_str().unwrap_or_default().to_string();
+
+        match event["event_type"].as_str() {
+            Some("fungible_token_asset") => {
+                let asset = &event["asset"];
+                let asset_identifier = text(asset, "asset_id");
+                Ok(match asset["asset_event_type"].as_str() {
+                    Some("mint") => Some(ContractEvent::FtMint {
+                        asset_identifier,
+                        recipient: text(asset, "recipient"),
+                        amount: amount(asset)?,
+                    }),
+                    Some("burn") => Some(ContractEvent::FtBurn {
+                        asset_identifier,
+                        sender: text(asset, "sender"),
+                        amount: amount(asset)?,
+                    }),
+                    Some("transfer") => Some(ContractEvent::FtTransfer {
+                        asset_identifier,
+                        sender: text(asset, "sender"),
+                        recipient: text(asset, "recipient"),
+                        amount: amount(asset)?,
+                    }),
+                    _ => None,
+                })
+            }
+            Some("smart_contract_log") if event["contract_log"]["topic"] == "print" => {
+                let log = &event["contract_log"];
+                let hex = log["value"]["hex"].as_str().ok_or("Print event is missing its value")?;
+                Ok(Some(ContractEvent::Print {
+                    contract_identifier: text(log, "contract_id"),
+                    value: ClarityValue::from_hex(hex)?,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Builds a receipt from a transaction entry of an event-observer `/new_block` payload
+    pub fn from_observer(contract_source: &str, transaction: &Value, events: Vec<ContractEvent>) -> Result<Self, String> {
+        let txid = transaction["txid"]
+            .as_str()
+            .ok_or("Block transaction is missing txid")?
+            .trim_start_matches("0x")
+            .to_string();
+        let result = match transaction["raw_result"].as_str() {
+            Some(hex) => Some(ClarityValue::from_hex(hex)?),
+            None => None,
+        };
+
+        Ok(Self {
+            txid,
+            contract_source: contract_source.to_string(),
+            status: TxStatus::parse(transaction["status"].as_str().unwrap_or("pending")),
+            result,
+            events,
+        })
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.status == TxStatus::Success
+    }
+
+    /// The `ok` value of a confirmed call, or the typed reason it failed
+    pub fn outcome(&self) -> Result<&ClarityValue, ContractError> {
+        match self.status {
+            TxStatus::Pending => Err(ContractError::Pending),
+            TxStatus::Dropped => Err(ContractError::Dropped),
+            TxStatus::AbortByPostCondition => Err(ContractError::PostConditionFailed),
+            TxStatus::Success | TxStatus::AbortByResponse => match &self.result {
+                Some(ClarityValue::Response(Ok(value))) => Ok(value),
+                Some(ClarityValue::Response(Err(code))) => {
+                    Err(ContractError::from_code(&self.contract_source, code))
+                }
+                Some(other) if self.status == TxStatus::Success => Ok(other),
+                _ => Err(ContractError::Other {
+                    contract_source: self.contract_source.clone(),
+                    code: ClarityValue::none(),
+                }),
+            },
+        }
+    }
+}
+
+impl ContractEvent {
+    /// Parses an event from an event-observer `/new_block` payload
+    pub fn from_observer(event: &Value) -> Result<Option<ContractEvent>, String> {
+        let amount = |value: &Value| -> Result<u128, String> {
+            value["amount"]
+                .as_str()
+                .and_then(|amount| amount.parse().ok())
+                .ok_or_else(|| "Invalid event amount".to_string())
+        };
+        let text = |value: &Value, field: &str| value[field].as_str().unwrap_or_default().to_string();
+
+        match event["type"].as_str() {
+            Some("ft_mint_event") => {
+                let mint = &event["ft_mint_event"];
+                Ok(Some(ContractEvent::FtMint {
+                    asset_identifier: text(mint, "asset_identifier"),
+                    recipient: text(mint, "recipient"),
+                    amount: amount(mint)?,
+                }))
+            }
+            Some("ft_burn_event") => {
+                let burn = &event["ft_burn_event"];
+                Ok(Some(ContractEvent::FtBurn {
+                    asset_identifier: text(burn, "asset_identifier"),
+                    sender: text(burn, "sender"),
+                    amount: amount(burn)?,
+                }))
+            }
+            Some("ft_transfer_event") => {
+                let transfer = &event["ft_transfer_event"];
+                Ok(Some(ContractEvent::FtTransfer {
+                    asset_identifier: text(transfer, "asset_identifier"),
+                    sender: text(transfer, "sender"),
+                    recipient: text(transfer, "recipient"),
+                    amount: amount(transfer)?,
+                }))
+            }
+            Some("contract_event") if event["contract_event"]["topic"] == "print" => {
+                let log = &event["contract_event"];
+                let hex = log["raw_value"].as_str().ok_or("Print event is missing its value")?;
+                Ok(Some(ContractEvent::Print {
+                    contract_identifier: text(log, "contract_identifier"),
+                    value: ClarityValue::from_hex(hex)?,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Contract-level failure of a state-changing call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractError {
+    Pending,
+    Dropped,
+    PostConditionFailed,
+    DuplicateLock,            // transaction_bridge.clar u1001
+    UnknownLock,              // transaction_bridge.clar u1002
+    UnfinalizedMerkleRoot,    // zbtcz.clar u1002
+    InsufficientZbtczBalance, // zbtcz.clar u1003
+    Unauthorized,             // zbtcz_smart_contract.clar err-unauthorized
+    InsufficientBalance,      // zbtcz_smart_contract.clar err-insufficient-balance
+    Other { contract_source: String, code: ClarityValue },
+}
+
+impl ContractError {
+    /// Maps an `(err ...)` value to the variant declared by the given contract
+    pub fn from_code(contract_source: &str, code: &ClarityValue) -> Self {
+        let known = match (contract_source, code) {
+            (s, ClarityValue::UInt(1001)) if s == transaction_bridge::SOURCE => Some(ContractError::DuplicateLock),
+            (s, ClarityValue::UInt(1002)) if s == transaction_bridge::SOURCE => Some(ContractError::UnknownLock),
+            (s, ClarityValue::UInt(1002)) if s == zbtcz::SOURCE => Some(ContractError::UnfinalizedMerkleRoot),
+            (s, ClarityValue::UInt(1003)) if s == zbtcz::SOURCE => Some(ContractError::InsufficientZbtczBalance),
+            (s, ClarityValue::UInt(100)) if s == zbtcz_smart_contract::SOURCE => Some(ContractError::Unauthorized),
+            (s, ClarityValue::UInt(101)) if s == zbtcz_smart_contract::SOURCE => {
+                Some(ContractError::InsufficientBalance)
+            }
+            _ => None,
+        };
+
+        known.unwrap_or_else(|| ContractError::Other {
+            contract_source: contract_source.to_string(),
+            code: code.clone(),
+        })
+    }
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractError::Pending => write!(f, "Transaction is not confirmed yet"),
+            ContractError::Dropped => write!(f, "Transaction was dropped from the mempool"),
+            ContractError::PostConditionFailed => write!(f, "Transaction aborted by a post-condition"),
+            ContractError::DuplicateLock => write!(f, "BTCZ lock already recorded"),
+            ContractError::UnknownLock => write!(f, "No BTCZ lock recorded for this transaction"),
+            ContractError::UnfinalizedMerkleRoot => write!(f, "Merkle root is not a finalized state"),
+            ContractError::InsufficientZbtczBalance => write!(f, "Insufficient zBTCZ balance"),
+            ContractError::Unauthorized => write!(f, "Caller is not the bridge operator"),
+            ContractError::InsufficientBalance => write!(f, "Insufficient balance"),
+            ContractError::Other { contract_source, code } => {
+                write!(f, "{} returned (err {})", contract_source, code)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_receipt_from_api_with_events() {
+        let print = ClarityValue::tuple([("event", ClarityValue::StringAscii("mint".to_string()))]);
+        let body = json!({
+            "tx_id": "0xabc123",
+            "tx_status": "success",
+            "tx_result": { "hex": ClarityValue::ok(ClarityValue::Bool(true)).to_hex(), "repr": "(ok true)" },
+            "events": [
+                {
+                    "event_type": "fungible_token_asset",
+                    "asset": {
+                        "asset_event_type": "mint",
+                        "asset_id": "SP000000000000000000002Q6VF78.zbtcz::zbtcz-token",
+                        "sender": "",
+                        "recipient": "SP000000000000000000002Q6VF78",
+                        "amount": "500"
+                    }
+                },
+                {
+                    "event_type": "smart_contract_log",
+                    "contract_log": {
+                        "contract_id": "SP000000000000000000002Q6VF78.zbtcz",
+                        "topic": "print",
+                        "value": { "hex": print.to_hex(), "repr": "" }
+                    }
+                },
+                { "event_type": "stx_asset", "asset": {} }
+            ]
+        });
+
+        let receipt = TransactionReceipt::from_api(zbtcz::SOURCE, &body).unwrap();
+        assert_eq!(receipt.txid, "abc123");
+        assert!(receipt.is_confirmed());
+        assert_eq!(receipt.outcome().unwrap(), &ClarityValue::Bool(true));
+        assert_eq!(receipt.events.len(), 2);
+        assert!(matches!(&receipt.events[0], ContractEvent::FtMint { amount: 500, .. }));
+        assert_eq!(
+            receipt.events[1],
+            ContractEvent::Print {
+                contract_identifier: "SP000000000000000000002Q6VF78.zbtcz".to_string(),
+                value: print,
+            }
+        );
+    }
+
+    #[test]
+    fn test_abort_codes_map_to_typed_errors() {
+        let abort = |source: &str, code: u128| {
+            let body = json!({
+                "tx_id": "0x01",
+                "tx_status": "abort_by_response",
+                "tx_result": { "hex": ClarityValue::err(ClarityValue::UInt(code)).to_hex() },
+                "events": []
+            });
+            TransactionReceipt::from_api(source, &body).unwrap().outcome().unwrap_err()
+        };
+
+        assert_eq!(abort(zbtcz::SOURCE, 1002), ContractError::UnfinalizedMerkleRoot);
+        assert_eq!(abort(zbtcz::SOURCE, 1003), ContractError::InsufficientZbtczBalance);
+        assert_eq!(abort(zbtcz_smart_contract::SOURCE, 100), ContractError::Unauthorized);
+        assert_eq!(abort(zbtcz_smart_contract::SOURCE, 101), ContractError::InsufficientBalance);
+        assert_eq!(abort(transaction_bridge::SOURCE, 1002), ContractError::UnknownLock);
+        assert!(matches!(abort(zbtcz::SOURCE, 42), ContractError::Other { .. }));
+    }
+
+    #[test]
+    fn test_pending_and_post_condition_status() {
+        let pending = TransactionReceipt::pending("01".to_string(), zbtcz::SOURCE);
+        assert_eq!(pending.outcome().unwrap_err(), ContractError::Pending);
+
+        let body = json!({ "tx_id": "0x02", "tx_status": "abort_by_post_condition", "events": [] });
+        let receipt = TransactionReceipt::from_api(zbtcz::SOURCE, &body).unwrap();
+        assert_eq!(receipt.outcome().unwrap_err(), ContractError::PostConditionFailed);
+        assert!(receipt.status.is_final());
+    }
+}
@@ -0,0 +1,537 @@
+// File: src/clarity/transaction.rs
+
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512_256};
+use crate::clarity::bindings::ContractCall;
+use crate::clarity::post_condition::{PostCondition, PostConditionMode};
+use crate::clarity::value::{c32_address_encode, PrincipalData, Reader};
+
+const AUTH_STANDARD: u8 = 0x04;
+const AUTH_SPONSORED: u8 = 0x05;
+const HASH_MODE_P2PKH: u8 = 0x00;
+const KEY_ENCODING_COMPRESSED: u8 = 0x00;
+const ANCHOR_MODE_ANY: u8 = 0x03;
+const PAYLOAD_CONTRACT_CALL: u8 = 0x02;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StacksNetwork {
+    Mainnet,
+    Testnet,
+}
+
+impl StacksNetwork {
+    fn from_transaction_version(version: u8) -> Result<Self, String> {
+        match version {
+            0x00 => Ok(StacksNetwork::Mainnet),
+            0x80 => Ok(StacksNetwork::Testnet),
+            other => Err(format!("Unknown transaction version {:#04x}", other)),
+        }
+    }
+
+    fn transaction_version(&self) -> u8 {
+        match self {
+            StacksNetwork::Mainnet => 0x00,
+            StacksNetwork::Testnet => 0x80,
+        }
+    }
+
+    fn chain_id(&self) -> u32 {
+        match self {
+            StacksNetwork::Mainnet => 0x0000_0001,
+            StacksNetwork::Testnet => 0x8000_0000,
+        }
+    }
+
+    /// Address version byte for single-sig (P2PKH) accounts
+    pub fn address_version(&self) -> u8 {
+        match self {
+            StacksNetwork::Mainnet => 22, // SP...
+            StacksNetwork::Testnet => 26, // ST...
+        }
+    }
+}
+
+/// Private key used to sign transactions on behalf of the node
+#[derive(Clone)]
+pub struct StacksSigner {
+    key: SigningKey,
+    pub network: StacksNetwork,
+}
+
+impl std::fmt::Debug for StacksSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StacksSigner({})", self.address()) // Never print the key itself
+    }
+}
+
+impl StacksSigner {
+    /// Accepts a 32-byte hex key, optionally with the `01` compressed-key suffix
+    pub fn from_hex(secret: &str, network: StacksNetwork) -> Result<Self, String> {
+        let mut bytes = hex::decode(secret.trim()).map_err(|_| "Invalid private key hex".to_string())?;
+        if bytes.len() == 33 && bytes[32] == 0x01 {
+            bytes.truncate(32);
+        }
+        let key = SigningKey::from_slice(&bytes).map_err(|_| "Invalid private key".to_string())?;
+        Ok(Self { key, network })
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key.verifying_key().to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    pub fn hash160(&self) -> [u8; 20] {
+        hash160(&self.public_key())
+    }
+
+    pub fn address(&self) -> String {
+        c32_address_encode(self.network.address_version(), &self.hash160())
+    }
+
+    /// Recoverable signature over `digest`: recovery id followed by the 64-byte signature
+    pub fn sign(&self, digest: &[u8; 32]) -> Result<[u8; 65], String> {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(digest)
+            .map_err(|err| format!("Signing failed: {}", err))?;
+        let mut encoded = [0u8; 65];
+        encoded[0] = recovery_id.to_byte();
+        encoded[1..].copy_from_slice(&signature.to_bytes());
+        Ok(encoded)
+    }
+}
+
+/// Hash160 of the compressed public key that produced a recoverable `signature` over `digest`
+pub fn recover_hash160(digest: &[u8; 32], signature: &[u8; 65]) -> Result<[u8; 20], String> {
+    let recovery_id = RecoveryId::from_byte(signature[0]).ok_or("Invalid recovery id")?;
+    let signature = Signature::from_slice(&signature[1..]).map_err(|_| "Invalid signature")?;
+    let key = VerifyingKey::recover_from_prehash(digest, &signature, recovery_id)
+        .map_err(|_| "Signature recovery failed")?;
+    Ok(hash160(key.to_encoded_point(true).as_bytes()))
+}
+
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    let mut out = [0u8; 20];
+    out.copy_from_slice(&Ripemd160::digest(Sha256::digest(data)));
+    out
+}
+
+fn sha512_256(data: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&Sha512_256::digest(data));
+    out
+}
+
+/// Single-sig spending condition authorizing a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendingCondition {
+    pub signer: [u8; 20],
+    pub nonce: u64,
+    pub fee: u64,
+    pub signature: [u8; 65],
+}
+
+impl SpendingCondition {
+    pub fn new(signer: [u8; 20], nonce: u64, fee: u64) -> Self {
+        Self {
+            signer,
+            nonce,
+            fee,
+            signature: [0u8; 65],
+        }
+    }
+
+    /// Placeholder a sponsored transaction carries until the sponsor fills it in
+    fn initial_sponsor() -> Self {
+        Self::new([0u8; 20], 0, 0)
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        if reader.read_u8()? != HASH_MODE_P2PKH {
+            return Err("Only single-sig (P2PKH) spending conditions are supported".to_string());
+        }
+        let signer = reader.read_hash160()?;
+        let nonce = reader.read_u64()?;
+        let fee = reader.read_u64()?;
+        if reader.read_u8()? != KEY_ENCODING_COMPRESSED {
+            return Err("Only compressed public keys are supported".to_string());
+        }
+        let mut signature = [0u8; 65];
+        signature.copy_from_slice(reader.take(65)?);
+        Ok(Self { signer, nonce, fee, signature })
+    }
+
+    fn serialize_into(&self, out: &mut Vec<u8>) {
+        out.push(HASH_MODE_P2PKH);
+        out.extend_from_slice(&self.signer);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.fee.to_be_bytes());
+        out.push(KEY_ENCODING_COMPRESSED);
+        out.extend_from_slice(&self.signature);
+    }
+
+    fn cleared(&self) -> Self {
+        Self::new(self.signer, 0, 0)
+    }
+
+    /// Signs `cur_sighash` for this condition and returns the next sighash in the chain
+    fn sign(&mut self, cur_sighash: [u8; 32], auth_flag: u8, signer: &StacksSigner) -> Result<[u8; 32], String> {
+        if signer.hash160() != self.signer {
+            return Err("Signing key does not match the spending condition".to_string());
+        }
+        let presign = presign_sighash(cur_sighash, auth_flag, self.fee, self.nonce);
+        self.signature = signer.sign(&presign)?;
+        Ok(postsign_sighash(presign, &self.signature))
+    }
+
+    /// Recovers the signer from the signature and checks it matches the condition
+    fn verify(&self, cur_sighash: [u8; 32], auth_flag: u8) -> Result<[u8; 32], String> {
+        let presign = presign_sighash(cur_sighash, auth_flag, self.fee, self.nonce);
+        if recover_hash160(&presign, &self.signature)? != self.signer {
+            return Err("Signature does not match the spending condition".to_string());
+        }
+        Ok(postsign_sighash(presign, &self.signature))
+    }
+}
+
+fn presign_sighash(cur_sighash: [u8; 32], auth_flag: u8, fee: u64, nonce: u64) -> [u8; 32] {
+    let mut data = cur_sighash.to_vec();
+    data.push(auth_flag);
+    data.extend_from_slice(&fee.to_be_bytes());
+    data.extend_from_slice(&nonce.to_be_bytes());
+    sha512_256(&data)
+}
+
+fn postsign_sighash(presign: [u8; 32], signature: &[u8; 65]) -> [u8; 32] {
+    let mut data = presign.to_vec();
+    data.push(KEY_ENCODING_COMPRESSED);
+    data.extend_from_slice(signature);
+    sha512_256(&data)
+}
+
+/// A contract-call transaction in Stacks wire format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StacksTransaction {
+    pub network: StacksNetwork,
+    pub origin: SpendingCondition,
+    pub sponsor: Option<SpendingCondition>, // Pays the fee when set
+    pub contract: PrincipalData,
+    pub call: ContractCall,
+    pub post_condition_mode: PostConditionMode,
+    pub post_conditions: Vec<PostCondition>,
+}
+
+impl StacksTransaction {
+    pub fn contract_call(
+        network: StacksNetwork,
+        origin: SpendingCondition,
+        contract_id: &str,
+        call: ContractCall,
+    ) -> Result<Self, String> {
+        let contract = PrincipalData::parse(contract_id)?;
+        if !matches!(contract, PrincipalData::Contract { .. }) {
+            return Err(format!("Not a contract identifier: {}", contract_id));
+        }
+        Ok(Self {
+            network,
+            origin,
+            sponsor: None,
+            contract,
+            call,
+            post_condition_mode: PostConditionMode::Allow,
+            post_conditions: Vec::new(),
+        })
+    }
+
+    /// Restricts the transaction to the given asset movements (deny mode)
+    pub fn with_post_conditions(mut self, post_conditions: Vec<PostCondition>) -> Self {
+        self.post_condition_mode = PostConditionMode::Deny;
+        self.post_conditions = post_conditions;
+        self
+    }
+
+    /// Marks the transaction as sponsored; the origin signs first, leaving the fee to the sponsor
+    pub fn sponsored(mut self) -> Self {
+        self.sponsor = Some(SpendingCondition::initial_sponsor());
+        self
+    }
+
+    /// Decodes a contract call. `resolve` maps the target contract and function name to
+    /// the binding's function name, rejecting calls it does not recognise.
+    pub fn deserialize<F>(bytes: &[u8], resolve: F) -> Result<Self, String>
+    where
+        F: Fn(&PrincipalData, &str) -> Option<&'static str>,
+    {
+        let mut reader = Reader::new(bytes);
+        let network = StacksNetwork::from_transaction_version(reader.read_u8()?)?;
+        if reader.read_u32()? != network.chain_id() {
+            return Err("Chain id does not match the transaction version".to_string());
+        }
+
+        let auth_type = reader.read_u8()?;
+        let origin = SpendingCondition::read(&mut reader)?;
+        let sponsor = match auth_type {
+            AUTH_STANDARD => None,
+            AUTH_SPONSORED => Some(SpendingCondition::read(&mut reader)?),
+            other => return Err(format!("Unknown authorization type {:#04x}", other)),
+        };
+
+        if reader.read_u8()? != ANCHOR_MODE_ANY {
+            return Err("Only anchor mode 'any' is supported".to_string());
+        }
+        let post_condition_mode = match reader.read_u8()? {
+            0x01 => PostConditionMode::Allow,
+            0x02 => PostConditionMode::Deny,
+            other => return Err(format!("Unknown post-condition mode {:#04x}", other)),
+        };
+        let post_conditions = (0..reader.read_u32()?)
+            .map(|_| PostCondition::read(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if reader.read_u8()? != PAYLOAD_CONTRACT_CALL {
+            return Err("Only contract-call payloads are supported".to_string());
+        }
+        let contract = PrincipalData::Contract {
+            version: reader.read_u8()?,
+            hash160: reader.read_hash160()?,
+            name: reader.read_name()?,
+        };
+        let function = reader.read_name()?;
+        let function_name = resolve(&contract, &function)
+            .ok_or_else(|| format!("Call to {}::{} is not allowed", contract, function))?;
+        let args = (0..reader.read_u32()?)
+            .map(|_| reader.read_value(0))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !reader.is_empty() {
+            return Err("Trailing bytes after transaction".to_string());
+        }
+
+        Ok(Self {
+            network,
+            origin,
+            sponsor,
+            contract,
+            call: ContractCall {
+                function_name,
+                read_only: false,
+                args,
+            },
+            post_condition_mode,
+            post_conditions,
+        })
+    }
+
+    /// Address of the account that signed the transaction and whose nonce it uses
+    pub fn origin_address(&self) -> String {
+        c32_address_encode(self.network.address_version(), &self.origin.signer)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        self.serialize_with_auth(&self.origin, self.sponsor.as_ref())
+    }
+
+    fn serialize_with_auth(&self, origin: &SpendingCondition, sponsor: Option<&SpendingCondition>) -> Vec<u8> {
+        let mut out = vec![self.network.transaction_version()];
+        out.extend_from_slice(&self.network.chain_id().to_be_bytes());
+
+        match sponsor {
+            Some(sponsor) => {
+                out.push(AUTH_SPONSORED);
+                origin.serialize_into(&mut out);
+                sponsor.serialize_into(&mut out);
+            }
+            None => {
+                out.push(AUTH_STANDARD);
+                origin.serialize_into(&mut out);
+            }
+        }
+
+        out.push(ANCHOR_MODE_ANY);
+        out.push(self.post_condition_mode as u8);
+        out.extend_from_slice(&(self.post_conditions.len() as u32).to_be_bytes());
+        for condition in &self.post_conditions {
+            condition.serialize_into(&mut out);
+        }
+
+        out.push(PAYLOAD_CONTRACT_CALL);
+        if let PrincipalData::Contract { version, hash160, name } = &self.contract {
+            out.push(*version);
+            out.extend_from_slice(hash160);
+            out.push(name.len() as u8);
+            out.extend_from_slice(name.as_bytes());
+        }
+        out.push(self.call.function_name.len() as u8);
+        out.extend_from_slice(self.call.function_name.as_bytes());
+        out.extend_from_slice(&(self.call.args.len() as u32).to_be_bytes());
+        for arg in &self.call.args {
+            out.extend_from_slice(&arg.serialize());
+        }
+        out
+    }
+
+    /// Transaction id as reported by the Stacks node (hex, no prefix)
+    pub fn txid(&self) -> String {
+        hex::encode(sha512_256(&self.serialize()))
+    }
+
+    fn initial_sighash(&self) -> [u8; 32] {
+        let sponsor = self.sponsor.as_ref().map(|_| SpendingCondition::initial_sponsor());
+        sha512_256(&self.serialize_with_auth(&self.origin.cleared(), sponsor.as_ref()))
+    }
+
+    /// Signs as the origin; in a sponsored transaction the sponsor signs afterwards
+    pub fn sign(&mut self, signer: &StacksSigner) -> Result<(), String> {
+        let initial = self.initial_sighash();
+        self.origin.sign(initial, AUTH_STANDARD, signer)?;
+        Ok(())
+    }
+
+    /// Fills in the sponsor's nonce and fee and signs after the origin's signature
+    pub fn sign_sponsor(&mut self, signer: &StacksSigner, nonce: u64, fee: u64) -> Result<(), String> {
+        if self.sponsor.is_none() {
+            return Err("Transaction is not sponsored".to_string());
+        }
+        let origin_sighash = self.origin.verify(self.initial_sighash(), AUTH_STANDARD)?;
+        let mut sponsor = SpendingCondition::new(signer.hash160(), nonce, fee);
+        sponsor.sign(origin_sighash, AUTH_SPONSORED, signer)?;
+        self.sponsor = Some(sponsor);
+        Ok(())
+    }
+
+    /// Checks the origin signature only, as a sponsor does before paying for the transaction
+    pub fn verify_origin(&self) -> Result<(), String> {
+        self.origin.verify(self.initial_sighash(), AUTH_STANDARD).map(|_| ())
+    }
+
+    pub fn verify(&self) -> Result<(), String> {
+        let origin_sighash = self.origin.verify(self.initial_sighash(), AUTH_STANDARD)?;
+        match &self.sponsor {
+            Some(sponsor) => sponsor.verify(origin_sighash, AUTH_SPONSORED).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::bindings::zbtcz;
+    use crate::clarity::post_condition::PostConditionPrincipal;
+
+    const TEST_KEY: &str = "edf9aee84d9b7abc145504dde6726c64f369d37ee34ded868fabd876c26570bc01";
+
+    #[test]
+    fn test_signer_address() {
+        let signer = StacksSigner::from_hex(TEST_KEY, StacksNetwork::Testnet).unwrap();
+        assert_eq!(signer.public_key().len(), 33);
+        assert!(signer.address().starts_with("ST"));
+        assert_eq!(PrincipalData::parse(&signer.address()).unwrap(), PrincipalData::Standard {
+            version: 26,
+            hash160: signer.hash160(),
+        });
+        assert!(StacksSigner::from_hex("zz", StacksNetwork::Testnet).is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify_contract_call() {
+        let signer = StacksSigner::from_hex(TEST_KEY, StacksNetwork::Testnet).unwrap();
+        let contract_id = format!("{}.zbtcz", signer.address());
+        let mut tx = StacksTransaction::contract_call(
+            StacksNetwork::Testnet,
+            SpendingCondition::new(signer.hash160(), 7, 1_000),
+            &contract_id,
+            zbtcz::burn_zbtcz(250, 9),
+        )
+        .unwrap();
+
+        let unsigned_txid = tx.txid();
+        tx.sign(&signer).unwrap();
+        assert_ne!(tx.txid(), unsigned_txid);
+        assert!(tx.verify().is_ok());
+
+        let bytes = tx.serialize();
+        assert_eq!(&bytes[..6], &[0x80, 0x80, 0x00, 0x00, 0x00, AUTH_STANDARD]);
+
+        // Any change to the signed fields invalidates the signature
+        tx.origin.fee += 1;
+        assert!(tx.verify().is_err());
+    }
+
+    #[test]
+    fn test_post_conditions_are_signed() {
+        let signer = StacksSigner::from_hex(TEST_KEY, StacksNetwork::Testnet).unwrap();
+        let contract_id = format!("{}.zbtcz", signer.address());
+        let burn = PostCondition::ft_sent_exactly(PostConditionPrincipal::Origin, &contract_id, "zbtcz-token", 250).unwrap();
+        let mut tx = StacksTransaction::contract_call(
+            StacksNetwork::Testnet,
+            SpendingCondition::new(signer.hash160(), 0, 1_000),
+            &contract_id,
+            zbtcz::burn_zbtcz(250, 9),
+        )
+        .unwrap()
+        .with_post_conditions(vec![PostCondition::no_stx_sent(), burn]);
+        tx.sign(&signer).unwrap();
+
+        // version, chain id, auth type, 1 + 20 + 8 + 8 + 1 + 65 spending condition, anchor mode
+        let bytes = tx.serialize();
+        assert_eq!(bytes[110], PostConditionMode::Deny as u8);
+        assert_eq!(&bytes[111..115], &2u32.to_be_bytes());
+
+        // Dropping a condition after signing invalidates the signature
+        tx.post_conditions.pop();
+        assert!(tx.verify().is_err());
+    }
+
+    #[test]
+    fn test_sponsored_round_trip() {
+        let user = StacksSigner::from_hex(TEST_KEY, StacksNetwork::Testnet).unwrap();
+        let sponsor = StacksSigner::from_hex(&"11".repeat(32), StacksNetwork::Testnet).unwrap();
+        let contract_id = format!("{}.zbtcz", sponsor.address());
+        let burn = PostCondition::ft_sent_exactly(PostConditionPrincipal::Origin, &contract_id, "zbtcz-token", 5).unwrap();
+        let mut tx = StacksTransaction::contract_call(
+            StacksNetwork::Testnet,
+            SpendingCondition::new(user.hash160(), 3, 0),
+            &contract_id,
+            zbtcz::burn_zbtcz(5, 1),
+        )
+        .unwrap()
+        .with_post_conditions(vec![burn])
+        .sponsored();
+        tx.sign(&user).unwrap();
+        assert!(tx.verify_origin().is_ok());
+        assert!(tx.verify().is_err()); // The sponsor has not signed yet
+
+        let resolve = |_: &PrincipalData, name: &str| (name == "burn-zbtcz").then_some("burn-zbtcz");
+        let mut decoded = StacksTransaction::deserialize(&tx.serialize(), resolve).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.origin_address(), user.address());
+
+        decoded.sign_sponsor(&sponsor, 12, 2_000).unwrap();
+        assert!(decoded.verify().is_ok());
+        assert_eq!(decoded.sponsor.as_ref().unwrap().fee, 2_000);
+        assert_eq!(StacksTransaction::deserialize(&decoded.serialize(), resolve).unwrap(), decoded);
+
+        // Calls the resolver does not recognise are refused
+        assert!(StacksTransaction::deserialize(&decoded.serialize(), |_, _| None).is_err());
+    }
+
+    #[test]
+    fn test_rejects_mismatched_signer() {
+        let signer = StacksSigner::from_hex(TEST_KEY, StacksNetwork::Testnet).unwrap();
+        let mut tx = StacksTransaction::contract_call(
+            StacksNetwork::Testnet,
+            SpendingCondition::new([1u8; 20], 0, 0),
+            &format!("{}.zbtcz", signer.address()),
+            zbtcz::burn_zbtcz(1, 1),
+        )
+        .unwrap();
+        assert!(tx.sign(&signer).is_err());
+        assert!(StacksTransaction::contract_call(
+            StacksNetwork::Testnet,
+            SpendingCondition::new([1u8; 20], 0, 0),
+            &signer.address(),
+            zbtcz::burn_zbtcz(1, 1),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,712 @@
+// File: src/clarity/value.rs
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+const C32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const MAX_NESTING_DEPTH: usize = 32; // Same limit the Stacks node enforces
+
+/// Consensus type prefixes (SIP-005)
+const TYPE_INT: u8 = 0x00;
+const TYPE_UINT: u8 = 0x01;
+const TYPE_BUFFER: u8 = 0x02;
+const TYPE_TRUE: u8 = 0x03;
+const TYPE_FALSE: u8 = 0x04;
+const TYPE_STANDARD_PRINCIPAL: u8 = 0x05;
+const TYPE_CONTRACT_PRINCIPAL: u8 = 0x06;
+const TYPE_RESPONSE_OK: u8 = 0x07;
+const TYPE_RESPONSE_ERR: u8 = 0x08;
+const TYPE_NONE: u8 = 0x09;
+const TYPE_SOME: u8 = 0x0a;
+const TYPE_LIST: u8 = 0x0b;
+const TYPE_TUPLE: u8 = 0x0c;
+const TYPE_STRING_ASCII: u8 = 0x0d;
+const TYPE_STRING_UTF8: u8 = 0x0e;
+
+/// A Stacks principal: either a standard account or a contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrincipalData {
+    Standard { version: u8, hash160: [u8; 20] },
+    Contract { version: u8, hash160: [u8; 20], name: String },
+}
+
+impl PrincipalData {
+    /// Parses `SP...` / `ST...` addresses and `SP....contract-name` identifiers
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (address, name) = match input.split_once('.') {
+            Some((address, name)) => (address, Some(name)),
+            None => (input, None),
+        };
+        let (version, hash160) = c32_address_decode(address)?;
+
+        match name {
+            Some(name) => {
+                if name.is_empty() || name.len() > 128 {
+                    return Err(format!("Invalid contract name: {}", name));
+                }
+                Ok(PrincipalData::Contract {
+                    version,
+                    hash160,
+                    name: name.to_string(),
+                })
+            }
+            None => Ok(PrincipalData::Standard { version, hash160 }),
+        }
+    }
+
+    /// Returns the `S...` address part of the principal
+    pub fn address(&self) -> String {
+        match self {
+            PrincipalData::Standard { version, hash160 }
+            | PrincipalData::Contract { version, hash160, .. } => c32_address_encode(*version, hash160),
+        }
+    }
+}
+
+impl fmt::Display for PrincipalData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrincipalData::Standard { .. } => write!(f, "{}", self.address()),
+            PrincipalData::Contract { name, .. } => write!(f, "{}.{}", self.address(), name),
+        }
+    }
+}
+
+/// A typed Clarity value as passed to and returned from contract calls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClarityValue {
+    Int(i128),
+    UInt(u128),
+    Bool(bool),
+    Buffer(Vec<u8>),
+    StringAscii(String),
+    StringUtf8(String),
+    Principal(PrincipalData),
+    Tuple(BTreeMap<String, ClarityValue>), // BTreeMap keeps fields in consensus (sorted) order
+    List(Vec<ClarityValue>),
+    Optional(Option<Box<ClarityValue>>),
+    Response(Result<Box<ClarityValue>, Box<ClarityValue>>),
+}
+
+impl ClarityValue {
+    pub fn principal(address: &str) -> Result<Self, String> {
+        PrincipalData::parse(address).map(ClarityValue::Principal)
+    }
+
+    pub fn some(value: ClarityValue) -> Self {
+        ClarityValue::Optional(Some(Box::new(value)))
+    }
+
+    pub fn none() -> Self {
+        ClarityValue::Optional(None)
+    }
+
+    pub fn ok(value: ClarityValue) -> Self {
+        ClarityValue::Response(Ok(Box::new(value)))
+    }
+
+    pub fn err(value: ClarityValue) -> Self {
+        ClarityValue::Response(Err(Box::new(value)))
+    }
+
+    pub fn tuple<I, K>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, ClarityValue)>,
+        K: Into<String>,
+    {
+        ClarityValue::Tuple(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Serializes the value using the Stacks consensus encoding
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.serialize_into(&mut out);
+        out
+    }
+
+    fn serialize_into(&self, out: &mut Vec<u8>) {
+        match self {
+            ClarityValue::Int(value) => {
+                out.push(TYPE_INT);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            ClarityValue::UInt(value) => {
+                out.push(TYPE_UINT);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            ClarityValue::Bool(true) => out.push(TYPE_TRUE),
+            ClarityValue::Bool(false) => out.push(TYPE_FALSE),
+            ClarityValue::Buffer(bytes) => {
+                out.push(TYPE_BUFFER);
+                write_prefixed(out, bytes);
+            }
+            ClarityValue::StringAscii(text) => {
+                out.push(TYPE_STRING_ASCII);
+                write_prefixed(out, text.as_bytes());
+            }
+            ClarityValue::StringUtf8(text) => {
+                out.push(TYPE_STRING_UTF8);
+                write_prefixed(out, text.as_bytes());
+            }
+            ClarityValue::Principal(PrincipalData::Standard { version, hash160 }) => {
+                out.push(TYPE_STANDARD_PRINCIPAL);
+                out.push(*version);
+                out.extend_from_slice(hash160);
+            }
+            ClarityValue::Principal(PrincipalData::Contract { version, hash160, name }) => {
+                out.push(TYPE_CONTRACT_PRINCIPAL);
+                out.push(*version);
+                out.extend_from_slice(hash160);
+                out.push(name.len() as u8);
+                out.extend_from_slice(name.as_bytes());
+            }
+            ClarityValue::Response(Ok(inner)) => {
+                out.push(TYPE_RESPONSE_OK);
+                inner.serialize_into(out);
+            }
+            ClarityValue::Response(Err(inner)) => {
+                out.push(TYPE_RESPONSE_ERR);
+                inner.serialize_into(out);
+            }
+            ClarityValue::Optional(None) => out.push(TYPE_NONE),
+            ClarityValue::Optional(Some(inner)) => {
+                out.push(TYPE_SOME);
+                inner.serialize_into(out);
+            }
+            ClarityValue::List(items) => {
+                out.push(TYPE_LIST);
+                out.extend_from_slice(&(items.len() as u32).to_be_bytes());
+                for item in items {
+                    item.serialize_into(out);
+                }
+            }
+            ClarityValue::Tuple(fields) => {
+                out.push(TYPE_TUPLE);
+                out.extend_from_slice(&(fields.len() as u32).to_be_bytes());
+                for (name, value) in fields {
+                    out.push(name.len() as u8);
+                    out.extend_from_slice(name.as_bytes());
+                    value.serialize_into(out);
+                }
+            }
+        }
+    }
+
+    /// Hex form (`0x`-prefixed) accepted by the Stacks node RPC
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.serialize()))
+    }
+
+    /// Deserializes a single value, rejecting trailing bytes
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { bytes, pos: 0 };
+        let value = reader.read_value(0)?;
+        if reader.pos != bytes.len() {
+            return Err(format!(
+                "Trailing bytes after Clarity value: {} of {} consumed",
+                reader.pos,
+                bytes.len()
+            ));
+        }
+        Ok(value)
+    }
+
+    pub fn from_hex(input: &str) -> Result<Self, String> {
+        let trimmed = input.strip_prefix("0x").unwrap_or(input);
+        let bytes = hex::decode(trimmed).map_err(|err| format!("Invalid hex: {}", err))?;
+        Self::deserialize(&bytes)
+    }
+
+    pub fn expect_u128(&self) -> Result<u128, String> {
+        match self {
+            ClarityValue::UInt(value) => Ok(*value),
+            other => Err(format!("Expected uint, found {}", other)),
+        }
+    }
+
+    pub fn expect_u64(&self) -> Result<u64, String> {
+        let value = self.expect_u128()?;
+        u64::try_from(value).map_err(|_| format!("uint {} does not fit in u64", value))
+    }
+
+    pub fn expect_i128(&self) -> Result<i128, String> {
+        match self {
+            ClarityValue::Int(value) => Ok(*value),
+            other => Err(format!("Expected int, found {}", other)),
+        }
+    }
+
+    pub fn expect_bool(&self) -> Result<bool, String> {
+        match self {
+            ClarityValue::Bool(value) => Ok(*value),
+            other => Err(format!("Expected bool, found {}", other)),
+        }
+    }
+
+    pub fn expect_buffer(&self) -> Result<&[u8], String> {
+        match self {
+            ClarityValue::Buffer(bytes) => Ok(bytes),
+            other => Err(format!("Expected buff, found {}", other)),
+        }
+    }
+
+    pub fn expect_string(&self) -> Result<&str, String> {
+        match self {
+            ClarityValue::StringAscii(text) | ClarityValue::StringUtf8(text) => Ok(text),
+            other => Err(format!("Expected string, found {}", other)),
+        }
+    }
+
+    pub fn expect_principal(&self) -> Result<&PrincipalData, String> {
+        match self {
+            ClarityValue::Principal(principal) => Ok(principal),
+            other => Err(format!("Expected principal, found {}", other)),
+        }
+    }
+
+    pub fn expect_list(&self) -> Result<&[ClarityValue], String> {
+        match self {
+            ClarityValue::List(items) => Ok(items),
+            other => Err(format!("Expected list, found {}", other)),
+        }
+    }
+
+    pub fn expect_tuple(&self) -> Result<&BTreeMap<String, ClarityValue>, String> {
+        match self {
+            ClarityValue::Tuple(fields) => Ok(fields),
+            other => Err(format!("Expected tuple, found {}", other)),
+        }
+    }
+
+    /// Looks up a tuple field by name
+    pub fn get(&self, field: &str) -> Result<&ClarityValue, String> {
+        self.expect_tuple()?
+            .get(field)
+            .ok_or_else(|| format!("Tuple has no field '{}'", field))
+    }
+
+    pub fn expect_optional(&self) -> Result<Option<&ClarityValue>, String> {
+        match self {
+            ClarityValue::Optional(inner) => Ok(inner.as_deref()),
+            other => Err(format!("Expected optional, found {}", other)),
+        }
+    }
+
+    /// Splits a response into its `ok` or `err` branch
+    pub fn expect_response(&self) -> Result<Result<&ClarityValue, &ClarityValue>, String> {
+        match self {
+            ClarityValue::Response(Ok(inner)) => Ok(Ok(inner)),
+            ClarityValue::Response(Err(inner)) => Ok(Err(inner)),
+            other => Err(format!("Expected response, found {}", other)),
+        }
+    }
+
+    /// Unwraps an `(ok ...)` response, turning `(err ...)` into an error message
+    pub fn expect_ok(&self) -> Result<&ClarityValue, String> {
+        match self.expect_response()? {
+            Ok(inner) => Ok(inner),
+            Err(inner) => Err(format!("Contract returned (err {})", inner)),
+        }
+    }
+}
+
+impl fmt::Display for ClarityValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClarityValue::Int(value) => write!(f, "{}", value),
+            ClarityValue::UInt(value) => write!(f, "u{}", value),
+            ClarityValue::Bool(value) => write!(f, "{}", value),
+            ClarityValue::Buffer(bytes) => write!(f, "0x{}", hex::encode(bytes)),
+            ClarityValue::StringAscii(text) => write!(f, "{:?}", text),
+            ClarityValue::StringUtf8(text) => write!(f, "u{:?}", text),
+            ClarityValue::Principal(principal) => write!(f, "'{}", principal),
+            ClarityValue::Tuple(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                write!(f, "}}")
+            }
+            ClarityValue::List(items) => {
+                write!(f, "(list")?;
+                for item in items {
+                    write!(f, " {}", item)?;
+                }
+                write!(f, ")")
+            }
+            ClarityValue::Optional(None) => write!(f, "none"),
+            ClarityValue::Optional(Some(inner)) => write!(f, "(some {})", inner),
+            ClarityValue::Response(Ok(inner)) => write!(f, "(ok {})", inner),
+            ClarityValue::Response(Err(inner)) => write!(f, "(err {})", inner),
+        }
+    }
+}
+
+impl From<u128> for ClarityValue {
+    fn from(value: u128) -> Self {
+        ClarityValue::UInt(value)
+    }
+}
+
+impl From<u64> for ClarityValue {
+    fn from(value: u64) -> Self {
+        ClarityValue::UInt(value.into())
+    }
+}
+
+impl From<i128> for ClarityValue {
+    fn from(value: i128) -> Self {
+        ClarityValue::Int(value)
+    }
+}
+
+impl From<bool> for ClarityValue {
+    fn from(value: bool) -> Self {
+        ClarityValue::Bool(value)
+    }
+}
+
+impl From<Vec<u8>> for ClarityValue {
+    fn from(value: Vec<u8>) -> Self {
+        ClarityValue::Buffer(value)
+    }
+}
+
+impl From<PrincipalData> for ClarityValue {
+    fn from(value: PrincipalData) -> Self {
+        ClarityValue::Principal(value)
+    }
+}
+
+fn write_prefixed(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Cursor over consensus-serialized bytes, shared with transaction decoding
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| format!("Unexpected end of input at byte {}", self.pos))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, String> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn read_16(&mut self) -> Result<[u8; 16], String> {
+        let mut buf = [0u8; 16];
+        buf.copy_from_slice(self.take(16)?);
+        Ok(buf)
+    }
+
+    pub(crate) fn read_hash160(&mut self) -> Result<[u8; 20], String> {
+        let mut buf = [0u8; 20];
+        buf.copy_from_slice(self.take(20)?);
+        Ok(buf)
+    }
+
+    pub(crate) fn read_name(&mut self) -> Result<String, String> {
+        let len = self.read_u8()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| "Invalid name encoding".to_string())
+    }
+
+    pub(crate) fn read_value(&mut self, depth: usize) -> Result<ClarityValue, String> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err("Clarity value nested too deeply".to_string());
+        }
+
+        let prefix = self.read_u8()?;
+        let value = match prefix {
+            TYPE_INT => ClarityValue::Int(i128::from_be_bytes(self.read_16()?)),
+            TYPE_UINT => ClarityValue::UInt(u128::from_be_bytes(self.read_16()?)),
+            TYPE_BUFFER => {
+                let len = self.read_u32()? as usize;
+                ClarityValue::Buffer(self.take(len)?.to_vec())
+            }
+            TYPE_TRUE => ClarityValue::Bool(true),
+            TYPE_FALSE => ClarityValue::Bool(false),
+            TYPE_STANDARD_PRINCIPAL => {
+                let version = self.read_u8()?;
+                let hash160 = self.read_hash160()?;
+                ClarityValue::Principal(PrincipalData::Standard { version, hash160 })
+            }
+            TYPE_CONTRACT_PRINCIPAL => {
+                let version = self.read_u8()?;
+                let hash160 = self.read_hash160()?;
+                let name = self.read_name()?;
+                ClarityValue::Principal(PrincipalData::Contract { version, hash160, name })
+            }
+            TYPE_RESPONSE_OK => ClarityValue::ok(self.read_value(depth + 1)?),
+            TYPE_RESPONSE_ERR => ClarityValue::err(self.read_value(depth + 1)?),
+            TYPE_NONE => ClarityValue::none(),
+            TYPE_SOME => ClarityValue::some(self.read_value(depth + 1)?),
+            TYPE_LIST => {
+                let len = self.read_u32()? as usize;
+                let mut items = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    items.push(self.read_value(depth + 1)?);
+                }
+                ClarityValue::List(items)
+            }
+            TYPE_TUPLE => {
+                let len = self.read_u32()? as usize;
+                let mut fields = BTreeMap::new();
+                for _ in 0..len {
+                    let name = self.read_name()?;
+                    let value = self.read_value(depth + 1)?;
+                    if fields.insert(name.clone(), value).is_some() {
+                        return Err(format!("Duplicate tuple field '{}'", name));
+                    }
+                }
+                ClarityValue::Tuple(fields)
+            }
+            TYPE_STRING_ASCII => {
+                let len = self.read_u32()? as usize;
+                let bytes = self.take(len)?;
+                if !bytes.is_ascii() {
+                    return Err("string-ascii contains non-ASCII bytes".to_string());
+                }
+                ClarityValue::StringAscii(String::from_utf8_lossy(bytes).into_owned())
+            }
+            TYPE_STRING_UTF8 => {
+                let len = self.read_u32()? as usize;
+                let bytes = self.take(len)?;
+                let text = String::from_utf8(bytes.to_vec())
+                    .map_err(|_| "string-utf8 is not valid UTF-8".to_string())?;
+                ClarityValue::StringUtf8(text)
+            }
+            other => return Err(format!("Unknown Clarity type prefix 0x{:02x}", other)),
+        };
+        Ok(value)
+    }
+}
+
+fn c32_encode(input: &[u8]) -> String {
+    let mut result = Vec::new();
+    let mut carry: u8 = 0;
+    let mut carry_bits: u8 = 0;
+
+    for byte in input.iter().rev() {
+        let low_bits_to_take = 5 - carry_bits;
+        let low_bits = byte & ((1 << low_bits_to_take) - 1);
+        result.push(C32_ALPHABET[((low_bits << carry_bits) + carry) as usize]);
+        carry_bits = (8 + carry_bits) - 5;
+        carry = byte >> (8 - carry_bits);
+
+        if carry_bits >= 5 {
+            result.push(C32_ALPHABET[(carry & 0x1f) as usize]);
+            carry_bits -= 5;
+            carry >>= 5;
+        }
+    }
+    if carry_bits > 0 {
+        result.push(C32_ALPHABET[carry as usize]);
+    }
+
+    // Strip zero digits produced by the bit packing, then re-add one per leading zero byte
+    while result.last() == Some(&C32_ALPHABET[0]) {
+        result.pop();
+    }
+    for byte in input {
+        if *byte != 0 {
+            break;
+        }
+        result.push(C32_ALPHABET[0]);
+    }
+
+    result.reverse();
+    String::from_utf8(result).unwrap_or_default()
+}
+
+fn c32_decode(input: &str) -> Result<Vec<u8>, String> {
+    let mut digits = Vec::with_capacity(input.len());
+    for c in input.chars() {
+        // c32 is case-insensitive and tolerates the usual look-alike characters
+        let normalized = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'L' | 'I' => '1',
+            other => other,
+        };
+        let digit = C32_ALPHABET
+            .iter()
+            .position(|a| *a as char == normalized)
+            .ok_or_else(|| format!("Invalid c32 character '{}'", c))?;
+        digits.push(digit as u16);
+    }
+
+    let leading_zeros = digits.iter().take_while(|d| **d == 0).count();
+    let mut result = Vec::new();
+    let mut carry: u16 = 0;
+    let mut carry_bits = 0;
+
+    for digit in digits.iter().rev() {
+        carry += digit << carry_bits;
+        carry_bits += 5;
+        if carry_bits >= 8 {
+            result.push((carry & 0xff) as u8);
+            carry_bits -= 8;
+            carry >>= 8;
+        }
+    }
+    if carry_bits > 0 {
+        result.push(carry as u8);
+    }
+
+    while result.last() == Some(&0) {
+        result.pop();
+    }
+    result.extend(std::iter::repeat_n(0, leading_zeros));
+    result.reverse();
+    Ok(result)
+}
+
+fn c32_checksum(version: u8, data: &[u8]) -> [u8; 4] {
+    let mut first = Sha256::new();
+    first.update([version]);
+    first.update(data);
+    let second = Sha256::digest(first.finalize());
+    [second[0], second[1], second[2], second[3]]
+}
+
+/// Encodes a version byte and hash160 as an `S...` address
+pub fn c32_address_encode(version: u8, hash160: &[u8; 20]) -> String {
+    let mut payload = hash160.to_vec();
+    payload.extend_from_slice(&c32_checksum(version, hash160));
+    format!(
+        "S{}{}",
+        C32_ALPHABET[(version & 0x1f) as usize] as char,
+        c32_encode(&payload)
+    )
+}
+
+/// Decodes an `S...` address into its version byte and hash160, verifying the checksum
+pub fn c32_address_decode(address: &str) -> Result<(u8, [u8; 20]), String> {
+    if !address.is_ascii() || address.len() < 3 || !address.starts_with('S') {
+        return Err(format!("Invalid Stacks address: {}", address));
+    }
+
+    let version_char = &address[1..2];
+    let version = c32_decode(version_char)?.first().copied().unwrap_or(0);
+    let decoded = c32_decode(&address[2..])?;
+    if decoded.len() != 24 {
+        return Err(format!("Invalid Stacks address length: {}", address));
+    }
+
+    let (data, checksum) = decoded.split_at(20);
+    if c32_checksum(version, data) != checksum {
+        return Err(format!("Invalid Stacks address checksum: {}", address));
+    }
+
+    let mut hash160 = [0u8; 20];
+    hash160.copy_from_slice(data);
+    Ok((version, hash160))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c32_address_round_trip() {
+        // Well-known burn addresses with an all-zero hash160
+        let (version, hash160) = c32_address_decode("SP000000000000000000002Q6VF78").unwrap();
+        assert_eq!(version, 22);
+        assert_eq!(hash160, [0u8; 20]);
+        assert_eq!(c32_address_encode(22, &hash160), "SP000000000000000000002Q6VF78");
+        assert_eq!(c32_address_encode(26, &hash160), "ST000000000000000000002AMW42H");
+
+        let hash160 = [0xa4u8; 20];
+        let address = c32_address_encode(26, &hash160);
+        assert_eq!(c32_address_decode(&address).unwrap(), (26, hash160));
+
+        assert!(c32_address_decode("SP000000000000000000002Q6VF79").is_err());
+    }
+
+    #[test]
+    fn test_consensus_encoding_matches_known_vectors() {
+        assert_eq!(
+            ClarityValue::UInt(1).to_hex(),
+            "0x0100000000000000000000000000000001"
+        );
+        assert_eq!(
+            ClarityValue::Int(-1).to_hex(),
+            "0x00ffffffffffffffffffffffffffffffff"
+        );
+        assert_eq!(ClarityValue::Bool(true).to_hex(), "0x03");
+        assert_eq!(ClarityValue::none().to_hex(), "0x09");
+        assert_eq!(
+            ClarityValue::ok(ClarityValue::Bool(true)).to_hex(),
+            "0x0703"
+        );
+        assert_eq!(
+            ClarityValue::StringAscii("hi".to_string()).to_hex(),
+            "0x0d000000026869"
+        );
+    }
+
+    #[test]
+    fn test_round_trip_nested_values() {
+        let value = ClarityValue::ok(ClarityValue::tuple([
+            ("amount", ClarityValue::UInt(1_000)),
+            ("memo", ClarityValue::some(ClarityValue::StringUtf8("zBTCZ ✓".to_string()))),
+            ("root", ClarityValue::Buffer(vec![0xab; 32])),
+            (
+                "owners",
+                ClarityValue::List(vec![
+                    ClarityValue::principal("SP000000000000000000002Q6VF78").unwrap(),
+                    ClarityValue::principal("SP000000000000000000002Q6VF78.zbtcz").unwrap(),
+                ]),
+            ),
+            ("delta", ClarityValue::err(ClarityValue::Int(-42))),
+        ]));
+
+        let decoded = ClarityValue::from_hex(&value.to_hex()).unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(decoded.expect_ok().unwrap().get("amount").unwrap().expect_u64().unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_malformed_input() {
+        assert!(ClarityValue::from_hex("0x01000000").is_err()); // Truncated uint
+        assert!(ClarityValue::from_hex("0x0304").is_err()); // Trailing bytes
+        assert!(ClarityValue::from_hex("0x0f").is_err()); // Unknown prefix
+        assert!(ClarityValue::from_hex("0x0d00000001ff").is_err()); // Non-ASCII string-ascii
+    }
+}
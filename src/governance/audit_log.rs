@@ -0,0 +1,285 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// `prev_hash` of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Actor recorded for steps the engine takes on its own, such as finalizing or expiring
+pub const SYSTEM_ACTOR: &str = "governance";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Submitted,
+    Activated,
+    Voted,
+    Finalized,
+    Executed,
+    CancelVoted,
+    Vetoed,
+    Cancelled,
+    Expired,
+}
+
+/// A parameter as it was before and after an executed proposal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterChange {
+    pub param: String,
+    pub before: u64,
+    pub after: u64,
+}
+
+/// One governance event, chained to the previous entry by `prev_hash`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub timestamp: DateTime<Utc>,
+    pub proposal_id: u64,
+    pub actor: String,
+    pub action: AuditAction,
+    pub detail: String,
+    pub changes: Vec<ParameterChange>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hex SHA-256 over the JSON encoding of every field but `hash`, previous hash included.
+    /// JSON escapes each string, so no two distinct entries encode the same way.
+    pub fn compute_hash(&self) -> String {
+        let mut fields = serde_json::to_value(self).expect("Audit entries always serialize");
+        if let Some(fields) = fields.as_object_mut() {
+            fields.remove("hash");
+        }
+        hex::encode(Sha256::digest(fields.to_string().as_bytes()))
+    }
+}
+
+/// Append-only, hash-chained record of governance activity, stored as JSON Lines. Editing
+/// or removing an entry breaks the chain from that point on, which `verify` reports.
+pub struct GovernanceAuditLog {
+    entries: Mutex<Vec<AuditEntry>>,
+    pub storage_path: PathBuf, // JSON Lines file entries are appended to
+}
+
+impl GovernanceAuditLog {
+    /// Opens the log at `storage_path`, refusing one that fails to parse or verify since
+    /// appending to it would fork the chain
+    pub fn new(storage_path: PathBuf) -> Result<Self, String> {
+        let entries = match fs::read_to_string(&storage_path) {
+            Ok(content) => {
+                let entries = Self::parse(&content)?;
+                Self::verify_chain(&entries)?;
+                entries
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(_) => return Err("Failed to read audit log".to_string()),
+        };
+        Ok(Self {
+            entries: Mutex::new(entries),
+            storage_path,
+        })
+    }
+
+    fn parse(content: &str) -> Result<Vec<AuditEntry>, String> {
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+            .collect()
+    }
+
+    /// Appends an entry to the chain and to disk
+    pub fn record(
+        &self,
+        proposal_id: u64,
+        actor: &str,
+        action: AuditAction,
+        detail: String,
+        changes: Vec<ParameterChange>,
+    ) -> Result<AuditEntry, String> {
+        let mut entries = self.entries.lock().map_err(|_| "Mutex lock failed")?;
+        let (sequence, prev_hash) = match entries.last() {
+            Some(last) => (last.sequence + 1, last.hash.clone()),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        let mut entry = AuditEntry {
+            sequence,
+            timestamp: Utc::now(),
+            proposal_id,
+            actor: actor.to_string(),
+            action,
+            detail,
+            changes,
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        let line = serde_json::to_string(&entry).map_err(|_| "Serialization failed")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.storage_path)
+            .map_err(|_| "Failed to open audit log")?;
+        writeln!(file, "{}", line).map_err(|_| "Failed to write to disk")?;
+        entries.push(entry.clone());
+        Ok(entry)
+    }
+
+    /// Entries in order, optionally only those for `proposal_id` and/or by `actor`
+    pub fn query(&self, proposal_id: Option<u64>, actor: Option<&str>) -> Result<Vec<AuditEntry>, String> {
+        let entries = self.entries.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(entries
+            .iter()
+            .filter(|entry| proposal_id.is_none_or(|id| entry.proposal_id == id))
+            .filter(|entry| actor.is_none_or(|actor| entry.actor == actor))
+            .cloned()
+            .collect())
+    }
+
+    /// Re-reads the log from disk and checks every link of the chain. Returns the number of
+    /// entries verified.
+    pub fn verify(&self) -> Result<usize, String> {
+        let content = match fs::read_to_string(&self.storage_path) {
+            Ok(content) => content,
+            Err(_) if self.entries.lock().map_err(|_| "Mutex lock failed")?.is_empty() => return Ok(0),
+            Err(_) => return Err("Audit log file is missing".to_string()),
+        };
+        let entries = Self::parse(&content)?;
+        Self::verify_chain(&entries)?;
+        let recorded = self.entries.lock().map_err(|_| "Mutex lock failed")?.len();
+        if entries.len() < recorded {
+            return Err(format!("{} entries are missing from the end of the log", recorded - entries.len()));
+        }
+        Ok(entries.len())
+    }
+
+    fn verify_chain(entries: &[AuditEntry]) -> Result<(), String> {
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for (i, entry) in entries.iter().enumerate() {
+            if entry.sequence != i as u64 || entry.prev_hash != prev_hash {
+                return Err(format!("Entry {} does not follow the entry before it", entry.sequence));
+            }
+            if entry.compute_hash() != entry.hash {
+                return Err(format!("Entry {} has been altered", entry.sequence));
+            }
+            prev_hash = entry.hash.clone();
+        }
+        Ok(())
+    }
+}
+
+/// One JSON object per line
+pub fn to_jsonl(entries: &[AuditEntry]) -> Result<String, String> {
+    let mut output = String::new();
+    for entry in entries {
+        output.push_str(&serde_json::to_string(entry).map_err(|_| "Serialization failed")?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// CSV with a header row; parameter changes are written as `param:before->after` joined by `;`
+pub fn to_csv(entries: &[AuditEntry]) -> String {
+    let mut output = String::from("sequence,timestamp,proposal_id,actor,action,detail,changes,prev_hash,hash\n");
+    for entry in entries {
+        let changes: Vec<String> = entry
+            .changes
+            .iter()
+            .map(|change| format!("{}:{}->{}", change.param, change.before, change.after))
+            .collect();
+        let action = serde_json::to_value(entry.action)
+            .ok()
+            .and_then(|value| value.as_str().map(String::from))
+            .unwrap_or_default();
+        let fields = [
+            entry.sequence.to_string(),
+            entry.timestamp.to_rfc3339(),
+            entry.proposal_id.to_string(),
+            entry.actor.clone(),
+            action,
+            entry.detail.clone(),
+            changes.join(";"),
+            entry.prev_hash.clone(),
+            entry.hash.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        output.push_str(&row.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit_log(name: &str) -> GovernanceAuditLog {
+        let path = std::env::temp_dir().join(format!("zook_audit_{}_{}.jsonl", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        GovernanceAuditLog::new(path).unwrap()
+    }
+
+    #[test]
+    fn test_chain_survives_restart_and_detects_tampering() {
+        let log = audit_log("chain");
+        log.record(1, "alice", AuditAction::Submitted, "Raise rewards".into(), Vec::new()).unwrap();
+        log.record(1, "full", AuditAction::Voted, "yes".into(), Vec::new()).unwrap();
+        let change = ParameterChange { param: "reward_rate".into(), before: 100, after: 150 };
+        log.record(1, SYSTEM_ACTOR, AuditAction::Executed, "executed".into(), vec![change]).unwrap();
+        log.record(2, "alice", AuditAction::Submitted, "Say \"hi\", twice".into(), Vec::new()).unwrap();
+        assert_eq!(log.verify().unwrap(), 4);
+
+        // Appending continues the chain after a restart
+        let reopened = GovernanceAuditLog::new(log.storage_path.clone()).unwrap();
+        reopened.record(2, "alice", AuditAction::Cancelled, "withdrawn".into(), Vec::new()).unwrap();
+        assert_eq!(reopened.verify().unwrap(), 5);
+        assert_eq!(reopened.query(Some(1), None).unwrap().len(), 3);
+        assert_eq!(reopened.query(Some(2), Some("alice")).unwrap().len(), 2);
+
+        let csv = to_csv(&reopened.query(None, None).unwrap());
+        assert!(csv.contains("reward_rate:100->150"));
+        assert!(csv.contains("\"Say \"\"hi\"\", twice\""));
+        assert_eq!(to_jsonl(&reopened.query(None, Some("full")).unwrap()).unwrap().lines().count(), 1);
+
+        let content = fs::read_to_string(&log.storage_path).unwrap();
+        fs::write(&log.storage_path, content.replace("\"after\":150", "\"after\":999")).unwrap();
+        assert_eq!(reopened.verify().unwrap_err(), "Entry 2 has been altered");
+
+        let truncated: Vec<&str> = content.lines().skip(1).collect();
+        fs::write(&log.storage_path, truncated.join("\n")).unwrap();
+        assert!(reopened.verify().unwrap_err().contains("does not follow"));
+        // A damaged log is refused rather than restarted at sequence 0
+        assert!(GovernanceAuditLog::new(log.storage_path.clone()).err().unwrap().contains("does not follow"));
+        fs::write(&log.storage_path, "not json\n").unwrap();
+        assert!(GovernanceAuditLog::new(log.storage_path.clone()).err().unwrap().starts_with("line 1"));
+    }
+
+    #[test]
+    fn test_hash_separates_fields_unambiguously() {
+        let log = audit_log("fields");
+        let change = |param: &str, before, after| ParameterChange { param: param.into(), before, after };
+        let two = log
+            .record(1, SYSTEM_ACTOR, AuditAction::Executed, "executed".into(), vec![change("p", 1, 2), change("q", 3, 4)])
+            .unwrap();
+        // Joined with separators these two change lists would both read "p:1:2;q:3:4"
+        let mut one = two.clone();
+        one.changes = vec![change("p:1:2;q", 3, 4)];
+        assert_ne!(one.compute_hash(), two.hash);
+    }
+}
@@ -0,0 +1,292 @@
+use std::sync::Arc;
+use crate::clarity::bindings::proposal_voting;
+use crate::clarity::receipt::TransactionReceipt;
+use crate::clarity::value::ClarityValue;
+use crate::governance::proposal_engine::{ContractStep, GovernanceProposal, ProposalEngine};
+use crate::interaction::ClarityInteractor;
+
+/// Parameter name as the contract spells it (`minimum_stake` -> `minimum-stake`)
+pub fn contract_param_name(param: &str) -> String {
+    param.replace('_', "-")
+}
+
+/// Parameter name as the registry spells it (`minimum-stake` -> `minimum_stake`)
+pub fn engine_param_name(param: &str) -> String {
+    param.replace('-', "_")
+}
+
+/// Feeds a print from the governance contract into the proposal engine
+pub fn apply_governance_print(governance: &ProposalEngine, value: &ClarityValue) -> Result<(), String> {
+    let Ok(event) = value.get("event").and_then(ClarityValue::expect_string) else {
+        return Ok(());
+    };
+    let param = engine_param_name(value.get("param-name")?.expect_string()?);
+    let amount = value.get("value")?.expect_u64()?;
+    match event {
+        "proposal-created" => {
+            let proposer = value.get("proposer")?.expect_principal()?.to_string();
+            governance.record_onchain_proposal(&param, amount, &proposer)?;
+        }
+        "vote-cast" => {
+            let voter = value.get("voter")?.expect_principal()?.to_string();
+            let vote_for = value.get("vote-for")?.expect_bool()?;
+            if governance.record_onchain_vote(&param, amount, &voter, vote_for)?.is_none() {
+                println!("No mirrored proposal matches on-chain vote for {} = {}", param, amount);
+            }
+        }
+        "proposal-finalized" => {
+            let approved = value.get("approved")?.expect_bool()?;
+            if governance.record_onchain_outcome(&param, amount, approved)?.is_none() {
+                println!("No open proposal matches on-chain outcome for {} = {}", param, amount);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Carries proposals from Rust governance through the proposal_voting contract: opens them
+/// there, votes the Rust outcome once voting closes and finalizes them, which applies
+/// approved changes on-chain. Votes and outcomes from the contract come back through the
+/// Stacks event observer.
+pub struct GovernanceContractSync {
+    pub governance: Arc<ProposalEngine>,
+    pub clarity: Arc<ClarityInteractor>,
+}
+
+impl GovernanceContractSync {
+    pub fn new(governance: Arc<ProposalEngine>, clarity: Arc<ClarityInteractor>) -> Self {
+        Self { governance, clarity }
+    }
+
+    /// Settles outstanding contract calls, then submits the next call for each proposal.
+    /// The contract holds one proposal at a time, so a new one is only mirrored once the
+    /// previous one has been finalized.
+    pub async fn sync(&self) -> Result<(), String> {
+        self.refresh_pending().await?;
+
+        for proposal in self.governance.awaiting_onchain_outcome()? {
+            let approved = proposal.approved().unwrap_or(false);
+            let (step, result) = if proposal.onchain.step < ContractStep::Voted {
+                (ContractStep::Voted, self.clarity.vote_on_proposal(approved).await)
+            } else {
+                (ContractStep::Finalized, self.clarity.finalize_proposal().await)
+            };
+            self.submitted(&proposal, step, result)?;
+        }
+
+        if let Some(proposal) = self.governance.next_to_mirror()? {
+            let action = proposal.single_action().ok_or("Only single-change proposals can be mirrored")?;
+            let result = self
+                .clarity
+                .propose_parameter_change(&contract_param_name(&action.param), action.value.into())
+                .await;
+            self.submitted(&proposal, ContractStep::Proposed, result)?;
+        }
+        Ok(())
+    }
+
+    fn submitted(
+        &self,
+        proposal: &GovernanceProposal,
+        step: ContractStep,
+        result: Result<TransactionReceipt, String>,
+    ) -> Result<(), String> {
+        match result {
+            Ok(receipt) => {
+                println!("Proposal {}: submitted {:?} call {}", proposal.proposal_id, step, receipt.txid);
+                self.governance
+                    .update_onchain_status(proposal.proposal_id, |onchain| onchain.pending = Some((step, receipt.txid)))
+            }
+            Err(e) => {
+                eprintln!("Proposal {}: {:?} call failed: {}", proposal.proposal_id, step, e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Records confirmed calls; failed ones are cleared so the next sync retries them
+    async fn refresh_pending(&self) -> Result<(), String> {
+        for proposal in self.governance.pending_contract_calls()? {
+            let Some((step, txid)) = proposal.onchain.pending.clone() else {
+                continue;
+            };
+            let pending = TransactionReceipt::pending(txid.clone(), proposal_voting::SOURCE);
+            let receipt = match self.clarity.refresh_receipt(&pending).await {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    eprintln!("Failed to refresh receipt {}: {}", txid, e);
+                    continue;
+                }
+            };
+            if !receipt.status.is_final() {
+                continue;
+            }
+
+            let outcome = receipt.outcome().map(|_| ()).map_err(|e| e.to_string());
+            if let Err(e) = &outcome {
+                eprintln!("Proposal {}: {:?} call {} failed: {}", proposal.proposal_id, step, txid, e);
+            }
+            self.governance.update_onchain_status(proposal.proposal_id, |onchain| {
+                onchain.pending = None;
+                if outcome.is_ok() {
+                    onchain.step = step;
+                }
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::bindings::ContractCall;
+    use crate::clarity::receipt::ContractEvent;
+    use crate::governance::parameters::ParameterRegistry;
+    use crate::governance::proposal_engine::{ProposalAction, ProposalState, ProposalTally, VoteChoice, VotingRules};
+    use crate::governance::validator_policies::GovernanceValidatorPolicies;
+    use crate::validator::node_registration::ValidatorRegistry;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const NODE: &str = "SP000000000000000000002Q6VF78";
+    const VALIDATOR: &str = "ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM";
+    const HOLDER: &str = "ST000000000000000000002AMW42H";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zook_contract_sync_{}_{}", std::process::id(), name))
+    }
+
+    /// Sync against an embedded governance contract, with `VALIDATOR` as the only validator,
+    /// one-second votes and no timelock
+    fn contract_sync(name: &str) -> GovernanceContractSync {
+        let validators = temp_path(&format!("{}_validators.json", name));
+        let proposals = temp_path(&format!("{}_proposals.json", name));
+        let _ = fs::remove_file(&validators);
+        let _ = fs::remove_file(&proposals);
+
+        let registry = ValidatorRegistry::new(1_000, validators);
+        registry.register_node(VALIDATOR.to_string(), 1_000_000).unwrap();
+        let policies = Arc::new(GovernanceValidatorPolicies::new(1_000_000, 80, 70));
+        let governance = ProposalEngine::new(
+            Arc::new(ParameterRegistry::new().register(policies.clone())),
+            Arc::new(registry),
+            policies,
+            proposals,
+        )
+        .unwrap()
+        .with_voting_rules(VotingRules {
+            voting_period_secs: 1,
+            timelock_delay_secs: 0,
+            ..VotingRules::default()
+        });
+        let clarity = ClarityInteractor::embedded(NODE).unwrap();
+        GovernanceContractSync::new(Arc::new(governance), Arc::new(clarity))
+    }
+
+    /// Delivers the prints of an embedded transaction the way the event observer would
+    fn deliver(sync: &GovernanceContractSync, txid: &str) {
+        let receipt = {
+            let vm = sync.clarity.embedded.as_ref().unwrap().lock().unwrap();
+            vm.receipt(txid).cloned().unwrap()
+        };
+        for event in receipt.events {
+            if let ContractEvent::Print { value, .. } = event {
+                apply_governance_print(&sync.governance, &value).unwrap();
+            }
+        }
+    }
+
+    /// Calls the governance contract as someone other than this node
+    fn call(sync: &GovernanceContractSync, sender: &str, call: ContractCall) {
+        let receipt = {
+            let mut vm = sync.clarity.embedded.as_ref().unwrap().lock().unwrap();
+            vm.call_public(sender, &sync.clarity.governance_address, &call).unwrap()
+        };
+        deliver(sync, &receipt.txid);
+    }
+
+    /// Runs a sync round and delivers the call it submitted for `proposal_id`, if any
+    async fn sync_round(sync: &GovernanceContractSync, proposal_id: u64) {
+        sync.sync().await.unwrap();
+        if let Some((_, txid)) = sync.governance.get_proposal(proposal_id).unwrap().onchain.pending {
+            deliver(sync, &txid);
+        }
+    }
+
+    async fn finalize(sync: &GovernanceContractSync, proposal_id: u64) -> ProposalState {
+        tokio::time::sleep(std::time::Duration::from_millis(1_100)).await;
+        sync.governance.finalize_proposal(proposal_id).unwrap()
+    }
+
+    fn submit(sync: &GovernanceContractSync, value: u64) -> u64 {
+        let id = sync
+            .governance
+            .submit_proposal("alice".into(), "Raise stake".into(), vec![ProposalAction::new("minimum_stake", value)])
+            .unwrap();
+        sync.governance.activate_proposal(id).unwrap();
+        id
+    }
+
+    fn onchain_minimum_stake(sync: &GovernanceContractSync) -> ClarityValue {
+        let vm = sync.clarity.embedded.as_ref().unwrap().lock().unwrap();
+        vm.data_var(&sync.clarity.governance_address, "minimum-stake").cloned().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_approved_change_lands_on_both_sides() {
+        let sync = contract_sync("approved");
+        let id = submit(&sync, 2_000_000);
+
+        sync_round(&sync, id).await;
+        let onchain = sync.governance.get_proposal(id).unwrap().onchain;
+        assert!(onchain.mirrored);
+        assert_eq!(onchain.pending.map(|(step, _)| step), Some(ContractStep::Proposed));
+
+        // The validator votes in the contract; the vote is weighted on the Rust side
+        call(&sync, VALIDATOR, proposal_voting::vote(true));
+        let proposal = sync.governance.get_proposal(id).unwrap();
+        assert_eq!(proposal.onchain.votes_for, 1);
+        assert_eq!(proposal.tally, ProposalTally { yes: 100, no: 0, abstain: 0 });
+        assert_eq!(finalize(&sync, id).await, ProposalState::Queued);
+
+        sync_round(&sync, id).await; // Votes the Rust outcome
+        sync_round(&sync, id).await; // Finalizes
+        let onchain = sync.governance.get_proposal(id).unwrap().onchain;
+        assert_eq!((onchain.votes_for, onchain.approved, onchain.conflict), (2, Some(true), None));
+        assert_eq!(onchain_minimum_stake(&sync), ClarityValue::UInt(2_000_000));
+
+        sync.governance.execute_proposal(id).unwrap();
+        assert_eq!(sync.governance.parameters.get("minimum_stake").unwrap().value, 2_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_disagreement_is_flagged_and_onchain_proposals_flow_back() {
+        let sync = contract_sync("conflict");
+        let id = submit(&sync, 3_000_000);
+        sync_round(&sync, id).await;
+
+        call(&sync, HOLDER, proposal_voting::vote(false));
+        call(&sync, HOLDER, proposal_voting::vote(false));
+        sync.governance.cast_vote(id, VALIDATOR, VoteChoice::Yes).unwrap();
+        assert_eq!(finalize(&sync, id).await, ProposalState::Queued);
+
+        sync_round(&sync, id).await;
+        sync_round(&sync, id).await;
+        let proposal = sync.governance.get_proposal(id).unwrap();
+        assert_eq!(proposal.onchain.approved, Some(false));
+        assert!(proposal.onchain.conflict.is_some());
+        assert!(sync.governance.execute_proposal(id).unwrap_err().contains("Conflicts"));
+        assert_eq!(sync.governance.list_conflicts().unwrap().len(), 1);
+        assert_eq!(onchain_minimum_stake(&sync), ClarityValue::UInt(1_000));
+
+        // With the contract free again, a proposal made there opens one in Rust
+        call(&sync, HOLDER, proposal_voting::propose("activity-threshold".to_string(), 90));
+        let mirrored = sync.governance.list_proposals(Some(ProposalState::Active)).unwrap();
+        assert_eq!(mirrored.len(), 1);
+        assert_eq!(mirrored[0].actions, vec![ProposalAction::new("activity_threshold", 90)]);
+        assert_eq!(mirrored[0].proposer, HOLDER);
+        assert!(mirrored[0].onchain.mirrored);
+    }
+}
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::governance::token::GovernanceToken;
+use crate::validator::node_registration::ValidatorRegistry;
+
+/// A gBTCZ holder's delegation of voting power to a validator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delegation {
+    pub delegator: String,
+    pub validator: String,
+    pub delegated_at: DateTime<Utc>,
+}
+
+/// Delegated gBTCZ backing a validator
+#[derive(Debug, Clone, Serialize)]
+pub struct DelegatedPower {
+    pub validator: String,
+    pub delegators: HashMap<String, u64>, // Delegator -> gBTCZ balance
+    pub total: u64,
+}
+
+/// Delegations from gBTCZ holders to registered validators. Delegated power is read from
+/// balances at a token snapshot, or current balances when none is given, so it follows
+/// transfers without re-delegating.
+pub struct DelegationRegistry {
+    pub delegations: Arc<Mutex<HashMap<String, Delegation>>>, // Delegator -> delegation
+    pub token: Arc<Mutex<GovernanceToken>>,
+    pub validator_registry: Arc<ValidatorRegistry>,
+    pub storage_path: PathBuf,
+}
+
+impl DelegationRegistry {
+    /// Fails if stored delegations exist but cannot be read
+    pub fn new(
+        token: Arc<Mutex<GovernanceToken>>,
+        validator_registry: Arc<ValidatorRegistry>,
+        storage_path: PathBuf,
+    ) -> Result<Self, String> {
+        let delegations = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable delegations in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+
+        Ok(Self {
+            delegations: Arc::new(Mutex::new(delegations)),
+            token,
+            validator_registry,
+            storage_path,
+        })
+    }
+
+    fn persist(&self, delegations: &HashMap<String, Delegation>) -> Result<(), String> {
+        let serialized = serde_json::to_string(delegations).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+
+    /// Delegates `delegator`'s gBTCZ to `validator`, replacing any existing delegation.
+    /// Returns the validator previously delegated to.
+    pub fn delegate(&self, delegator: &str, validator: &str) -> Result<Option<String>, String> {
+        if !self.validator_registry.get_node(validator)?.active {
+            return Err("Validator is not active".to_string());
+        }
+        if self.balance_at(delegator, None)? == 0 {
+            return Err("Delegator holds no gBTCZ".to_string());
+        }
+
+        let mut delegations = self.delegations.lock().map_err(|_| "Mutex lock failed")?;
+        let previous = delegations.insert(
+            delegator.to_string(),
+            Delegation {
+                delegator: delegator.to_string(),
+                validator: validator.to_string(),
+                delegated_at: Utc::now(),
+            },
+        );
+        self.persist(&delegations)?;
+        println!("{} delegated gBTCZ voting power to {}", delegator, validator);
+        Ok(previous.map(|delegation| delegation.validator))
+    }
+
+    pub fn undelegate(&self, delegator: &str) -> Result<Delegation, String> {
+        let mut delegations = self.delegations.lock().map_err(|_| "Mutex lock failed")?;
+        let delegation = delegations.remove(delegator).ok_or("No delegation found")?;
+        self.persist(&delegations)?;
+        println!("{} undelegated from {}", delegator, delegation.validator);
+        Ok(delegation)
+    }
+
+    pub fn get_delegation(&self, delegator: &str) -> Result<Option<Delegation>, String> {
+        let delegations = self.delegations.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(delegations.get(delegator).cloned())
+    }
+
+    pub fn list_delegations(&self) -> Result<Vec<Delegation>, String> {
+        let delegations = self.delegations.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(delegations.values().cloned().collect())
+    }
+
+    /// Takes a gBTCZ balance snapshot for later `balance_at` reads
+    pub fn snapshot(&self) -> Result<u64, String> {
+        let mut token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+        token.snapshot()
+    }
+
+    /// Balance at `snapshot_id`, or the current balance without one
+    pub fn balance_at(&self, account: &str, snapshot_id: Option<u64>) -> Result<u64, String> {
+        let token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+        Self::read_balance(&token, account, snapshot_id)
+    }
+
+    fn read_balance(token: &GovernanceToken, account: &str, snapshot_id: Option<u64>) -> Result<u64, String> {
+        match snapshot_id {
+            Some(snapshot_id) => token.balance_at(account, snapshot_id),
+            None => Ok(token.get_balance(account)),
+        }
+    }
+
+    /// gBTCZ delegated to `validator`, leaving out delegators in `excluded`
+    pub fn delegated_power(
+        &self,
+        validator: &str,
+        excluded: &HashSet<String>,
+        snapshot_id: Option<u64>,
+    ) -> Result<DelegatedPower, String> {
+        let delegations = self.delegations.lock().map_err(|_| "Mutex lock failed")?;
+        let token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+        let mut delegators = HashMap::new();
+        for d in delegations
+            .values()
+            .filter(|d| d.validator == validator && !excluded.contains(&d.delegator))
+        {
+            delegators.insert(d.delegator.clone(), Self::read_balance(&token, &d.delegator, snapshot_id)?);
+        }
+
+        Ok(DelegatedPower {
+            validator: validator.to_string(),
+            total: delegators.values().sum(),
+            delegators,
+        })
+    }
+
+    /// gBTCZ delegated to validators that are currently active, each delegator's balance
+    /// weighed by `weight`
+    pub fn total_delegated_power(&self, snapshot_id: Option<u64>, weight: impl Fn(u64) -> u64) -> Result<u64, String> {
+        let delegations = self.delegations.lock().map_err(|_| "Mutex lock failed")?;
+        let token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+        let nodes = self.validator_registry.nodes.lock().map_err(|_| "Mutex lock failed")?;
+        delegations
+            .values()
+            .filter(|d| nodes.get(&d.validator).is_some_and(|node| node.active))
+            .map(|d| Self::read_balance(&token, &d.delegator, snapshot_id).map(&weight))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zook_delegation_{}_{}", std::process::id(), name))
+    }
+
+    fn registry(name: &str) -> DelegationRegistry {
+        let validators = temp_path(&format!("{}_validators.json", name));
+        let delegations = temp_path(&format!("{}_delegations.json", name));
+        let _ = fs::remove_file(&validators);
+        let _ = fs::remove_file(&delegations);
+
+        let validator_registry = ValidatorRegistry::new(1_000, validators);
+        validator_registry.register_node("v1".to_string(), 1_000).unwrap();
+        validator_registry.register_node("v2".to_string(), 1_000).unwrap();
+        let mut token = GovernanceToken::new("gBTCZ", 0);
+        token.mint("alice", 300).unwrap();
+        token.mint("bob", 200).unwrap();
+        DelegationRegistry::new(Arc::new(Mutex::new(token)), Arc::new(validator_registry), delegations).unwrap()
+    }
+
+    #[test]
+    fn test_delegate_redelegate_and_undelegate() {
+        let registry = registry("lifecycle");
+        assert_eq!(registry.delegate("alice", "v1").unwrap(), None);
+        registry.delegate("bob", "v1").unwrap();
+        assert!(registry.delegate("carol", "v1").is_err()); // No balance
+        assert!(registry.delegate("alice", "unknown").is_err());
+        assert_eq!(registry.delegated_power("v1", &HashSet::new(), None).unwrap().total, 500);
+
+        assert_eq!(registry.delegate("alice", "v2").unwrap(), Some("v1".to_string()));
+        assert_eq!(registry.delegated_power("v1", &HashSet::new(), None).unwrap().total, 200);
+        assert_eq!(registry.delegated_power("v2", &HashSet::new(), None).unwrap().total, 300);
+
+        let excluded = HashSet::from(["bob".to_string()]);
+        assert_eq!(registry.delegated_power("v1", &excluded, None).unwrap().total, 0);
+
+        registry.undelegate("bob").unwrap();
+        assert!(registry.undelegate("bob").is_err());
+        assert_eq!(registry.total_delegated_power(None, |balance| balance).unwrap(), 300);
+
+        // Delegations survive a restart
+        let reopened = DelegationRegistry::new(
+            registry.token.clone(),
+            registry.validator_registry.clone(),
+            registry.storage_path.clone(),
+        )
+        .unwrap();
+        assert_eq!(reopened.get_delegation("alice").unwrap().unwrap().validator, "v2");
+
+        // Damaged delegations are refused rather than dropped
+        fs::write(&registry.storage_path, "{\"alice\":").unwrap();
+        let damaged = DelegationRegistry::new(
+            registry.token.clone(),
+            registry.validator_registry.clone(),
+            registry.storage_path.clone(),
+        );
+        assert!(damaged.err().unwrap().starts_with("Unreadable delegations"));
+    }
+}
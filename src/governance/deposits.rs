@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
+use crate::governance::token::GovernanceToken;
+
+/// Token account holding deposits until their proposal is decided
+pub const ESCROW_ACCOUNT: &str = "governance-deposit-escrow";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DepositStatus {
+    Escrowed,
+    Refunded,
+    Burned,
+}
+
+/// gBTCZ a proposer put up to submit a proposal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub depositor: String,
+    pub amount: u64,
+    pub status: DepositStatus,
+}
+
+/// Escrows proposal deposits in gBTCZ and settles them once the outcome is known
+pub struct ProposalDeposits {
+    pub token: Arc<Mutex<GovernanceToken>>,
+    pub amount: Mutex<u64>, // Deposit required per proposal
+}
+
+impl ProposalDeposits {
+    pub fn new(token: Arc<Mutex<GovernanceToken>>, amount: u64) -> Self {
+        Self {
+            token,
+            amount: Mutex::new(amount),
+        }
+    }
+
+    pub fn required_amount(&self) -> Result<u64, String> {
+        let amount = self.amount.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(*amount)
+    }
+
+    /// Moves the required deposit from `depositor` into escrow
+    pub fn escrow(&self, depositor: &str) -> Result<Deposit, String> {
+        let amount = self.required_amount()?;
+        let mut token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+        token.validate_governance_transaction(depositor, amount)?;
+        if amount > 0 {
+            token.transfer(depositor, ESCROW_ACCOUNT, amount)?;
+        }
+        Ok(Deposit {
+            depositor: depositor.to_string(),
+            amount,
+            status: DepositStatus::Escrowed,
+        })
+    }
+
+    /// Returns an escrowed deposit to its depositor
+    pub fn refund(&self, deposit: &mut Deposit) -> Result<(), String> {
+        self.settle(deposit, DepositStatus::Refunded)
+    }
+
+    /// Destroys an escrowed deposit
+    pub fn burn(&self, deposit: &mut Deposit) -> Result<(), String> {
+        self.settle(deposit, DepositStatus::Burned)
+    }
+
+    fn settle(&self, deposit: &mut Deposit, status: DepositStatus) -> Result<(), String> {
+        if deposit.status != DepositStatus::Escrowed {
+            return Err(format!("Deposit was already {:?}", deposit.status));
+        }
+        if deposit.amount > 0 {
+            let mut token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+            match status {
+                DepositStatus::Refunded => token.transfer(ESCROW_ACCOUNT, &deposit.depositor, deposit.amount)?,
+                _ => token.burn(ESCROW_ACCOUNT, deposit.amount)?,
+            }
+        }
+        deposit.status = status;
+        println!("{:?} {} gBTCZ deposit of {}", status, deposit.amount, deposit.depositor);
+        Ok(())
+    }
+}
+
+impl GovernableModule for ProposalDeposits {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        Ok(vec![ParameterSpec {
+            name: "proposal_deposit",
+            module: "governance",
+            kind: ParameterType::Amount,
+            min: 0,
+            max: 1_000_000,
+            unit: "gBTCZ",
+            description: "Deposit escrowed with each proposal; burned if it misses quorum, is vetoed or is withdrawn once open",
+            value: self.required_amount()?,
+        }])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        match name {
+            "proposal_deposit" => {
+                let mut amount = self.amount.lock().map_err(|_| "Mutex lock failed")?;
+                *amount = value;
+                Ok(())
+            }
+            _ => Err(format!("Unknown governance parameter: {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escrow_refund_and_burn() {
+        let mut token = GovernanceToken::new("gBTCZ", 0);
+        token.mint("alice", 250).unwrap();
+        let deposits = ProposalDeposits::new(Arc::new(Mutex::new(token)), 100);
+
+        let mut refunded = deposits.escrow("alice").unwrap();
+        let mut burned = deposits.escrow("alice").unwrap();
+        assert!(deposits.escrow("alice").unwrap_err().contains("Insufficient"));
+        assert!(deposits.escrow("bob").is_err());
+
+        deposits.refund(&mut refunded).unwrap();
+        deposits.burn(&mut burned).unwrap();
+        assert!(deposits.refund(&mut burned).is_err()); // Settled only once
+
+        let token = deposits.token.lock().unwrap();
+        assert_eq!(token.get_balance("alice"), 150);
+        assert_eq!(token.get_balance(ESCROW_ACCOUNT), 0);
+        assert_eq!(token.total_supply, 150);
+    }
+}
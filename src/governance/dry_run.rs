@@ -0,0 +1,137 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::governance::audit_log::ParameterChange;
+use crate::governance::parameters::{ParameterRegistry, ParameterSpec};
+use crate::governance::proposal_engine::ProposalAction;
+use crate::governance::tallying::TallyStrategy;
+use crate::governance::validator_policies::{GovernanceValidatorPolicies, ValidatorPolicy};
+use crate::validator::node_registration::{ValidatorNode, ValidatorRegistry};
+
+/// How a proposal would change one validator's standing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidatorImpact {
+    pub address: String,
+    pub eligible_before: bool, // Meets the validator policy
+    pub eligible_after: bool,
+    pub voting_power_before: u64, // Own weight under the tally strategy, delegated gBTCZ excluded
+    pub voting_power_after: u64,
+    pub reward_before: u64, // Next reward cycle
+    pub reward_after: u64,
+}
+
+/// What executing a proposal would do, computed on a copy of the governed state
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    pub tally_strategy: String,
+    pub changes: Vec<ParameterChange>,
+    pub below_minimum_stake: Vec<String>, // Active validators meeting the current minimum stake but not the new one
+    pub validators: Vec<ValidatorImpact>, // Active validators, by address
+    pub total_voting_power_before: u64,
+    pub total_voting_power_after: u64,
+    pub next_rewards_before: u64, // Paid out across validators in the next cycle
+    pub next_rewards_after: u64,
+}
+
+/// Copy of every governable parameter and the validator set. Changes applied to it are
+/// checked like real ones but never reach the modules or disk.
+#[derive(Debug, Clone)]
+pub struct GovernedState {
+    pub parameters: BTreeMap<String, ParameterSpec>,
+    pub validators: Vec<ValidatorNode>,
+}
+
+impl GovernedState {
+    pub fn capture(parameters: &ParameterRegistry, registry: &ValidatorRegistry) -> Result<Self, String> {
+        let nodes = registry.nodes.lock().map_err(|_| "Mutex lock failed")?;
+        let mut validators: Vec<ValidatorNode> = nodes.values().cloned().collect();
+        validators.sort_by(|a, b| a.address.cmp(&b.address));
+        Ok(Self {
+            parameters: parameters
+                .list()?
+                .into_iter()
+                .map(|spec| (spec.name.to_string(), spec))
+                .collect(),
+            validators,
+        })
+    }
+
+    /// Applies `actions` in order, with the registry's bounds checks, and reports each
+    /// parameter's value before and after
+    pub fn apply(&mut self, actions: &[ProposalAction]) -> Result<Vec<ParameterChange>, String> {
+        let mut changes = Vec::new();
+        for action in actions {
+            let spec = self
+                .parameters
+                .get_mut(&action.param)
+                .ok_or_else(|| format!("Unknown governance parameter: {}", action.param))?;
+            spec.check(action.value)?;
+            changes.push(ParameterChange {
+                param: action.param.clone(),
+                before: spec.value,
+                after: action.value,
+            });
+            spec.value = action.value;
+        }
+        Ok(changes)
+    }
+
+    fn value(&self, name: &str) -> Option<u64> {
+        self.parameters.get(name).map(|spec| spec.value)
+    }
+
+    /// Validator policy as set in this copy
+    fn policies(&self) -> GovernanceValidatorPolicies {
+        let default = ValidatorPolicy::default();
+        GovernanceValidatorPolicies::new(
+            self.value("minimum_stake").unwrap_or(default.minimum_stake),
+            self.value("activity_threshold").unwrap_or(default.activity_threshold),
+            self.value("compliance_threshold").unwrap_or(default.compliance_threshold),
+        )
+    }
+}
+
+/// Compares validators under the `before` and `after` states. Each validator meeting the
+/// policy is paid `reward_rate` per cycle.
+pub fn compare(before: &GovernedState, after: &GovernedState, strategy: &dyn TallyStrategy, changes: Vec<ParameterChange>) -> DryRunReport {
+    let (policies_before, policies_after) = (before.policies(), after.policies());
+    let rate_before = before.value("reward_rate").unwrap_or(0);
+    let rate_after = after.value("reward_rate").unwrap_or(0);
+    let minimum_before = policies_before.get_policy().unwrap_or_default().minimum_stake;
+    let minimum_after = policies_after.get_policy().unwrap_or_default().minimum_stake;
+
+    let validators: Vec<ValidatorImpact> = after
+        .validators
+        .iter()
+        .filter(|node| node.active)
+        .map(|node| {
+            let eligible_before = policies_before.meets_policy(node);
+            let eligible_after = policies_after.meets_policy(node);
+            ValidatorImpact {
+                address: node.address.clone(),
+                eligible_before,
+                eligible_after,
+                voting_power_before: strategy.validator_weight(node, &policies_before),
+                voting_power_after: strategy.validator_weight(node, &policies_after),
+                reward_before: if eligible_before { rate_before } else { 0 },
+                reward_after: if eligible_after { rate_after } else { 0 },
+            }
+        })
+        .collect();
+    let below_minimum_stake = after
+        .validators
+        .iter()
+        .filter(|node| node.active && node.staked_btcz >= minimum_before && node.staked_btcz < minimum_after)
+        .map(|node| node.address.clone())
+        .collect();
+
+    DryRunReport {
+        tally_strategy: strategy.name().to_string(),
+        changes,
+        below_minimum_stake,
+        total_voting_power_before: validators.iter().map(|v| v.voting_power_before).sum(),
+        total_voting_power_after: validators.iter().map(|v| v.voting_power_after).sum(),
+        next_rewards_before: validators.iter().map(|v| v.reward_before).sum(),
+        next_rewards_after: validators.iter().map(|v| v.reward_after).sum(),
+        validators,
+    }
+}
@@ -0,0 +1,404 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use crate::clarity::transaction::recover_hash160;
+use crate::clarity::value::c32_address_decode;
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
+
+/// What guardians may do without waiting for a governance vote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EmergencyAction {
+    PauseBridge,
+    FreezeRewards,
+    VetoProposal { proposal_id: u64 }, // Holds a queued proposal back from execution
+}
+
+impl EmergencyAction {
+    fn encode(&self) -> String {
+        match self {
+            EmergencyAction::PauseBridge => "pause-bridge".to_string(),
+            EmergencyAction::FreezeRewards => "freeze-rewards".to_string(),
+            EmergencyAction::VetoProposal { proposal_id } => format!("veto-proposal:{}", proposal_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmergencyStatus {
+    Active,   // In force until `expires_at` unless ratified
+    Ratified, // Confirmed by a governance vote; in force until lifted
+    Lapsed,   // Not ratified in time
+    Lifted,   // Ended by a governance vote
+}
+
+/// One use of guardian powers, kept permanently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyRecord {
+    pub id: u64,
+    pub action: EmergencyAction,
+    pub reason: String,
+    pub signers: Vec<String>, // Guardian addresses whose signatures authorized it
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>, // End of the ratification window
+    pub status: EmergencyStatus,
+    pub resolved_at: Option<DateTime<Utc>>, // When it was ratified, lapsed or lifted
+}
+
+impl EmergencyRecord {
+    /// Whether the action applies at `now`
+    pub fn in_force(&self, now: DateTime<Utc>) -> bool {
+        match self.status {
+            EmergencyStatus::Active => now < self.expires_at,
+            EmergencyStatus::Ratified => true,
+            EmergencyStatus::Lapsed | EmergencyStatus::Lifted => false,
+        }
+    }
+}
+
+/// Digest each guardian signs to authorize emergency `id`. The id is the council's next
+/// record id, so a set of signatures cannot be replayed.
+pub fn emergency_digest(id: u64, action: &EmergencyAction, reason: &str) -> [u8; 32] {
+    let message = format!("zook-guardian-emergency:{}:{}:{}", id, action.encode(), reason);
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&Sha256::digest(message.as_bytes()));
+    digest
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GuardianStore {
+    next_id: u64,
+    records: BTreeMap<u64, EmergencyRecord>,
+    last_ratified: u64,
+    last_lifted: u64,
+}
+
+impl Default for GuardianStore {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            records: BTreeMap::new(),
+            last_ratified: 0,
+            last_lifted: 0,
+        }
+    }
+}
+
+/// M-of-N guardian keys that can pause the bridge, freeze reward distribution or veto a
+/// queued proposal. Each action lapses at the end of the ratification window unless a
+/// governance proposal ratifies it through the `ratify_emergency` parameter; ratified
+/// actions stay in force until lifted through `lift_emergency`.
+#[derive(Debug)]
+pub struct GuardianCouncil {
+    guardians: Vec<(String, [u8; 20])>, // Address and its hash160
+    pub threshold: usize,
+    pub ratification_window_secs: i64,
+    store: Mutex<GuardianStore>,
+    pub storage_path: PathBuf,
+}
+
+impl GuardianCouncil {
+    /// With no guardians, emergency powers are disabled
+    pub fn new(
+        guardians: Vec<String>,
+        threshold: usize,
+        ratification_window_secs: i64,
+        storage_path: PathBuf,
+    ) -> Result<Self, String> {
+        let guardians = guardians
+            .into_iter()
+            .map(|address| {
+                let (_, hash160) = c32_address_decode(&address)?;
+                Ok((address, hash160))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if !guardians.is_empty() && (threshold == 0 || threshold > guardians.len()) {
+            return Err(format!(
+                "Guardian threshold must be between 1 and {}, got {}",
+                guardians.len(),
+                threshold
+            ));
+        }
+        let store = match fs::read_to_string(&storage_path) {
+            // Starting empty would silently lift any pause or freeze in force
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable guardian records in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => GuardianStore::default(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+        Ok(Self {
+            guardians,
+            threshold,
+            ratification_window_secs,
+            store: Mutex::new(store),
+            storage_path,
+        })
+    }
+
+    pub fn guardians(&self) -> Vec<String> {
+        self.guardians.iter().map(|(address, _)| address.clone()).collect()
+    }
+
+    /// Id the next emergency action will be recorded under, which guardians sign over
+    pub fn next_id(&self) -> Result<u64, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.next_id)
+    }
+
+    /// Carries out `action` once at least `threshold` distinct guardians have signed its
+    /// digest. `signatures` are hex-encoded 65-byte recoverable signatures.
+    pub fn authorize(&self, action: EmergencyAction, reason: String, signatures: &[String]) -> Result<EmergencyRecord, String> {
+        if self.guardians.is_empty() {
+            return Err("No guardians are configured".to_string());
+        }
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let now = Utc::now();
+        if store.records.values().any(|record| record.action == action && record.in_force(now)) {
+            return Err(format!("{:?} is already in force", action));
+        }
+
+        let digest = emergency_digest(store.next_id, &action, &reason);
+        let mut signers = Vec::new();
+        for signature in signatures {
+            let bytes: [u8; 65] = hex::decode(signature)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or("Signatures must be 65 bytes of hex")?;
+            let hash160 = recover_hash160(&digest, &bytes)?;
+            let (address, _) = self
+                .guardians
+                .iter()
+                .find(|(_, guardian)| *guardian == hash160)
+                .ok_or("Signature is not from a guardian")?;
+            if !signers.contains(address) {
+                signers.push(address.clone());
+            }
+        }
+        if signers.len() < self.threshold {
+            return Err(format!(
+                "{} of {} required guardian signatures",
+                signers.len(),
+                self.threshold
+            ));
+        }
+
+        let record = EmergencyRecord {
+            id: store.next_id,
+            action,
+            reason,
+            signers,
+            created_at: now,
+            expires_at: now + Duration::seconds(self.ratification_window_secs),
+            status: EmergencyStatus::Active,
+            resolved_at: None,
+        };
+        store.records.insert(record.id, record.clone());
+        store.next_id += 1;
+        self.persist(&store)?;
+        println!(
+            "Guardian emergency {}: {:?} by {} ({}), lapses at {} unless ratified",
+            record.id,
+            record.action,
+            record.signers.join(", "),
+            record.reason,
+            record.expires_at
+        );
+        Ok(record)
+    }
+
+    pub fn is_bridge_paused(&self) -> Result<bool, String> {
+        self.in_force(EmergencyAction::PauseBridge)
+    }
+
+    pub fn are_rewards_frozen(&self) -> Result<bool, String> {
+        self.in_force(EmergencyAction::FreezeRewards)
+    }
+
+    fn in_force(&self, action: EmergencyAction) -> Result<bool, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let now = Utc::now();
+        Ok(store.records.values().any(|record| record.action == action && record.in_force(now)))
+    }
+
+    /// The latest veto of `proposal_id`, if guardians ever vetoed it
+    pub fn veto_for(&self, proposal_id: u64) -> Result<Option<EmergencyRecord>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store
+            .records
+            .values()
+            .rev()
+            .find(|record| record.action == EmergencyAction::VetoProposal { proposal_id })
+            .cloned())
+    }
+
+    /// Every emergency action in id order
+    pub fn list(&self) -> Result<Vec<EmergencyRecord>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.records.values().cloned().collect())
+    }
+
+    /// Marks actions whose ratification window has closed as lapsed. Returns their ids.
+    pub fn expire_lapsed(&self) -> Result<Vec<u64>, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let now = Utc::now();
+        let mut lapsed = Vec::new();
+        for record in store.records.values_mut() {
+            if record.status == EmergencyStatus::Active && now >= record.expires_at {
+                record.status = EmergencyStatus::Lapsed;
+                record.resolved_at = Some(now);
+                println!("Guardian emergency {} lapsed without ratification", record.id);
+                lapsed.push(record.id);
+            }
+        }
+        if !lapsed.is_empty() {
+            self.persist(&store)?;
+        }
+        Ok(lapsed)
+    }
+
+    /// Moves an action from `from` to `to` on behalf of a governance vote
+    /// Checks that emergency action `id` is `from` and still in force
+    fn check_resolvable(record: Option<&EmergencyRecord>, id: u64, from: EmergencyStatus) -> Result<(), String> {
+        let record = record.ok_or("Emergency action not found")?;
+        if record.status != from || !record.in_force(Utc::now()) {
+            return Err(format!("Emergency action {} is {:?}, not {:?}", id, record.status, from));
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, id: u64, from: EmergencyStatus, to: EmergencyStatus) -> Result<(), String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let now = Utc::now();
+        Self::check_resolvable(store.records.get(&id), id, from)?;
+        let record = store.records.get_mut(&id).ok_or("Emergency action not found")?;
+        record.status = to;
+        record.resolved_at = Some(now);
+        match to {
+            EmergencyStatus::Ratified => store.last_ratified = id,
+            _ => store.last_lifted = id,
+        }
+        self.persist(&store)
+    }
+
+    fn persist(&self, store: &GuardianStore) -> Result<(), String> {
+        let serialized = serde_json::to_string(store).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+}
+
+impl GovernableModule for GuardianCouncil {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let record = |name, description, value| ParameterSpec {
+            name,
+            module: "guardians",
+            kind: ParameterType::RecordId,
+            min: 1,
+            max: store.next_id - 1,
+            unit: "emergency id",
+            description,
+            value,
+        };
+        Ok(vec![
+            record("ratify_emergency", "Keeps a guardian emergency action in force", store.last_ratified),
+            record("lift_emergency", "Ends a ratified guardian emergency action", store.last_lifted),
+        ])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        match name {
+            "ratify_emergency" => self.resolve(value, EmergencyStatus::Active, EmergencyStatus::Ratified),
+            "lift_emergency" => self.resolve(value, EmergencyStatus::Ratified, EmergencyStatus::Lifted),
+            _ => Err(format!("Unknown governance parameter: {}", name)),
+        }
+    }
+
+    fn check_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        match name {
+            "ratify_emergency" => Self::check_resolvable(store.records.get(&value), value, EmergencyStatus::Active),
+            "lift_emergency" => Self::check_resolvable(store.records.get(&value), value, EmergencyStatus::Ratified),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::transaction::{StacksNetwork, StacksSigner};
+
+    fn signer(byte: u8) -> StacksSigner {
+        StacksSigner::from_hex(&hex::encode([byte; 32]), StacksNetwork::Testnet).unwrap()
+    }
+
+    /// Council of guardians 1, 2 and 3 requiring two signatures
+    fn council(name: &str, window_secs: i64) -> GuardianCouncil {
+        let path = std::env::temp_dir().join(format!("zook_guardians_{}_{}.json", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        let guardians = (1..=3).map(|byte| signer(byte).address()).collect();
+        GuardianCouncil::new(guardians, 2, window_secs, path).unwrap()
+    }
+
+    fn sign(council: &GuardianCouncil, action: EmergencyAction, reason: &str, keys: &[u8]) -> Vec<String> {
+        let digest = emergency_digest(council.next_id().unwrap(), &action, reason);
+        keys.iter().map(|&byte| hex::encode(signer(byte).sign(&digest).unwrap())).collect()
+    }
+
+    #[test]
+    fn test_threshold_signatures_pause_until_ratified_and_lifted() {
+        let council = council("pause", 3_600);
+        let pause = EmergencyAction::PauseBridge;
+
+        let one = sign(&council, pause, "exploit", &[1, 1]);
+        assert_eq!(council.authorize(pause, "exploit".into(), &one).unwrap_err(), "1 of 2 required guardian signatures");
+        let outsider = sign(&council, pause, "exploit", &[1, 9]);
+        assert!(council.authorize(pause, "exploit".into(), &outsider).is_err());
+        // Signatures cover the reason, so they cannot be reused for another one
+        let signatures = sign(&council, pause, "exploit", &[1, 3]);
+        assert!(council.authorize(pause, "other".into(), &signatures).is_err());
+
+        let record = council.authorize(pause, "exploit".into(), &signatures).unwrap();
+        assert_eq!(record.signers.len(), 2);
+        assert!(council.is_bridge_paused().unwrap());
+        assert!(!council.are_rewards_frozen().unwrap());
+        assert!(council.authorize(pause, "exploit".into(), &sign(&council, pause, "exploit", &[1, 2])).is_err());
+
+        assert!(council.set_governance_parameter("lift_emergency", record.id).is_err());
+        council.set_governance_parameter("ratify_emergency", record.id).unwrap();
+        assert_eq!(council.governance_parameters().unwrap()[0].value, record.id);
+        council.set_governance_parameter("lift_emergency", record.id).unwrap();
+        assert!(!council.is_bridge_paused().unwrap());
+    }
+
+    #[test]
+    fn test_unratified_actions_lapse() {
+        let council = council("lapse", 0);
+        let freeze = EmergencyAction::FreezeRewards;
+        let signatures = sign(&council, freeze, "runaway rewards", &[2, 3]);
+        let record = council.authorize(freeze, "runaway rewards".into(), &signatures).unwrap();
+        assert!(!council.are_rewards_frozen().unwrap());
+
+        assert_eq!(council.expire_lapsed().unwrap(), vec![record.id]);
+        assert!(council.set_governance_parameter("ratify_emergency", record.id).is_err());
+        assert_eq!(council.list().unwrap()[0].status, EmergencyStatus::Lapsed);
+    }
+
+    #[test]
+    fn test_unreadable_records_are_refused() {
+        let council = council("unreadable", 3_600);
+        let signatures = sign(&council, EmergencyAction::PauseBridge, "exploit", &[1, 2]);
+        council.authorize(EmergencyAction::PauseBridge, "exploit".into(), &signatures).unwrap();
+        fs::write(&council.storage_path, "{").unwrap();
+
+        let guardians = council.guardians();
+        let reopened = GuardianCouncil::new(guardians, 2, 3_600, council.storage_path.clone());
+        assert!(reopened.err().unwrap().starts_with("Unreadable guardian records"));
+    }
+}
@@ -1,8 +1,22 @@
 // File: src/governance/mod.rs
 
+#[allow(dead_code)] // Superseded by validator_rewards_tokenomics
 pub mod validator_rewards;
 pub mod validator_policies;
-pub mod cross_layer_governance;
+pub mod validator_policy_api;
+pub mod audit_log;
+pub mod contract_sync;
+pub mod delegation;
+pub mod deposits;
+pub mod dry_run;
+pub mod guardians;
+pub mod outcomes;
+pub mod parameters;
+pub mod proposal_engine;
+pub mod tallying;
+pub mod token;
+pub mod treasury;
+pub mod validator_admission;
 pub mod validator_rewards_tokenomics; // Include the correct module for rewards logic
 
 use validator_rewards_tokenomics::ValidatorRewards;
@@ -10,10 +24,12 @@ use std::sync::Arc;
 use std::path::PathBuf;
 use crate::validator::node_registration::ValidatorNode;
 
+#[allow(dead_code)] // Rewards are driven through the parameter registry and APIs instead
 pub struct GovernanceModule {
     pub rewards: Arc<ValidatorRewards>,
 }
 
+#[allow(dead_code)]
 impl GovernanceModule {
     pub fn new(reward_rate: u64, storage_path: PathBuf) -> Self {
         Self {
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::bridge::merkle::MerkleTree;
+use crate::bridge::state_anchoring::L2StateSummary;
+use crate::governance::proposal_engine::{ProposalAction, ProposalTally};
+
+/// The result of an executed proposal, as committed to the anchored L2 state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceOutcome {
+    pub proposal_id: u64,
+    pub actions: Vec<ProposalAction>,
+    pub tally: ProposalTally,
+    pub executed_at: DateTime<Utc>,
+}
+
+impl GovernanceOutcome {
+    /// Merkle leaf committing to every field of the outcome. Actions are encoded in order as
+    /// `param:value` pairs, so a single-change outcome hashes as it did before actions.
+    pub fn leaf(&self) -> Vec<u8> {
+        let actions: Vec<String> = self
+            .actions
+            .iter()
+            .map(|action| format!("{}:{}", action.param, action.value))
+            .collect();
+        let encoded = format!(
+            "governance-outcome:{}:{}:{}:{}:{}:{}",
+            self.proposal_id,
+            actions.join(":"),
+            self.tally.yes,
+            self.tally.no,
+            self.tally.abstain,
+            self.executed_at.timestamp()
+        );
+        Sha256::digest(encoded.as_bytes()).to_vec()
+    }
+}
+
+/// Hex Merkle root over outcomes in execution order, or `None` when nothing has executed
+pub fn governance_root(outcomes: &[GovernanceOutcome]) -> Option<String> {
+    if outcomes.is_empty() {
+        return None;
+    }
+    let tree = MerkleTree::new(outcomes.iter().map(GovernanceOutcome::leaf).collect());
+    Some(hex::encode(tree.get_root()))
+}
+
+/// Inclusion proof that an outcome is part of a governance root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceOutcomeProof {
+    pub outcome: GovernanceOutcome,
+    pub governance_root: String,
+    pub merkle_proof: Vec<String>, // Hex sibling hashes from leaf to root
+}
+
+impl GovernanceOutcomeProof {
+    /// Proves `outcomes[index]` against the root of `outcomes`
+    pub fn new(outcomes: &[GovernanceOutcome], index: usize) -> Result<Self, String> {
+        let outcome = outcomes.get(index).ok_or("Outcome not found")?.clone();
+        let tree = MerkleTree::new(outcomes.iter().map(GovernanceOutcome::leaf).collect());
+        Ok(Self {
+            merkle_proof: tree.get_proof(&outcome.leaf()).iter().map(hex::encode).collect(),
+            governance_root: hex::encode(tree.get_root()),
+            outcome,
+        })
+    }
+
+    /// Checks the proof against the governance root committed in `summary`. Needs nothing
+    /// but the proof and the anchored summary, so it can be run from BTCZ data alone.
+    pub fn verify(&self, summary: &L2StateSummary) -> Result<(), String> {
+        if summary.governance_root.as_deref() != Some(self.governance_root.as_str()) {
+            return Err(format!(
+                "Anchored state at height {} does not commit to governance root {}",
+                summary.block_height, self.governance_root
+            ));
+        }
+        let root = hex::decode(&self.governance_root).map_err(|_| "Invalid governance root")?;
+        let proof = self
+            .merkle_proof
+            .iter()
+            .map(hex::decode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "Invalid Merkle proof encoding")?;
+        if !MerkleTree::verify_proof(proof, root, self.outcome.leaf()) {
+            return Err(format!("Proposal {} is not included in the governance root", self.outcome.proposal_id));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,306 @@
+use serde::Serialize;
+use std::sync::Arc;
+
+/// What a parameter value measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterType {
+    Duration,    // Seconds
+    Amount,      // Token base units
+    Percent,     // 0-100
+    BasisPoints, // 1/100th of a percent
+    RecordId,    // Identifies a record the module keeps, e.g. an emergency action
+    Flag,        // 0 (off) or 1 (on)
+    Count,       // Whole units, e.g. epochs
+}
+
+impl ParameterType {
+    /// Commands act once on the record they name, e.g. approving it, so unlike settings they
+    /// cannot be set back to a previous value
+    pub fn is_command(&self) -> bool {
+        matches!(self, ParameterType::RecordId)
+    }
+}
+
+/// A governable parameter as declared by the module that owns it
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterSpec {
+    pub name: &'static str,
+    pub module: &'static str,
+    pub kind: ParameterType,
+    pub min: u64,
+    pub max: u64,
+    pub unit: &'static str,
+    pub description: &'static str,
+    pub value: u64, // Current value
+}
+
+impl ParameterSpec {
+    pub fn check(&self, value: u64) -> Result<(), String> {
+        if value < self.min || value > self.max {
+            return Err(format!(
+                "{} must be between {} and {} {}, got {}",
+                self.name, self.min, self.max, self.unit, value
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Implemented by modules whose settings governance proposals may change
+pub trait GovernableModule: Send + Sync {
+    /// Declares the module's parameters along with their current values
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String>;
+
+    /// Sets a parameter declared by this module; bounds are checked by the registry
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String>;
+
+    /// Checks, without changing anything, that setting `name` to `value` would succeed.
+    /// Commands use it to check the record they act on before any change is applied.
+    fn check_governance_parameter(&self, _name: &str, _value: u64) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Every governable parameter across modules, used to validate proposals at submission and
+/// to apply them at execution
+#[derive(Default)]
+pub struct ParameterRegistry {
+    modules: Vec<Arc<dyn GovernableModule>>,
+}
+
+impl ParameterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, module: Arc<dyn GovernableModule>) -> Self {
+        self.modules.push(module);
+        self
+    }
+
+    pub fn list(&self) -> Result<Vec<ParameterSpec>, String> {
+        let mut parameters = Vec::new();
+        for module in &self.modules {
+            parameters.extend(module.governance_parameters()?);
+        }
+        Ok(parameters)
+    }
+
+    pub fn get(&self, name: &str) -> Result<ParameterSpec, String> {
+        self.find(name).map(|(_, spec)| spec)
+    }
+
+    /// Checks that `name` is a known parameter and `value` lies within its bounds
+    pub fn validate(&self, name: &str, value: u64) -> Result<ParameterSpec, String> {
+        let spec = self.get(name)?;
+        spec.check(value)?;
+        Ok(spec)
+    }
+
+    /// Checks every change of a proposal. A command cannot be undone, so it must be the only
+    /// change; otherwise a later failure would leave it applied while the rest rolls back.
+    pub fn validate_all(&self, changes: &[(&str, u64)]) -> Result<Vec<ParameterSpec>, String> {
+        let specs = changes
+            .iter()
+            .map(|&(name, value)| self.validate(name, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        if changes.len() > 1 {
+            if let Some(command) = specs.iter().find(|spec| spec.kind.is_command()) {
+                return Err(format!("{} cannot be undone, so it must be the only change in a proposal", command.name));
+            }
+        }
+        Ok(specs)
+    }
+
+    pub fn apply(&self, name: &str, value: u64) -> Result<(), String> {
+        let (module, spec) = self.find(name)?;
+        spec.check(value)?;
+        module.set_governance_parameter(name, value)?;
+        println!("Governance parameter {} updated from {} to {} {}", name, spec.value, value, spec.unit);
+        Ok(())
+    }
+
+    /// Applies an ordered set of changes as one. Every change is checked before any is
+    /// applied, and if one fails the settings already applied are rolled back to their
+    /// previous values. Commands are only ever applied on their own.
+    pub fn apply_all(&self, changes: &[(&str, u64)]) -> Result<(), String> {
+        let specs = self.validate_all(changes)?;
+        for &(name, value) in changes {
+            let (module, _) = self.find(name)?;
+            module
+                .check_governance_parameter(name, value)
+                .map_err(|e| format!("Cannot apply {}: {}", name, e))?;
+        }
+        let mut applied = Vec::new();
+        for (&(name, value), spec) in changes.iter().zip(specs) {
+            if let Err(e) = self.apply(name, value) {
+                self.roll_back(applied);
+                return Err(format!("Failed to apply {}: {}", name, e));
+            }
+            applied.push((name, spec.value));
+        }
+        Ok(())
+    }
+
+    fn roll_back(&self, applied: Vec<(&str, u64)>) {
+        for (name, previous) in applied.into_iter().rev() {
+            if let Err(rollback) = self.restore(name, previous) {
+                eprintln!("Failed to roll back governance parameter {}: {}", name, rollback);
+            }
+        }
+    }
+
+    /// Puts back a value the parameter held before, without re-checking today's bounds
+    fn restore(&self, name: &str, value: u64) -> Result<(), String> {
+        let (module, _) = self.find(name)?;
+        module.set_governance_parameter(name, value)?;
+        println!("Governance parameter {} rolled back to {}", name, value);
+        Ok(())
+    }
+
+    fn find(&self, name: &str) -> Result<(&Arc<dyn GovernableModule>, ParameterSpec), String> {
+        for module in &self.modules {
+            if let Some(spec) = module.governance_parameters()?.into_iter().find(|spec| spec.name == name) {
+                return Ok((module, spec));
+            }
+        }
+        Err(format!("Unknown governance parameter: {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::state_anchoring::StateAnchoring;
+    use crate::governance::validator_policies::GovernanceValidatorPolicies;
+
+    fn registry() -> (ParameterRegistry, Arc<StateAnchoring>) {
+        let anchoring = Arc::new(StateAnchoring::new());
+        let registry = ParameterRegistry::new()
+            .register(anchoring.clone())
+            .register(Arc::new(GovernanceValidatorPolicies::new(1_000_000, 80, 70)));
+        (registry, anchoring)
+    }
+
+    #[test]
+    fn test_lists_declared_parameters() {
+        let (registry, _) = registry();
+        let names: Vec<_> = registry.list().unwrap().iter().map(|spec| spec.name).collect();
+        assert_eq!(
+            names,
+            vec!["anchoring_frequency", "minimum_stake", "activity_threshold", "compliance_threshold", "policy_notice_epochs"]
+        );
+        let spec = registry.get("compliance_threshold").unwrap();
+        assert_eq!((spec.kind, spec.value), (ParameterType::Percent, 70));
+    }
+
+    #[test]
+    fn test_validates_name_and_bounds() {
+        let (registry, anchoring) = registry();
+        assert!(registry.validate("anchoring_frequency", 600).is_ok());
+        assert!(registry.validate("anchoring_frequency", 1).unwrap_err().contains("between"));
+        assert!(registry.validate("activity_threshold", 101).is_err());
+        assert!(registry.validate("block_size", 1).unwrap_err().contains("Unknown"));
+
+        assert!(registry.apply("anchoring_frequency", 0).is_err());
+        registry.apply("anchoring_frequency", 600).unwrap();
+        assert_eq!(anchoring.get_anchoring_frequency().unwrap(), 600);
+    }
+
+    /// Module whose only parameter refuses to be set to 7
+    struct Flaky;
+
+    impl GovernableModule for Flaky {
+        fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+            Ok(vec![ParameterSpec {
+                name: "flaky",
+                module: "test",
+                kind: ParameterType::Amount,
+                min: 0,
+                max: 10,
+                unit: "units",
+                description: "Fails on 7",
+                value: 0,
+            }])
+        }
+
+        fn set_governance_parameter(&self, _name: &str, value: u64) -> Result<(), String> {
+            if value == 7 {
+                return Err("Refused".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_apply_all_rolls_back_on_failure() {
+        let (registry, anchoring) = registry();
+        let registry = registry.register(Arc::new(Flaky));
+        let before = anchoring.get_anchoring_frequency().unwrap();
+
+        // Out-of-bounds changes are caught before anything is applied
+        assert!(registry.apply_all(&[("anchoring_frequency", 600), ("flaky", 11)]).is_err());
+        assert_eq!(anchoring.get_anchoring_frequency().unwrap(), before);
+
+        let err = registry.apply_all(&[("anchoring_frequency", 600), ("flaky", 7)]).unwrap_err();
+        assert!(err.contains("flaky"));
+        assert_eq!(anchoring.get_anchoring_frequency().unwrap(), before);
+
+        registry.apply_all(&[("anchoring_frequency", 600), ("flaky", 3)]).unwrap();
+        assert_eq!(anchoring.get_anchoring_frequency().unwrap(), 600);
+    }
+
+    /// Command approving records 1 and 2; record 3 fails the check
+    #[derive(Default)]
+    struct Approvals {
+        approved: std::sync::Mutex<Vec<u64>>,
+    }
+
+    impl GovernableModule for Approvals {
+        fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+            Ok(vec![ParameterSpec {
+                name: "approve",
+                module: "test",
+                kind: ParameterType::RecordId,
+                min: 1,
+                max: 3,
+                unit: "record id",
+                description: "Approves a record",
+                value: 0,
+            }])
+        }
+
+        fn set_governance_parameter(&self, _name: &str, value: u64) -> Result<(), String> {
+            self.approved.lock().unwrap().push(value);
+            Ok(())
+        }
+
+        fn check_governance_parameter(&self, _name: &str, value: u64) -> Result<(), String> {
+            match value {
+                3 => Err("Record 3 is not pending".to_string()),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_commands_are_only_applied_on_their_own() {
+        let (registry, anchoring) = registry();
+        let approvals = Arc::new(Approvals::default());
+        let registry = registry.register(approvals.clone());
+        let before = anchoring.get_anchoring_frequency().unwrap();
+
+        // Mixed with any other change, a command is refused before anything runs
+        for changes in [&[("approve", 1), ("anchoring_frequency", 600)][..], &[("approve", 1), ("approve", 2)]] {
+            assert!(registry.validate_all(changes).unwrap_err().contains("only change"));
+            assert!(registry.apply_all(changes).unwrap_err().contains("only change"));
+        }
+        assert!(approvals.approved.lock().unwrap().is_empty());
+        assert_eq!(anchoring.get_anchoring_frequency().unwrap(), before);
+
+        assert!(registry.apply_all(&[("approve", 3)]).unwrap_err().contains("not pending"));
+        registry.apply_all(&[("approve", 1)]).unwrap();
+        assert_eq!(*approvals.approved.lock().unwrap(), vec![1]);
+    }
+}
@@ -0,0 +1,1865 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::bridge::state_anchoring::{L2StateSummary, StateAnchoring};
+use crate::validator::node_registration::ValidatorRegistry;
+use crate::governance::validator_policies::GovernanceValidatorPolicies;
+use crate::governance::audit_log::{AuditAction, GovernanceAuditLog, ParameterChange, SYSTEM_ACTOR};
+use crate::governance::delegation::DelegationRegistry;
+use crate::governance::deposits::{Deposit, ProposalDeposits};
+use crate::governance::dry_run::{self, DryRunReport, GovernedState};
+use crate::governance::guardians::{EmergencyAction, EmergencyRecord, EmergencyStatus, GuardianCouncil};
+use crate::governance::outcomes::{governance_root, GovernanceOutcome, GovernanceOutcomeProof};
+use crate::governance::parameters::ParameterRegistry;
+use crate::governance::tallying::{self, TallyStrategy};
+
+/// Lifecycle of a governance proposal
+///
+/// draft -> active -> passed -> queued -> executed, with rejected, expired and cancelled as
+/// the other terminal states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalState {
+    Draft,     // Submitted, voting not yet open
+    Active,    // Voting open until `voting_ends_at`
+    Passed,    // Met quorum and approval
+    Rejected,  // Failed quorum or approval
+    Queued,    // Waiting out the timelock before execution
+    Executed,  // Parameter changes applied
+    Expired,   // Not executed within the execution window
+    Cancelled, // Withdrawn before execution
+}
+
+impl ProposalState {
+    pub fn can_transition_to(self, next: ProposalState) -> bool {
+        use ProposalState::*;
+        matches!(
+            (self, next),
+            (Draft, Active)
+                | (Draft, Cancelled)
+                | (Active, Passed)
+                | (Active, Rejected)
+                | (Active, Cancelled)
+                | (Passed, Queued)
+                | (Passed, Expired)
+                | (Passed, Cancelled)
+                | (Queued, Executed)
+                | (Queued, Expired)
+                | (Queued, Cancelled)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteChoice {
+    Yes,
+    No,
+    Abstain,
+}
+
+/// A validator's or delegator's vote. For validators `weight` is their own voting power when
+/// cast; gBTCZ delegated to them is added when the proposal is tallied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteRecord {
+    pub choice: VoteChoice,
+    pub weight: u64,
+    pub cast_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalTally {
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+}
+
+impl ProposalTally {
+    fn add(&mut self, choice: VoteChoice, weight: u64) {
+        match choice {
+            VoteChoice::Yes => self.yes += weight,
+            VoteChoice::No => self.no += weight,
+            VoteChoice::Abstain => self.abstain += weight,
+        }
+    }
+
+    pub fn turnout(&self) -> u64 {
+        self.yes + self.no + self.abstain
+    }
+}
+
+/// Voting period and thresholds, as percentages, applied when a proposal is finalized
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VotingRules {
+    pub voting_period_secs: i64,
+    pub quorum_percent: u64,   // Share of total voting power that must vote, abstentions included
+    pub approval_percent: u64, // Share of yes among yes + no votes needed to pass
+    pub execution_window_secs: i64, // After the timelock ends, time left to execute before the proposal expires
+    pub timelock_delay_secs: i64, // Delay between passing and execution
+    pub supermajority_percent: u64, // Share of total voting power that can cancel a queued proposal, and of yes + no votes the supermajority strategy needs
+    #[serde(default = "default_tally_strategy")]
+    pub tally_strategy: String, // Strategy for proposals whose changes do not require another
+}
+
+fn default_tally_strategy() -> String {
+    tallying::SIMPLE_MAJORITY.to_string()
+}
+
+impl Default for VotingRules {
+    fn default() -> Self {
+        Self {
+            voting_period_secs: 3 * 86_400,
+            quorum_percent: 40,
+            approval_percent: 50,
+            execution_window_secs: 14 * 86_400,
+            timelock_delay_secs: 2 * 86_400,
+            supermajority_percent: 67,
+            tally_strategy: default_tally_strategy(),
+        }
+    }
+}
+
+/// Calls this node makes to carry a proposal through the proposal_voting contract, in order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContractStep {
+    #[default]
+    None,
+    Proposed,  // `propose` with the proposal's change
+    Voted,     // `vote` carrying the Rust outcome
+    Finalized, // `finalize-proposal`
+}
+
+/// A proposal's mirror in the proposal_voting contract
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OnchainStatus {
+    pub mirrored: bool, // Seen in the contract
+    pub step: ContractStep, // Last confirmed call by this node
+    pub pending: Option<(ContractStep, String)>, // Call by this node awaiting confirmation, with its txid
+    pub votes_for: u64, // Unweighted on-chain votes
+    pub votes_against: u64,
+    pub approved: Option<bool>, // Outcome of the contract's finalize-proposal
+    pub conflict: Option<String>, // Set when the contract and Rust disagree on the outcome
+}
+
+/// Actor recorded in the audit log for outcomes decided by the proposal_voting contract
+const ONCHAIN_ACTOR: &str = "proposal_voting";
+
+/// Actor recorded in the audit log for guardian vetoes
+const GUARDIAN_ACTOR: &str = "guardians";
+
+/// One parameter change within a proposal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalAction {
+    pub param: String,
+    pub value: u64,
+}
+
+impl ProposalAction {
+    pub fn new(param: impl Into<String>, value: u64) -> Self {
+        Self { param: param.into(), value }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceProposal {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub description: String,
+    #[serde(default)]
+    pub actions: Vec<ProposalAction>, // Applied in order, all or none
+    #[serde(default, skip_serializing)]
+    param: Option<String>, // Single change stored before proposals carried actions
+    #[serde(default, skip_serializing)]
+    value: Option<u64>,
+    pub state: ProposalState,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub voting_ends_at: Option<DateTime<Utc>>, // Set when voting opens
+    #[serde(default)]
+    pub snapshot_id: Option<u64>, // gBTCZ snapshot taken when voting opens; delegated weight is read at it
+    pub voting_power: u64, // Total voting power of active validators when finalized
+    pub votes: HashMap<String, VoteRecord>, // Validator address -> vote
+    #[serde(default)]
+    pub delegator_votes: HashMap<String, VoteRecord>, // Delegator -> vote overriding its validator's
+    pub tally: ProposalTally,
+    #[serde(default = "default_tally_strategy")]
+    pub tally_strategy: String, // Name of the strategy that weighs and decides the vote
+    #[serde(default)]
+    pub queued_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub eta: Option<DateTime<Utc>>, // Earliest execution time once queued
+    #[serde(default)]
+    pub executed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cancel_votes: HashMap<String, u64>, // Validator address -> voting power backing cancellation
+    #[serde(default)]
+    pub onchain: OnchainStatus,
+    #[serde(default)]
+    pub deposit: Option<Deposit>, // gBTCZ escrowed by the proposer
+}
+
+impl GovernanceProposal {
+    /// Moves the proposal to `next`, rejecting transitions the lifecycle does not allow
+    fn transition(&mut self, next: ProposalState) -> Result<(), String> {
+        if !self.state.can_transition_to(next) {
+            return Err(format!(
+                "Proposal {} cannot move from {:?} to {:?}",
+                self.proposal_id, self.state, next
+            ));
+        }
+        println!("Proposal {}: {:?} -> {:?}", self.proposal_id, self.state, next);
+        self.state = next;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Whether a queued proposal is still within its timelock delay
+    fn in_timelock(&self) -> bool {
+        self.state == ProposalState::Queued && self.eta.is_some_and(|eta| Utc::now() < eta)
+    }
+
+    /// Whether Rust governance approved the proposal, once voting has closed
+    pub fn approved(&self) -> Option<bool> {
+        match self.state {
+            ProposalState::Draft | ProposalState::Active => None,
+            ProposalState::Passed | ProposalState::Queued | ProposalState::Executed => Some(true),
+            ProposalState::Rejected | ProposalState::Expired | ProposalState::Cancelled => Some(false),
+        }
+    }
+
+    /// The proposal's change when it makes exactly one, the only kind the contract can hold
+    pub fn single_action(&self) -> Option<&ProposalAction> {
+        match self.actions.as_slice() {
+            [action] => Some(action),
+            _ => None,
+        }
+    }
+
+    /// Whether the proposal makes exactly the change `param` = `value`
+    fn is_change(&self, param: &str, value: u64) -> bool {
+        self.single_action().is_some_and(|action| action.param == param && action.value == value)
+    }
+
+    /// Moves a change stored in the old single `param`/`value` form into `actions`
+    fn migrate_legacy_change(&mut self) {
+        if let (Some(param), Some(value)) = (self.param.take(), self.value.take()) {
+            if self.actions.is_empty() {
+                self.actions.push(ProposalAction::new(param, value));
+            }
+        }
+    }
+
+    /// Occupies the contract's single proposal slot until finalized there
+    fn holds_contract_slot(&self) -> bool {
+        (self.onchain.mirrored || self.onchain.pending.is_some()) && self.onchain.approved.is_none()
+    }
+}
+
+/// Persisted form: ids come from `next_proposal_id` so they are never reused
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProposalStore {
+    next_proposal_id: u64,
+    proposals: BTreeMap<u64, GovernanceProposal>,
+}
+
+impl Default for ProposalStore {
+    fn default() -> Self {
+        Self {
+            next_proposal_id: 1,
+            proposals: BTreeMap::new(),
+        }
+    }
+}
+
+/// Governance proposals from submission to execution: voting, validated state transitions and
+/// application of the approved parameter changes
+pub struct ProposalEngine {
+    store: Arc<Mutex<ProposalStore>>,
+    pub parameters: Arc<ParameterRegistry>,
+    pub validator_registry: Arc<ValidatorRegistry>,
+    pub governance_policies: Arc<GovernanceValidatorPolicies>,
+    pub delegations: Option<Arc<DelegationRegistry>>,
+    pub deposits: Option<Arc<ProposalDeposits>>,
+    pub anchoring: Option<Arc<StateAnchoring>>, // Receives the outcome root after each execution
+    pub audit_log: Option<Arc<GovernanceAuditLog>>,
+    pub storage_path: PathBuf, // Path to save governance proposals
+    pub voting_rules: VotingRules,
+    pub guardians: Option<Arc<GuardianCouncil>>, // Multisig that can veto queued proposals
+    tally_strategies: HashMap<String, Arc<dyn TallyStrategy>>, // Strategies proposals may use, by name
+    required_strategies: HashMap<String, String>, // Parameter -> strategy proposals changing it must use
+}
+
+impl ProposalEngine {
+    /// Loads proposals from `storage_path`, failing rather than starting empty if the file
+    /// exists but cannot be read
+    pub fn new(
+        parameters: Arc<ParameterRegistry>,
+        validator_registry: Arc<ValidatorRegistry>,
+        governance_policies: Arc<GovernanceValidatorPolicies>,
+        storage_path: PathBuf,
+    ) -> Result<Self, String> {
+        let mut store: ProposalStore = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable proposals in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ProposalStore::default(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+        store.proposals.values_mut().for_each(GovernanceProposal::migrate_legacy_change);
+        let built_in: [Arc<dyn TallyStrategy>; 4] = [
+            Arc::new(tallying::SimpleMajority),
+            Arc::new(tallying::Supermajority),
+            Arc::new(tallying::ComplianceWeighted),
+            Arc::new(tallying::Quadratic),
+        ];
+
+        Ok(Self {
+            store: Arc::new(Mutex::new(store)),
+            parameters,
+            validator_registry,
+            governance_policies,
+            delegations: None,
+            deposits: None,
+            anchoring: None,
+            audit_log: None,
+            storage_path,
+            voting_rules: VotingRules::default(),
+            guardians: None,
+            tally_strategies: built_in.into_iter().map(|s| (s.name().to_string(), s)).collect(),
+            required_strategies: HashMap::new(),
+        })
+    }
+
+    pub fn with_voting_rules(mut self, voting_rules: VotingRules) -> Self {
+        self.voting_rules = voting_rules;
+        self
+    }
+
+    /// Adds gBTCZ delegated to validators to their voting weight
+    pub fn with_delegations(mut self, delegations: Arc<DelegationRegistry>) -> Self {
+        self.delegations = Some(delegations);
+        self
+    }
+
+    /// Requires a gBTCZ deposit with every submitted proposal
+    pub fn with_deposits(mut self, deposits: Arc<ProposalDeposits>) -> Self {
+        self.deposits = Some(deposits);
+        self
+    }
+
+    /// Commits executed outcomes into anchored L2 state, starting with those already executed
+    pub fn with_anchoring(mut self, anchoring: Arc<StateAnchoring>) -> Self {
+        self.anchoring = Some(anchoring);
+        if let Err(e) = self.commit_outcomes() {
+            eprintln!("Failed to commit governance outcomes: {}", e);
+        }
+        self
+    }
+
+    /// Records every submission, vote and state change in a hash-chained audit log
+    pub fn with_audit_log(mut self, audit_log: Arc<GovernanceAuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    pub fn with_guardians(mut self, guardians: Arc<GuardianCouncil>) -> Self {
+        self.guardians = Some(guardians);
+        self
+    }
+
+    /// Proposals changing `param` must be tallied with the strategy named `strategy`
+    pub fn with_required_strategy(mut self, param: impl Into<String>, strategy: impl Into<String>) -> Self {
+        self.required_strategies.insert(param.into(), strategy.into());
+        self
+    }
+
+    fn tally_strategy(&self, name: &str) -> Result<Arc<dyn TallyStrategy>, String> {
+        self.tally_strategies
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown tally strategy: {}", name))
+    }
+
+    /// The strategy the changed parameters require, which a requested strategy must match;
+    /// otherwise the requested one or the default
+    fn select_strategy(&self, actions: &[ProposalAction], requested: Option<&str>) -> Result<String, String> {
+        let mut required: Option<(&str, &str)> = None;
+        for action in actions {
+            let Some(strategy) = self.required_strategies.get(&action.param) else {
+                continue;
+            };
+            match required {
+                Some((param, other)) if other != strategy => {
+                    return Err(format!(
+                        "{} requires the {} tally strategy but {} requires {}",
+                        action.param, strategy, param, other
+                    ));
+                }
+                _ => required = Some((&action.param, strategy)),
+            }
+        }
+        let name = match (required, requested) {
+            (Some((param, strategy)), Some(requested)) if strategy != requested => {
+                return Err(format!("{} requires the {} tally strategy, not {}", param, strategy, requested));
+            }
+            (Some((_, name)), _) | (None, Some(name)) => name,
+            (None, None) => self.voting_rules.tally_strategy.as_str(),
+        };
+        self.tally_strategy(name)?;
+        Ok(name.to_string())
+    }
+
+    /// Writes the store the caller already holds the lock for
+    fn persist(&self, store: &ProposalStore) -> Result<(), String> {
+        let serialized = serde_json::to_string(store).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+
+    /// Appends to the audit log; failures are logged rather than undoing the action
+    fn audit(&self, proposal_id: u64, actor: &str, action: AuditAction, detail: String, changes: Vec<ParameterChange>) {
+        let Some(audit_log) = &self.audit_log else {
+            return;
+        };
+        if let Err(e) = audit_log.record(proposal_id, actor, action, detail, changes) {
+            eprintln!("Failed to audit {:?} on proposal {}: {}", action, proposal_id, e);
+        }
+    }
+
+    /// Records a draft proposal after checking every action against the parameter registry
+    /// and escrowing the proposer's deposit; voting opens with `activate_proposal`
+    pub fn submit_proposal(
+        &self,
+        proposer: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+    ) -> Result<u64, String> {
+        self.create_proposal(proposer, description, actions, None, self.deposits.as_deref())
+    }
+
+    /// Like `submit_proposal`, but tallied with the strategy named `strategy`, which must agree
+    /// with any strategy the changed parameters require
+    pub fn submit_proposal_with_strategy(
+        &self,
+        proposer: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+        strategy: &str,
+    ) -> Result<u64, String> {
+        self.create_proposal(proposer, description, actions, Some(strategy), self.deposits.as_deref())
+    }
+
+    fn create_proposal(
+        &self,
+        proposer: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+        strategy: Option<&str>,
+        deposits: Option<&ProposalDeposits>,
+    ) -> Result<u64, String> {
+        self.validate_actions(&actions)?;
+        let tally_strategy = self.select_strategy(&actions, strategy)?;
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let deposit = deposits.map(|deposits| deposits.escrow(&proposer)).transpose()?;
+        let proposal_id = store.next_proposal_id;
+        let now = Utc::now();
+        let changes: Vec<String> = actions
+            .iter()
+            .map(|action| format!("{}={}", action.param, action.value))
+            .collect();
+        self.audit(
+            proposal_id,
+            &proposer,
+            AuditAction::Submitted,
+            format!("{} [{}] tallied by {}", description, changes.join(", "), tally_strategy),
+            Vec::new(),
+        );
+        store.proposals.insert(
+            proposal_id,
+            GovernanceProposal {
+                proposal_id,
+                proposer,
+                description,
+                actions,
+                param: None,
+                value: None,
+                state: ProposalState::Draft,
+                created_at: now,
+                updated_at: now,
+                voting_ends_at: None,
+                snapshot_id: None,
+                voting_power: 0,
+                votes: HashMap::new(),
+                delegator_votes: HashMap::new(),
+                tally: ProposalTally::default(),
+                tally_strategy,
+                queued_at: None,
+                eta: None,
+                executed_at: None,
+                cancel_votes: HashMap::new(),
+                onchain: OnchainStatus::default(),
+                deposit,
+            },
+        );
+        store.next_proposal_id += 1;
+        self.persist(&store)?;
+        Ok(proposal_id)
+    }
+
+    /// A proposal needs at least one action, may change each parameter only once and may
+    /// only carry a command on its own
+    fn validate_actions(&self, actions: &[ProposalAction]) -> Result<(), String> {
+        if actions.is_empty() {
+            return Err("Proposal has no actions".to_string());
+        }
+        let mut seen = HashSet::new();
+        for action in actions {
+            if !seen.insert(action.param.as_str()) {
+                return Err(format!("Parameter {} is changed more than once", action.param));
+            }
+        }
+        let changes: Vec<(&str, u64)> = actions.iter().map(|action| (action.param.as_str(), action.value)).collect();
+        self.parameters.validate_all(&changes)?;
+        Ok(())
+    }
+
+    /// Previews `actions` on a copy of the governed state: parameter changes, validators
+    /// falling below the minimum stake, voting power shifts under the tally strategy the
+    /// proposal would use and next cycle's rewards. Nothing is applied or persisted.
+    pub fn dry_run(&self, actions: &[ProposalAction], strategy: Option<&str>) -> Result<DryRunReport, String> {
+        self.validate_actions(actions)?;
+        let strategy = self.tally_strategy(&self.select_strategy(actions, strategy)?)?;
+        self.preview(actions, strategy.as_ref())
+    }
+
+    /// Previews what executing a submitted proposal would do, as `dry_run` does
+    pub fn dry_run_proposal(&self, proposal_id: u64) -> Result<DryRunReport, String> {
+        let proposal = self.get_proposal(proposal_id)?;
+        let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+        self.preview(&proposal.actions, strategy.as_ref())
+    }
+
+    fn preview(&self, actions: &[ProposalAction], strategy: &dyn TallyStrategy) -> Result<DryRunReport, String> {
+        let current = GovernedState::capture(&self.parameters, &self.validator_registry)?;
+        let mut proposed = current.clone();
+        let changes = proposed.apply(actions)?;
+        Ok(dry_run::compare(&current, &proposed, strategy, changes))
+    }
+
+    /// Opens voting for the configured voting period and snapshots gBTCZ balances, so tokens
+    /// moved after activation cannot be voted a second time
+    pub fn activate_proposal(&self, proposal_id: u64) -> Result<DateTime<Utc>, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        proposal.transition(ProposalState::Active)?;
+        let voting_ends_at = Utc::now() + Duration::seconds(self.voting_rules.voting_period_secs);
+        proposal.voting_ends_at = Some(voting_ends_at);
+        proposal.snapshot_id = self.delegations.as_ref().map(|d| d.snapshot()).transpose()?;
+        self.persist(&store)?;
+        self.audit(
+            proposal_id,
+            SYSTEM_ACTOR,
+            AuditAction::Activated,
+            format!("Voting open until {}", voting_ends_at.to_rfc3339()),
+            Vec::new(),
+        );
+        Ok(voting_ends_at)
+    }
+
+    /// Records or replaces `validator`'s vote while the voting period is open. The vote is
+    /// weighted, by the proposal's tally strategy, from the validator's standing at the time
+    /// it is cast plus the gBTCZ delegated to it by holders who have not voted themselves, as
+    /// of the proposal's snapshot.
+    pub fn cast_vote(&self, proposal_id: u64, validator: &str, choice: VoteChoice) -> Result<ProposalTally, String> {
+        let node = self.validator_registry.get_node(validator)?;
+        if !node.active {
+            return Err("Validator is not active".to_string());
+        }
+        let proposal = self.get_proposal(proposal_id)?;
+        let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+        let weight = strategy.validator_weight(&node, &self.governance_policies);
+        if weight == 0 && self.delegated_power(strategy.as_ref(), validator, &HashSet::new(), proposal.snapshot_id)? == 0 {
+            return Err("Validator has no voting power".to_string());
+        }
+
+        let tally = self.record_vote(proposal_id, |proposal| {
+            proposal.votes.insert(
+                validator.to_string(),
+                VoteRecord {
+                    choice,
+                    weight,
+                    cast_at: Utc::now(),
+                },
+            );
+        })?;
+        let detail = format!("{:?} with weight {}", choice, weight);
+        self.audit(proposal_id, validator, AuditAction::Voted, detail, Vec::new());
+        Ok(tally)
+    }
+
+    /// Records a delegator's own vote, which replaces its validator's vote for the delegated
+    /// gBTCZ on this proposal. It is weighted, by the proposal's tally strategy, from the
+    /// delegator's balance at the proposal's snapshot.
+    pub fn cast_delegator_vote(&self, proposal_id: u64, delegator: &str, choice: VoteChoice) -> Result<ProposalTally, String> {
+        let delegations = self.delegations.as_ref().ok_or("Delegation is not enabled")?;
+        if delegations.get_delegation(delegator)?.is_none() {
+            return Err("Only delegators can override their validator's vote".to_string());
+        }
+        let proposal = self.get_proposal(proposal_id)?;
+        let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+        let weight = strategy.token_weight(delegations.balance_at(delegator, proposal.snapshot_id)?);
+        if weight == 0 {
+            return Err("Delegator held no gBTCZ when voting opened".to_string());
+        }
+
+        let tally = self.record_vote(proposal_id, |proposal| {
+            proposal.delegator_votes.insert(
+                delegator.to_string(),
+                VoteRecord {
+                    choice,
+                    weight,
+                    cast_at: Utc::now(),
+                },
+            );
+        })?;
+        let detail = format!("{:?} with weight {} as delegator", choice, weight);
+        self.audit(proposal_id, delegator, AuditAction::Voted, detail, Vec::new());
+        Ok(tally)
+    }
+
+    /// Applies `vote` to an active proposal and refreshes its tally
+    fn record_vote(&self, proposal_id: u64, vote: impl FnOnce(&mut GovernanceProposal)) -> Result<ProposalTally, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        if proposal.state != ProposalState::Active {
+            return Err(format!("Proposal is {:?}, not open for voting", proposal.state));
+        }
+        if proposal.voting_ends_at.is_some_and(|ends_at| Utc::now() >= ends_at) {
+            return Err("Voting period has ended".to_string());
+        }
+
+        vote(proposal);
+        proposal.tally = self.tally(proposal)?;
+        proposal.updated_at = Utc::now();
+        let tally = proposal.tally.clone();
+        self.persist(&store)?;
+        Ok(tally)
+    }
+
+    /// Validator votes count their own power plus gBTCZ from delegators who did not vote
+    fn tally(&self, proposal: &GovernanceProposal) -> Result<ProposalTally, String> {
+        let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+        let overridden: HashSet<String> = proposal.delegator_votes.keys().cloned().collect();
+        let mut tally = ProposalTally::default();
+        for (validator, vote) in &proposal.votes {
+            let delegated = self.delegated_power(strategy.as_ref(), validator, &overridden, proposal.snapshot_id)?;
+            tally.add(vote.choice, vote.weight + delegated);
+        }
+        for vote in proposal.delegator_votes.values() {
+            tally.add(vote.choice, vote.weight);
+        }
+        Ok(tally)
+    }
+
+    fn delegated_power(
+        &self,
+        strategy: &dyn TallyStrategy,
+        validator: &str,
+        excluded: &HashSet<String>,
+        snapshot_id: Option<u64>,
+    ) -> Result<u64, String> {
+        let Some(delegations) = &self.delegations else {
+            return Ok(0);
+        };
+        let power = delegations.delegated_power(validator, excluded, snapshot_id)?;
+        Ok(power.delegators.values().map(|&balance| strategy.token_weight(balance)).sum())
+    }
+
+    /// Closes voting once the period has ended. If the proposal's tally strategy finds quorum
+    /// and approval met, the proposal passes and is queued behind the timelock, otherwise it is
+    /// rejected. Returns the resulting state.
+    pub fn finalize_proposal(&self, proposal_id: u64) -> Result<ProposalState, String> {
+        let proposal = self.get_proposal(proposal_id)?;
+        let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+        let total_power = self.calculate_voting_power(strategy.as_ref(), proposal.snapshot_id)?;
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        if proposal.state != ProposalState::Active {
+            return Err(format!("Proposal is {:?}, not open for voting", proposal.state));
+        }
+        if proposal.voting_ends_at.is_some_and(|ends_at| Utc::now() < ends_at) {
+            return Err("Voting period has not ended".to_string());
+        }
+
+        let tally = self.tally(proposal)?;
+        proposal.tally = tally.clone();
+        let decision = strategy.decide(&tally, total_power, &self.voting_rules);
+        proposal.voting_power = total_power;
+        if decision.passed() {
+            proposal.transition(ProposalState::Passed)?;
+            self.queue(proposal)?;
+        } else {
+            proposal.transition(ProposalState::Rejected)?;
+            self.settle_deposit(proposal, decision.quorum_met);
+        }
+        let outcome = proposal.state;
+        self.persist(&store)?;
+        let summary = format!(
+            "{:?} by {} with {} yes / {} no / {} abstain of {} voting power",
+            outcome,
+            strategy.name(),
+            tally.yes,
+            tally.no,
+            tally.abstain,
+            total_power
+        );
+        println!("Proposal {} {}", proposal_id, summary);
+        self.audit(proposal_id, SYSTEM_ACTOR, AuditAction::Finalized, summary, Vec::new());
+        Ok(outcome)
+    }
+
+    /// Refunds the proposer's escrowed deposit, or burns it when the proposal missed quorum, was
+    /// vetoed or was withdrawn after voting opened. Passed proposals stay escrowed until executed, expired or vetoed.
+    /// Settlement problems are logged rather than undoing the state change.
+    fn settle_deposit(&self, proposal: &mut GovernanceProposal, refund: bool) {
+        let (Some(deposits), Some(deposit)) = (&self.deposits, proposal.deposit.as_mut()) else {
+            return;
+        };
+        let settled = if refund { deposits.refund(deposit) } else { deposits.burn(deposit) };
+        if let Err(e) = settled {
+            eprintln!("Failed to settle deposit for proposal {}: {}", proposal.proposal_id, e);
+        }
+    }
+
+    /// Starts the timelock on a passed proposal
+    fn queue(&self, proposal: &mut GovernanceProposal) -> Result<(), String> {
+        proposal.transition(ProposalState::Queued)?;
+        let now = Utc::now();
+        proposal.queued_at = Some(now);
+        proposal.eta = Some(now + Duration::seconds(self.voting_rules.timelock_delay_secs));
+        Ok(())
+    }
+
+    /// Applies a queued proposal's parameter change once its timelock has expired
+    pub fn execute_proposal(&self, proposal_id: u64) -> Result<(), String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        if proposal.state != ProposalState::Queued {
+            return Err(format!("Proposal is {:?}, not queued for execution", proposal.state));
+        }
+        if proposal.in_timelock() {
+            return Err("Timelock has not expired".to_string());
+        }
+        if let Some(conflict) = &proposal.onchain.conflict {
+            return Err(format!("Conflicts with the on-chain outcome: {}", conflict));
+        }
+        if let Some(veto) = self.veto_in_force(proposal_id)? {
+            return Err(format!("Held by guardian veto {} until {}", veto.id, veto.expires_at));
+        }
+        let changes = self.apply_proposal(proposal)?;
+        proposal.transition(ProposalState::Executed)?;
+        proposal.executed_at = Some(Utc::now());
+        self.settle_deposit(proposal, true);
+        self.persist(&store)?;
+        drop(store);
+        self.audit(proposal_id, SYSTEM_ACTOR, AuditAction::Executed, "Parameters applied".to_string(), changes);
+        self.commit_outcomes()
+    }
+
+    /// Executes every queued proposal whose timelock has expired. Failed executions stay
+    /// queued and are retried until the execution window closes. Returns the executed ids.
+    pub fn execute_due_proposals(&self) -> Result<Vec<u64>, String> {
+        let due: Vec<u64> = {
+            let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+            store
+                .proposals
+                .values()
+                .filter(|p| p.state == ProposalState::Queued && !p.in_timelock())
+                .map(|p| p.proposal_id)
+                .collect()
+        };
+
+        let mut executed = Vec::new();
+        for proposal_id in due {
+            match self.execute_proposal(proposal_id) {
+                Ok(()) => executed.push(proposal_id),
+                Err(e) => eprintln!("Failed to execute proposal {}: {}", proposal_id, e),
+            }
+        }
+        Ok(executed)
+    }
+
+    /// Withdraws a draft or open proposal on behalf of its proposer. Only drafts get their
+    /// deposit back; withdrawing once voting has opened burns it, like missing quorum would.
+    /// Passed proposals can only be stopped by a guardian veto or a validator supermajority.
+    pub fn cancel_proposal(&self, proposal_id: u64, canceller: &str) -> Result<(), String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        if proposal.proposer != canceller {
+            return Err(format!("{} may not cancel proposal {}", canceller, proposal_id));
+        }
+        if !matches!(proposal.state, ProposalState::Draft | ProposalState::Active) {
+            return Err(format!("Proposal is {:?} and can no longer be withdrawn", proposal.state));
+        }
+        let refund = proposal.state == ProposalState::Draft;
+        proposal.transition(ProposalState::Cancelled)?;
+        self.settle_deposit(proposal, refund);
+        self.persist(&store)?;
+        let detail = "Withdrawn by the proposer".to_string();
+        self.audit(proposal_id, canceller, AuditAction::Cancelled, detail, Vec::new());
+        Ok(())
+    }
+
+    /// Holds a queued proposal back from execution on the signatures of enough guardians.
+    /// The hold lapses unless governance ratifies it, in which case the proposal is cancelled
+    /// by `apply_guardian_vetoes`.
+    pub fn guardian_veto(&self, proposal_id: u64, reason: String, signatures: &[String]) -> Result<EmergencyRecord, String> {
+        let guardians = self.guardians.as_ref().ok_or("Guardians are not configured")?;
+        let proposal = self.get_proposal(proposal_id)?;
+        if !proposal.in_timelock() {
+            return Err("Proposal is not in its timelock".to_string());
+        }
+        let record = guardians.authorize(EmergencyAction::VetoProposal { proposal_id }, reason, signatures)?;
+        let detail = format!("Emergency {} by {}: {}", record.id, record.signers.join(", "), record.reason);
+        self.audit(proposal_id, GUARDIAN_ACTOR, AuditAction::Vetoed, detail, Vec::new());
+        Ok(record)
+    }
+
+    /// The guardian veto currently holding `proposal_id`, if any
+    fn veto_in_force(&self, proposal_id: u64) -> Result<Option<EmergencyRecord>, String> {
+        let Some(guardians) = &self.guardians else {
+            return Ok(None);
+        };
+        Ok(guardians.veto_for(proposal_id)?.filter(|veto| veto.in_force(Utc::now())))
+    }
+
+    /// Cancels queued proposals whose guardian veto governance has ratified, burning their
+    /// deposits. Returns the cancelled ids.
+    pub fn apply_guardian_vetoes(&self) -> Result<Vec<u64>, String> {
+        let Some(guardians) = &self.guardians else {
+            return Ok(Vec::new());
+        };
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let mut cancelled = Vec::new();
+        for proposal in store.proposals.values_mut().filter(|p| p.state == ProposalState::Queued) {
+            let Some(veto) = guardians.veto_for(proposal.proposal_id)? else {
+                continue;
+            };
+            if veto.status == EmergencyStatus::Ratified {
+                proposal.transition(ProposalState::Cancelled)?;
+                self.settle_deposit(proposal, false);
+                cancelled.push((proposal.proposal_id, veto.id));
+            }
+        }
+        if !cancelled.is_empty() {
+            self.persist(&store)?;
+        }
+        drop(store);
+        for &(proposal_id, emergency_id) in &cancelled {
+            let detail = format!("Guardian veto {} ratified by governance", emergency_id);
+            self.audit(proposal_id, GUARDIAN_ACTOR, AuditAction::Cancelled, detail, Vec::new());
+        }
+        Ok(cancelled.into_iter().map(|(proposal_id, _)| proposal_id).collect())
+    }
+
+    /// Records `validator`'s support for cancelling a queued proposal during its timelock.
+    /// The proposal is cancelled once the supporting voting power reaches the supermajority.
+    /// Returns whether it was cancelled.
+    pub fn vote_to_cancel(&self, proposal_id: u64, validator: &str) -> Result<bool, String> {
+        let node = self.validator_registry.get_node(validator)?;
+        if !node.active {
+            return Err("Validator is not active".to_string());
+        }
+        let proposal = self.get_proposal(proposal_id)?;
+        let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+        let weight = strategy.validator_weight(&node, &self.governance_policies);
+        let total_power = self.calculate_voting_power(strategy.as_ref(), None)?;
+
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        if !proposal.in_timelock() {
+            return Err("Proposal is not in its timelock".to_string());
+        }
+        proposal.cancel_votes.insert(validator.to_string(), weight);
+        let support: u64 = proposal.cancel_votes.values().sum();
+        let cancelled = total_power > 0 && support * 100 >= self.voting_rules.supermajority_percent * total_power;
+        if cancelled {
+            proposal.transition(ProposalState::Cancelled)?;
+            self.settle_deposit(proposal, false);
+            println!("Proposal {} cancelled by {} of {} voting power", proposal_id, support, total_power);
+        }
+        self.persist(&store)?;
+        drop(store);
+        let detail = format!("Weight {}; {} of {} voting power in support", weight, support, total_power);
+        self.audit(proposal_id, validator, AuditAction::CancelVoted, detail, Vec::new());
+        if cancelled {
+            let detail = "Cancelled by validator supermajority".to_string();
+            self.audit(proposal_id, SYSTEM_ACTOR, AuditAction::Cancelled, detail, Vec::new());
+        }
+        Ok(cancelled)
+    }
+
+    /// Expires passed or queued proposals not executed within the execution window after
+    /// their timelock. Returns the expired proposal ids.
+    pub fn expire_stale_proposals(&self) -> Result<Vec<u64>, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let window = Duration::seconds(self.voting_rules.execution_window_secs);
+        let now = Utc::now();
+        let mut expired = Vec::new();
+        for proposal in store.proposals.values_mut() {
+            let mut start = proposal.eta.or(proposal.voting_ends_at).unwrap_or(proposal.created_at);
+            // A veto that lapsed unratified gives the proposal a fresh execution window
+            if let Some(guardians) = &self.guardians {
+                if let Some(veto) = guardians.veto_for(proposal.proposal_id)? {
+                    start = start.max(veto.resolved_at.unwrap_or(veto.expires_at));
+                }
+            }
+            let deadline = start + window;
+            if proposal.state.can_transition_to(ProposalState::Expired) && now >= deadline {
+                proposal.transition(ProposalState::Expired)?;
+                // It reached quorum; only a veto burns the deposit of a passed proposal
+                self.settle_deposit(proposal, true);
+                expired.push(proposal.proposal_id);
+            }
+        }
+        if !expired.is_empty() {
+            self.persist(&store)?;
+        }
+        drop(store);
+        for &proposal_id in &expired {
+            let detail = "Not executed within the execution window".to_string();
+            self.audit(proposal_id, SYSTEM_ACTOR, AuditAction::Expired, detail, Vec::new());
+        }
+        Ok(expired)
+    }
+
+    /// Links a proposal opened in the governance contract to its Rust counterpart: the one
+    /// this node mirrored, otherwise the oldest undecided proposal for the same change, or a
+    /// new active proposal when the change was proposed directly on-chain. Returns its id.
+    pub fn record_onchain_proposal(&self, param: &str, value: u64, proposer: &str) -> Result<u64, String> {
+        {
+            let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+            let matches = |p: &GovernanceProposal| p.is_change(param, value);
+            let linked = store
+                .proposals
+                .values()
+                .find(|p| matches(p) && p.holds_contract_slot())
+                .or_else(|| {
+                    store
+                        .proposals
+                        .values()
+                        .find(|p| matches(p) && !p.onchain.mirrored && p.approved().is_none())
+                })
+                .map(|p| p.proposal_id);
+            if let Some(proposal_id) = linked {
+                let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+                proposal.onchain.mirrored = true;
+                self.persist(&store)?;
+                return Ok(proposal_id);
+            }
+        }
+
+        // The contract takes no deposit, so neither does its mirror
+        let proposal_id = self.create_proposal(
+            proposer.to_string(),
+            "Proposed in the governance contract".to_string(),
+            vec![ProposalAction::new(param, value)],
+            None,
+            None,
+        )?;
+        self.activate_proposal(proposal_id)?;
+        self.update_onchain_status(proposal_id, |onchain| onchain.mirrored = true)?;
+        println!("Opened proposal {} for on-chain proposal {} = {}", proposal_id, param, value);
+        Ok(proposal_id)
+    }
+
+    /// Counts a vote cast in the governance contract. A vote from an active validator is also
+    /// cast, weighted, on the Rust side while voting is open. Returns the affected proposal id.
+    pub fn record_onchain_vote(&self, param: &str, value: u64, voter: &str, vote_for: bool) -> Result<Option<u64>, String> {
+        let proposal_id = {
+            let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+            let Some(proposal) = store
+                .proposals
+                .values_mut()
+                .find(|p| p.onchain.mirrored && p.onchain.approved.is_none() && p.is_change(param, value))
+            else {
+                return Ok(None);
+            };
+            if vote_for {
+                proposal.onchain.votes_for += 1;
+            } else {
+                proposal.onchain.votes_against += 1;
+            }
+            let proposal_id = proposal.proposal_id;
+            self.persist(&store)?;
+            proposal_id
+        };
+        let detail = format!("{} in the governance contract", if vote_for { "For" } else { "Against" });
+        self.audit(proposal_id, voter, AuditAction::Voted, detail, Vec::new());
+
+        let is_validator = self.validator_registry.get_node(voter).is_ok_and(|node| node.active);
+        if is_validator && self.get_proposal(proposal_id)?.state == ProposalState::Active {
+            let choice = if vote_for { VoteChoice::Yes } else { VoteChoice::No };
+            if let Err(e) = self.cast_vote(proposal_id, voter, choice) {
+                eprintln!("On-chain vote by {} not counted on proposal {}: {}", voter, proposal_id, e);
+            }
+        }
+        Ok(Some(proposal_id))
+    }
+
+    /// Applies the result of the contract's finalize-proposal. An undecided proposal follows
+    /// the chain; one Rust already decided is flagged as a conflict if the outcomes differ.
+    /// Returns the affected proposal id, if any.
+    pub fn record_onchain_outcome(&self, param: &str, value: u64, approved: bool) -> Result<Option<u64>, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let matches = |p: &GovernanceProposal| p.is_change(param, value);
+        let target = store
+            .proposals
+            .values()
+            .find(|p| matches(p) && p.holds_contract_slot())
+            .or_else(|| store.proposals.values().find(|p| matches(p) && p.approved().is_none()))
+            .map(|p| p.proposal_id);
+        let Some(proposal_id) = target else {
+            return Ok(None);
+        };
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        proposal.onchain.approved = Some(approved);
+
+        match proposal.approved() {
+            None => {
+                // The chain already ran the vote, so a draft skips straight through voting
+                if proposal.state == ProposalState::Draft {
+                    proposal.transition(ProposalState::Active)?;
+                }
+                let strategy = self.tally_strategy(&proposal.tally_strategy)?;
+                proposal.voting_power = self.calculate_voting_power(strategy.as_ref(), proposal.snapshot_id)?;
+                if approved {
+                    proposal.transition(ProposalState::Passed)?;
+                    self.queue(proposal)?;
+                } else {
+                    proposal.transition(ProposalState::Rejected)?;
+                    let decision = strategy.decide(&proposal.tally, proposal.voting_power, &self.voting_rules);
+                    self.settle_deposit(proposal, decision.quorum_met);
+                }
+            }
+            Some(decided) if decided != approved => {
+                let conflict = format!(
+                    "contract {} the change but governance left it {:?}",
+                    if approved { "approved" } else { "rejected" },
+                    proposal.state
+                );
+                eprintln!("Proposal {} conflicts with the governance contract: {}", proposal_id, conflict);
+                proposal.onchain.conflict = Some(conflict);
+            }
+            Some(_) => {}
+        }
+        let outcome = if approved { "approved" } else { "rejected" };
+        let detail = match &proposal.onchain.conflict {
+            Some(conflict) => format!("Contract {}; conflict: {}", outcome, conflict),
+            None => format!("Contract {}; now {:?}", outcome, proposal.state),
+        };
+        self.persist(&store)?;
+        println!("Proposal {} {} on-chain", proposal_id, outcome);
+        self.audit(proposal_id, ONCHAIN_ACTOR, AuditAction::Finalized, detail, Vec::new());
+        Ok(Some(proposal_id))
+    }
+
+    /// The oldest active single-change proposal not yet in the contract, if the contract's slot
+    /// is free. Multi-action proposals are decided in Rust alone.
+    pub fn next_to_mirror(&self) -> Result<Option<GovernanceProposal>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        if store.proposals.values().any(GovernanceProposal::holds_contract_slot) {
+            return Ok(None);
+        }
+        Ok(store
+            .proposals
+            .values()
+            .filter(|p| p.single_action().is_some())
+            .find(|p| p.state == ProposalState::Active && !p.onchain.mirrored && p.onchain.approved.is_none())
+            .cloned())
+    }
+
+    /// Mirrored proposals Rust has decided but the contract has not finalized yet, with no
+    /// call from this node outstanding
+    pub fn awaiting_onchain_outcome(&self) -> Result<Vec<GovernanceProposal>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store
+            .proposals
+            .values()
+            .filter(|p| p.onchain.mirrored && p.onchain.approved.is_none() && p.onchain.pending.is_none())
+            .filter(|p| p.onchain.step < ContractStep::Finalized && p.approved().is_some())
+            .cloned()
+            .collect())
+    }
+
+    /// Proposals with a contract call from this node awaiting confirmation
+    pub fn pending_contract_calls(&self) -> Result<Vec<GovernanceProposal>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.proposals.values().filter(|p| p.onchain.pending.is_some()).cloned().collect())
+    }
+
+    pub fn update_onchain_status(&self, proposal_id: u64, update: impl FnOnce(&mut OnchainStatus)) -> Result<(), String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let proposal = store.proposals.get_mut(&proposal_id).ok_or("Proposal not found")?;
+        update(&mut proposal.onchain);
+        self.persist(&store)
+    }
+
+    /// Proposals whose Rust and on-chain outcomes disagree
+    pub fn list_conflicts(&self) -> Result<Vec<GovernanceProposal>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.proposals.values().filter(|p| p.onchain.conflict.is_some()).cloned().collect())
+    }
+
+    /// Validator voting power plus delegated gBTCZ, read at `snapshot_id` when given, as
+    /// `strategy` weighs them
+    fn calculate_voting_power(&self, strategy: &dyn TallyStrategy, snapshot_id: Option<u64>) -> Result<u64, String> {
+        let nodes = self.validator_registry.nodes.lock().map_err(|_| "Mutex lock failed")?;
+
+        let total_power: u64 = nodes
+            .iter()
+            .filter(|(_, node)| node.active)
+            .map(|(_, node)| strategy.validator_weight(node, &self.governance_policies))
+            .sum();
+        drop(nodes);
+
+        match &self.delegations {
+            Some(delegations) => Ok(total_power + delegations.total_delegated_power(snapshot_id, |balance| strategy.token_weight(balance))?),
+            None => Ok(total_power),
+        }
+    }
+
+    /// Applies every action of the proposal and reports each parameter's value before and after
+    fn apply_proposal(&self, proposal: &GovernanceProposal) -> Result<Vec<ParameterChange>, String> {
+        let before = proposal
+            .actions
+            .iter()
+            .map(|action| self.parameters.get(&action.param).map(|spec| spec.value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let changes: Vec<(&str, u64)> = proposal
+            .actions
+            .iter()
+            .map(|action| (action.param.as_str(), action.value))
+            .collect();
+        self.parameters.apply_all(&changes)?;
+        proposal
+            .actions
+            .iter()
+            .zip(before)
+            .map(|(action, before)| {
+                Ok(ParameterChange {
+                    param: action.param.clone(),
+                    before,
+                    after: self.parameters.get(&action.param)?.value,
+                })
+            })
+            .collect()
+    }
+
+    pub fn get_proposal(&self, proposal_id: u64) -> Result<GovernanceProposal, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        store
+            .proposals
+            .get(&proposal_id)
+            .cloned()
+            .ok_or_else(|| "Proposal not found".to_string())
+    }
+
+    /// All proposals in id order, optionally only those in `state`
+    pub fn list_proposals(&self, state: Option<ProposalState>) -> Result<Vec<GovernanceProposal>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store
+            .proposals
+            .values()
+            .filter(|p| state.is_none_or(|state| p.state == state))
+            .cloned()
+            .collect())
+    }
+
+    /// Executed proposals in execution order; each new execution appends a leaf to the
+    /// outcome tree, so earlier roots stay reproducible
+    pub fn executed_outcomes(&self) -> Result<Vec<GovernanceOutcome>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let mut outcomes: Vec<GovernanceOutcome> = store
+            .proposals
+            .values()
+            .filter(|p| p.state == ProposalState::Executed)
+            .filter_map(|p| {
+                Some(GovernanceOutcome {
+                    proposal_id: p.proposal_id,
+                    actions: p.actions.clone(),
+                    tally: p.tally.clone(),
+                    executed_at: p.executed_at?,
+                })
+            })
+            .collect();
+        outcomes.sort_by_key(|outcome| (outcome.executed_at, outcome.proposal_id));
+        Ok(outcomes)
+    }
+
+    /// Hands the current outcome root to state anchoring
+    fn commit_outcomes(&self) -> Result<(), String> {
+        let Some(anchoring) = &self.anchoring else {
+            return Ok(());
+        };
+        let root = governance_root(&self.executed_outcomes()?);
+        println!("Committing governance outcome root {:?} to anchored state", root);
+        anchoring.commit_governance_root(root)
+    }
+
+    /// Inclusion proof for an executed proposal, against `root` when given (an earlier,
+    /// already anchored root) or the current outcome root otherwise
+    pub fn outcome_proof(&self, proposal_id: u64, root: Option<&str>) -> Result<GovernanceOutcomeProof, String> {
+        let outcomes = self.executed_outcomes()?;
+        let index = outcomes
+            .iter()
+            .position(|outcome| outcome.proposal_id == proposal_id)
+            .ok_or("Invalid or unexecuted proposal")?;
+        let Some(root) = root else {
+            return GovernanceOutcomeProof::new(&outcomes, index);
+        };
+        (index + 1..=outcomes.len())
+            .find(|&len| governance_root(&outcomes[..len]).as_deref() == Some(root))
+            .map(|len| GovernanceOutcomeProof::new(&outcomes[..len], index))
+            .unwrap_or_else(|| Err(format!("Proposal {} is not committed to governance root {}", proposal_id, root)))
+    }
+
+    /// Verifies that an anchored state summary commits to the execution of `proposal_id` and
+    /// returns the inclusion proof, which anyone can re-check against the summary alone
+    pub fn validate_l1_proposal(
+        &self,
+        state_summary: &L2StateSummary,
+        proposal_id: u64,
+    ) -> Result<GovernanceOutcomeProof, String> {
+        if let Some(anchoring) = &self.anchoring {
+            if !anchoring.is_anchored(state_summary) {
+                return Err("State summary has not been anchored".to_string());
+            }
+        }
+        let root = state_summary
+            .governance_root
+            .as_deref()
+            .ok_or("State summary carries no governance commitment")?;
+        let proof = self.outcome_proof(proposal_id, Some(root))?;
+        proof.verify(state_summary)?;
+
+        println!(
+            "Proposal {} verified against anchored state at height {} (governance root {})",
+            proposal_id, state_summary.block_height, root
+        );
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::state_anchoring::StateAnchoring;
+    use crate::clarity::transaction::{StacksNetwork, StacksSigner};
+    use crate::governance::guardians::emergency_digest;
+    use crate::governance::parameters::GovernableModule;
+    use crate::governance::token::GovernanceToken;
+    use crate::governance::validator_rewards_tokenomics::ValidatorRewards;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zook_governance_{}_{}", std::process::id(), name))
+    }
+
+    /// Governance with two validators, `full` (voting power 100) and `half` (50), plus the
+    /// gBTCZ balances, deposit and delegations a test asks for
+    struct Governance {
+        name: String,
+        balances: Vec<(&'static str, u64)>,
+        deposit: Option<u64>,
+        delegations: Vec<(&'static str, &'static str)>, // Delegator, validator
+    }
+
+    /// Built governance and the token its deposits and delegations draw on
+    struct Fixture {
+        engine: ProposalEngine,
+        token: Arc<Mutex<GovernanceToken>>,
+    }
+
+    fn governance(name: &str) -> Governance {
+        Governance {
+            name: name.to_string(),
+            balances: Vec::new(),
+            deposit: None,
+            delegations: Vec::new(),
+        }
+    }
+
+    impl Governance {
+        fn with_balance(mut self, account: &'static str, amount: u64) -> Self {
+            self.balances.push((account, amount));
+            self
+        }
+
+        fn with_deposit(mut self, amount: u64) -> Self {
+            self.deposit = Some(amount);
+            self
+        }
+
+        fn with_delegation(mut self, delegator: &'static str, validator: &'static str) -> Self {
+            self.delegations.push((delegator, validator));
+            self
+        }
+
+        fn build(self) -> Fixture {
+            let registry_path = temp_path(&format!("{}_validators.json", self.name));
+            let proposals_path = temp_path(&format!("{}_proposals.json", self.name));
+            let _ = fs::remove_file(&registry_path);
+            let _ = fs::remove_file(&proposals_path);
+
+            let registry = ValidatorRegistry::new(1_000, registry_path);
+            registry.register_node("full".to_string(), 1_000_000).unwrap();
+            registry.register_node("half".to_string(), 500_000).unwrap();
+            let registry = Arc::new(registry);
+            let policies = Arc::new(GovernanceValidatorPolicies::new(1_000_000, 80, 70));
+            let parameters = ParameterRegistry::new()
+                .register(Arc::new(StateAnchoring::new()))
+                .register(Arc::new(Mutex::new(ValidatorRewards::new(100, temp_path("rewards.json")))))
+                .register(policies.clone());
+            let mut engine = ProposalEngine::new(Arc::new(parameters), registry.clone(), policies, proposals_path).unwrap();
+
+            let mut token = GovernanceToken::new("gBTCZ", 0);
+            for (account, amount) in self.balances {
+                token.mint(account, amount).unwrap();
+            }
+            let token = Arc::new(Mutex::new(token));
+            if let Some(amount) = self.deposit {
+                engine = engine.with_deposits(Arc::new(ProposalDeposits::new(token.clone(), amount)));
+            }
+            if !self.delegations.is_empty() {
+                let delegations_path = temp_path(&format!("{}_delegations.json", self.name));
+                let _ = fs::remove_file(&delegations_path);
+                let delegations = DelegationRegistry::new(token.clone(), registry, delegations_path).unwrap();
+                for (delegator, validator) in self.delegations {
+                    delegations.delegate(delegator, validator).unwrap();
+                }
+                engine = engine.with_delegations(Arc::new(delegations));
+            }
+            Fixture { engine, token }
+        }
+    }
+
+    impl Fixture {
+        fn balance(&self, account: &str) -> u64 {
+            self.token.lock().unwrap().get_balance(account)
+        }
+    }
+
+    fn engine(name: &str) -> ProposalEngine {
+        governance(name).build().engine
+    }
+
+    /// A second engine over the same store, as after a restart
+    fn reopen(engine: &ProposalEngine) -> Result<ProposalEngine, String> {
+        ProposalEngine::new(
+            engine.parameters.clone(),
+            engine.validator_registry.clone(),
+            engine.governance_policies.clone(),
+            engine.storage_path.clone(),
+        )
+    }
+
+    fn reward_rate(engine: &ProposalEngine) -> u64 {
+        engine.parameters.get("reward_rate").unwrap().value
+    }
+
+    fn end_voting(engine: &ProposalEngine, proposal_id: u64) {
+        let mut store = engine.store.lock().unwrap();
+        store.proposals.get_mut(&proposal_id).unwrap().voting_ends_at = Some(Utc::now() - Duration::seconds(1));
+    }
+
+    fn submit(engine: &ProposalEngine, value: u64) -> u64 {
+        let id = engine
+            .submit_proposal("alice".into(), "Adjust rewards".into(), vec![ProposalAction::new("reward_rate", value)])
+            .unwrap();
+        engine.activate_proposal(id).unwrap();
+        id
+    }
+
+    /// Submits a proposal `full` alone carries, leaving it queued behind the timelock
+    fn queued(engine: &ProposalEngine, value: u64) -> u64 {
+        let id = submit(engine, value);
+        engine.cast_vote(id, "full", VoteChoice::Yes).unwrap();
+        end_voting(engine, id);
+        assert_eq!(engine.finalize_proposal(id).unwrap(), ProposalState::Queued);
+        id
+    }
+
+    fn end_timelock(engine: &ProposalEngine, proposal_id: u64) {
+        let mut store = engine.store.lock().unwrap();
+        store.proposals.get_mut(&proposal_id).unwrap().eta = Some(Utc::now() - Duration::seconds(1));
+    }
+
+    fn signer(byte: u8) -> StacksSigner {
+        StacksSigner::from_hex(&hex::encode([byte; 32]), StacksNetwork::Testnet).unwrap()
+    }
+
+    /// Council of guardians 1 and 2, both of whom must sign
+    fn guardians(name: &str) -> Arc<GuardianCouncil> {
+        let path = temp_path(&format!("{}_guardians.json", name));
+        let _ = fs::remove_file(&path);
+        let addresses = (1..=2).map(|byte| signer(byte).address()).collect();
+        Arc::new(GuardianCouncil::new(addresses, 2, 3_600, path).unwrap())
+    }
+
+    fn veto_signatures(guardians: &GuardianCouncil, proposal_id: u64, reason: &str) -> Vec<String> {
+        let digest = emergency_digest(guardians.next_id().unwrap(), &EmergencyAction::VetoProposal { proposal_id }, reason);
+        (1..=2).map(|byte| hex::encode(signer(byte).sign(&digest).unwrap())).collect()
+    }
+
+    #[test]
+    fn test_ids_are_monotonic_across_restarts() {
+        let engine = engine("ids");
+        let first = submit(&engine, 1);
+        let second = submit(&engine, 2);
+        assert!(engine.cancel_proposal(first, "mallory").is_err());
+        engine.cancel_proposal(first, "alice").unwrap();
+
+        let reopened = reopen(&engine).unwrap();
+        assert_eq!(reopened.get_proposal(first).unwrap().state, ProposalState::Cancelled);
+        let third = reopened
+            .submit_proposal("bob".into(), "Later".into(), vec![ProposalAction::new("reward_rate", 3)])
+            .unwrap();
+        assert_eq!((first, second, third), (1, 2, 3));
+    }
+
+    #[test]
+    fn test_unreadable_store_is_refused() {
+        let engine = engine("unreadable");
+        submit(&engine, 1);
+        fs::write(&engine.storage_path, "{ truncated").unwrap();
+
+        let reopened = reopen(&engine);
+        assert!(reopened.err().unwrap().starts_with("Unreadable proposals"));
+        // The damaged file is left for an operator to recover
+        assert_eq!(fs::read_to_string(&engine.storage_path).unwrap(), "{ truncated");
+    }
+
+    #[test]
+    fn test_unexecuted_proposals_expire() {
+        let rules = VotingRules { execution_window_secs: 0, ..VotingRules::default() };
+        let engine = engine("expires").with_voting_rules(rules);
+        let id = queued(&engine, 5);
+        assert!(engine.expire_stale_proposals().unwrap().is_empty());
+
+        end_timelock(&engine, id);
+        assert_eq!(engine.expire_stale_proposals().unwrap(), vec![id]);
+        assert_eq!(engine.get_proposal(id).unwrap().state, ProposalState::Expired);
+        assert!(engine.execute_proposal(id).is_err());
+    }
+
+    #[test]
+    fn test_guardian_veto_holds_proposal_until_ratified() {
+        let guardians = guardians("veto");
+        let engine = engine("veto").with_guardians(guardians.clone());
+        let id = queued(&engine, 5);
+        // Once passed, the proposer can no longer withdraw it
+        assert!(engine.cancel_proposal(id, "alice").is_err());
+
+        let signatures = veto_signatures(&guardians, id, "malicious upgrade");
+        assert!(engine.guardian_veto(id, "malicious upgrade".into(), &signatures[..1]).is_err());
+        let veto = engine.guardian_veto(id, "malicious upgrade".into(), &signatures).unwrap();
+        end_timelock(&engine, id);
+        assert!(engine.execute_proposal(id).unwrap_err().contains("guardian veto"));
+        assert!(engine.apply_guardian_vetoes().unwrap().is_empty());
+
+        guardians.set_governance_parameter("ratify_emergency", veto.id).unwrap();
+        assert_eq!(engine.apply_guardian_vetoes().unwrap(), vec![id]);
+        assert_eq!(engine.get_proposal(id).unwrap().state, ProposalState::Cancelled);
+        assert_eq!(reward_rate(&engine), 100);
+
+        let late = queued(&engine, 6);
+        end_timelock(&engine, late);
+        let signatures = veto_signatures(&guardians, late, "too late");
+        assert_eq!(
+            engine.guardian_veto(late, "too late".into(), &signatures).unwrap_err(),
+            "Proposal is not in its timelock"
+        );
+    }
+
+    #[test]
+    fn test_supermajority_cancels_queued_proposal() {
+        let engine = engine("supermajority");
+        let id = queued(&engine, 5);
+
+        // 100 of 150 voting power is just short of the 67% supermajority
+        assert!(!engine.vote_to_cancel(id, "full").unwrap());
+        assert!(engine.vote_to_cancel(id, "half").unwrap());
+        assert_eq!(engine.get_proposal(id).unwrap().state, ProposalState::Cancelled);
+        assert!(engine.execute_due_proposals().unwrap().is_empty());
+        assert_eq!(reward_rate(&engine), 100);
+    }
+
+    #[test]
+    fn test_dry_run_previews_without_applying() {
+        let engine = engine("dry_run");
+        let actions = vec![ProposalAction::new("minimum_stake", 2_000_000), ProposalAction::new("reward_rate", 150)];
+        let id = engine
+            .submit_proposal("alice".into(), "Raise the bar".into(), actions.clone())
+            .unwrap();
+
+        let report = engine.dry_run_proposal(id).unwrap();
+        assert_eq!(report.changes[0], ParameterChange { param: "minimum_stake".into(), before: 1_000_000, after: 2_000_000 });
+        // `half` already falls short of the current minimum, so only `full` newly drops below
+        assert_eq!(report.below_minimum_stake, vec!["full".to_string()]);
+        assert_eq!((report.total_voting_power_before, report.total_voting_power_after), (150, 75));
+        assert_eq!((report.next_rewards_before, report.next_rewards_after), (100, 0));
+        let full = &report.validators[0];
+        assert_eq!((full.address.as_str(), full.eligible_before, full.eligible_after), ("full", true, false));
+
+        assert_eq!(engine.dry_run(&actions, None).unwrap().validators, report.validators);
+        assert!(engine.dry_run(&[ProposalAction::new("minimum_stake", 0)], None).is_err());
+        assert_eq!(engine.parameters.get("minimum_stake").unwrap().value, 1_000_000);
+        assert_eq!(reward_rate(&engine), 100);
+    }
+
+    #[test]
+    fn test_executed_outcomes_verify_against_anchored_state() {
+        let anchoring = Arc::new(StateAnchoring::new());
+        let engine = engine("anchored").with_anchoring(anchoring.clone());
+        let first = queued(&engine, 200);
+        end_timelock(&engine, first);
+        engine.execute_proposal(first).unwrap();
+
+        let anchor = |height: u64| {
+            anchoring
+                .anchor_state(L2StateSummary {
+                    block_height: height,
+                    state_root: format!("root-{}", height),
+                    total_transactions: 0,
+                    timestamp: Utc::now(),
+                    compliance: true,
+                    governance_root: None,
+                    treasury_balance: None,
+                })
+                .unwrap();
+            anchoring.get_latest_anchored_state().unwrap()
+        };
+        let early = anchor(1);
+        assert!(early.governance_root.is_some());
+        engine.validate_l1_proposal(&early, first).unwrap();
+
+        let second = queued(&engine, 300);
+        assert!(engine.validate_l1_proposal(&early, second).is_err()); // Not executed yet
+        end_timelock(&engine, second);
+        engine.execute_proposal(second).unwrap();
+        let later = anchor(2);
+
+        // Both outcomes are provable against the later anchor; the first still against the earlier one
+        let proof = engine.validate_l1_proposal(&later, second).unwrap();
+        assert_eq!(proof.outcome.actions, vec![ProposalAction::new("reward_rate", 300)]);
+        assert_eq!(proof.outcome.tally, ProposalTally { yes: 100, no: 0, abstain: 0 });
+        engine.validate_l1_proposal(&later, first).unwrap();
+        engine.validate_l1_proposal(&early, first).unwrap();
+        assert!(engine.validate_l1_proposal(&early, second).is_err());
+
+        // The proof stands on its own, and a forged outcome does not verify
+        proof.verify(&later).unwrap();
+        assert!(proof.verify(&early).is_err());
+        let mut forged = proof.clone();
+        forged.outcome.actions[0].value = 999;
+        assert!(forged.verify(&later).is_err());
+
+        // Summaries that were never anchored are refused
+        let mut unanchored = later.clone();
+        unanchored.block_height = 3;
+        assert_eq!(engine.validate_l1_proposal(&unanchored, first).unwrap_err(), "State summary has not been anchored");
+    }
+
+    #[test]
+    fn test_multi_action_proposal_applies_atomically() {
+        let engine = engine("actions");
+        let actions = vec![
+            ProposalAction::new("reward_rate", 150),
+            ProposalAction::new("anchoring_frequency", 600),
+        ];
+        // Every action is checked before the proposal is recorded
+        let invalid = vec![ProposalAction::new("reward_rate", 150), ProposalAction::new("anchoring_frequency", 1)];
+        assert!(engine.submit_proposal("alice".into(), "Bad".into(), invalid).is_err());
+        let repeated = vec![ProposalAction::new("reward_rate", 150), ProposalAction::new("reward_rate", 160)];
+        assert!(engine.submit_proposal("alice".into(), "Twice".into(), repeated).unwrap_err().contains("more than once"));
+        assert!(engine.submit_proposal("alice".into(), "Empty".into(), Vec::new()).is_err());
+
+        let id = engine.submit_proposal("alice".into(), "Rebalance".into(), actions.clone()).unwrap();
+        engine.activate_proposal(id).unwrap();
+        engine.cast_vote(id, "full", VoteChoice::Yes).unwrap();
+        end_voting(&engine, id);
+        engine.finalize_proposal(id).unwrap();
+        end_timelock(&engine, id);
+        // Multi-action proposals never occupy the contract's single-change slot
+        assert!(engine.next_to_mirror().unwrap().is_none());
+
+        engine.execute_proposal(id).unwrap();
+        assert_eq!(reward_rate(&engine), 150);
+        assert_eq!(engine.parameters.get("anchoring_frequency").unwrap().value, 600);
+        assert_eq!(engine.executed_outcomes().unwrap()[0].actions, actions);
+    }
+
+    #[test]
+    fn test_legacy_single_change_is_migrated() {
+        let engine = engine("legacy");
+        let id = submit(&engine, 5);
+        let stored = fs::read_to_string(&engine.storage_path).unwrap();
+        let legacy = stored.replace(r#""actions":[{"param":"reward_rate","value":5}]"#, r#""param":"reward_rate","value":5"#);
+        assert_ne!(stored, legacy);
+        fs::write(&engine.storage_path, legacy).unwrap();
+
+        let reopened = reopen(&engine).unwrap();
+        assert_eq!(reopened.get_proposal(id).unwrap().actions, vec![ProposalAction::new("reward_rate", 5)]);
+    }
+
+    #[test]
+    fn test_lifecycle_is_audited_with_parameter_changes() {
+        let audit_path = temp_path("audit.jsonl");
+        let _ = fs::remove_file(&audit_path);
+        let audit_log = Arc::new(GovernanceAuditLog::new(audit_path).unwrap());
+        let engine = engine("audited").with_audit_log(audit_log.clone());
+
+        let id = queued(&engine, 175);
+        end_timelock(&engine, id);
+        engine.execute_proposal(id).unwrap();
+        let withdrawn = submit(&engine, 5);
+        engine.cancel_proposal(withdrawn, "alice").unwrap();
+
+        let actions: Vec<AuditAction> = audit_log.query(Some(id), None).unwrap().iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            vec![AuditAction::Submitted, AuditAction::Activated, AuditAction::Voted, AuditAction::Finalized, AuditAction::Executed]
+        );
+        let executed = audit_log.query(Some(id), Some(SYSTEM_ACTOR)).unwrap().pop().unwrap();
+        assert_eq!(executed.changes, vec![ParameterChange { param: "reward_rate".into(), before: 100, after: 175 }]);
+        assert_eq!(audit_log.query(None, Some("alice")).unwrap().len(), 3);
+        assert_eq!(audit_log.verify().unwrap(), 8);
+    }
+
+    /// Voting power, quorum and tally strategies
+    mod voting {
+        use super::*;
+
+        #[test]
+        fn test_weighted_vote_passes_and_executes() {
+            let engine = engine("passes");
+            let id = engine
+                .submit_proposal("alice".into(), "Raise rewards".into(), vec![ProposalAction::new("reward_rate", 250)])
+                .unwrap();
+            assert!(engine.cast_vote(id, "full", VoteChoice::Yes).unwrap_err().contains("Draft"));
+            engine.activate_proposal(id).unwrap();
+
+            engine.cast_vote(id, "half", VoteChoice::Yes).unwrap();
+            // A validator may change its vote; only the latest one counts
+            let tally = engine.cast_vote(id, "half", VoteChoice::No).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 0, no: 50, abstain: 0 });
+            let tally = engine.cast_vote(id, "full", VoteChoice::Yes).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 100, no: 50, abstain: 0 });
+            assert!(engine.cast_vote(id, "unknown", VoteChoice::Yes).is_err());
+
+            assert_eq!(engine.finalize_proposal(id).unwrap_err(), "Voting period has not ended");
+            end_voting(&engine, id);
+            assert_eq!(engine.finalize_proposal(id).unwrap(), ProposalState::Queued);
+            assert!(engine.cast_vote(id, "full", VoteChoice::No).is_err());
+
+            // Nothing changes until the timelock expires
+            assert_eq!(engine.execute_proposal(id).unwrap_err(), "Timelock has not expired");
+            assert!(engine.execute_due_proposals().unwrap().is_empty());
+            end_timelock(&engine, id);
+            assert_eq!(engine.execute_due_proposals().unwrap(), vec![id]);
+            assert_eq!(reward_rate(&engine), 250);
+
+            let proposal = engine.get_proposal(id).unwrap();
+            assert_eq!(proposal.state, ProposalState::Executed);
+            assert_eq!(proposal.voting_power, 150);
+            assert!(proposal.executed_at.is_some());
+            assert!(engine.cancel_proposal(id, "alice").is_err());
+        }
+
+        #[test]
+        fn test_rejected_without_quorum_or_majority() {
+            let rules = VotingRules { quorum_percent: 70, ..VotingRules::default() };
+            let engine = engine("rejects").with_voting_rules(rules);
+
+            // 50 of 150 voting power is below the 70% quorum
+            let quorum = submit(&engine, 1);
+            engine.cast_vote(quorum, "half", VoteChoice::Yes).unwrap();
+            end_voting(&engine, quorum);
+            assert_eq!(engine.finalize_proposal(quorum).unwrap(), ProposalState::Rejected);
+
+            let majority = submit(&engine, 2);
+            engine.cast_vote(majority, "full", VoteChoice::No).unwrap();
+            engine.cast_vote(majority, "half", VoteChoice::Yes).unwrap();
+            end_voting(&engine, majority);
+            assert_eq!(engine.finalize_proposal(majority).unwrap(), ProposalState::Rejected);
+
+            assert!(engine.execute_proposal(majority).is_err());
+            assert_eq!(reward_rate(&engine), 100);
+            assert_eq!(engine.list_proposals(Some(ProposalState::Rejected)).unwrap().len(), 2);
+        }
+
+        #[test]
+        fn test_proposals_are_tallied_by_their_strategy() {
+            let engine = engine("strategies").with_required_strategy("anchoring_frequency", tallying::SUPERMAJORITY);
+            let simple = submit(&engine, 5);
+            let required = engine
+                .submit_proposal("alice".into(), "Anchor less often".into(), vec![ProposalAction::new("anchoring_frequency", 600)])
+                .unwrap();
+            assert_eq!(engine.get_proposal(required).unwrap().tally_strategy, tallying::SUPERMAJORITY);
+            engine.activate_proposal(required).unwrap();
+
+            // 100 yes to 50 no is a simple majority but short of the 67% supermajority
+            for id in [simple, required] {
+                engine.cast_vote(id, "full", VoteChoice::Yes).unwrap();
+                engine.cast_vote(id, "half", VoteChoice::No).unwrap();
+                end_voting(&engine, id);
+            }
+            assert_eq!(engine.finalize_proposal(simple).unwrap(), ProposalState::Queued);
+            assert_eq!(engine.finalize_proposal(required).unwrap(), ProposalState::Rejected);
+
+            let mixed = vec![ProposalAction::new("reward_rate", 5), ProposalAction::new("anchoring_frequency", 600)];
+            let err = engine
+                .submit_proposal_with_strategy("alice".into(), "Mixed".into(), mixed, tallying::QUADRATIC)
+                .unwrap_err();
+            assert!(err.contains("requires the supermajority"));
+            let actions = vec![ProposalAction::new("reward_rate", 6)];
+            assert!(engine.submit_proposal_with_strategy("alice".into(), "Unknown".into(), actions, "ranked").is_err());
+        }
+    }
+
+    /// gBTCZ voting power read at the snapshot taken when voting opens
+    mod snapshots {
+        use super::*;
+
+        #[test]
+        fn test_delegated_gbtcz_counts_and_can_be_overridden() {
+            let engine = governance("delegation")
+                .with_balance("alice", 300)
+                .with_balance("bob", 100)
+                .with_delegation("alice", "half")
+                .with_delegation("bob", "half")
+                .build()
+                .engine;
+
+            let id = submit(&engine, 7);
+            let tally = engine.cast_vote(id, "half", VoteChoice::Yes).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 450, no: 0, abstain: 0 });
+
+            // Bob's gBTCZ leaves the validator's vote and follows his own
+            let tally = engine.cast_delegator_vote(id, "bob", VoteChoice::No).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 350, no: 100, abstain: 0 });
+            assert!(engine.cast_delegator_vote(id, "carol", VoteChoice::No).is_err());
+
+            end_voting(&engine, id);
+            assert_eq!(engine.finalize_proposal(id).unwrap(), ProposalState::Queued);
+            assert_eq!(engine.get_proposal(id).unwrap().voting_power, 550);
+        }
+
+        #[test]
+        fn test_transferred_gbtcz_cannot_vote_twice() {
+            let fixture = governance("revote")
+                .with_balance("alice", 300)
+                .with_balance("carol", 100)
+                .with_delegation("alice", "half")
+                .with_delegation("carol", "full")
+                .build();
+            let (engine, token) = (&fixture.engine, &fixture.token);
+
+            let id = submit(engine, 7);
+            let tally = engine.cast_vote(id, "half", VoteChoice::Yes).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 350, no: 0, abstain: 0 });
+
+            // Alice's tokens move to Carol after voting opened; they stay with Alice's vote
+            token.lock().unwrap().transfer("alice", "carol", 300).unwrap();
+            let tally = engine.cast_vote(id, "full", VoteChoice::No).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 350, no: 200, abstain: 0 });
+            let tally = engine.cast_delegator_vote(id, "carol", VoteChoice::No).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 350, no: 200, abstain: 0 });
+
+            // A fresh holder who received everything after the snapshot has nothing to vote
+            token.lock().unwrap().transfer("carol", "dave", 400).unwrap();
+            engine.delegations.as_ref().unwrap().delegate("dave", "full").unwrap();
+            assert_eq!(
+                engine.cast_delegator_vote(id, "dave", VoteChoice::No).unwrap_err(),
+                "Delegator held no gBTCZ when voting opened"
+            );
+
+            end_voting(engine, id);
+            engine.finalize_proposal(id).unwrap();
+            let proposal = engine.get_proposal(id).unwrap();
+            assert_eq!(proposal.tally, ProposalTally { yes: 350, no: 200, abstain: 0 });
+            assert_eq!(proposal.voting_power, 550);
+
+            // Later proposals see the new balances
+            let next = submit(engine, 8);
+            let tally = engine.cast_vote(next, "full", VoteChoice::Yes).unwrap();
+            assert_eq!(tally, ProposalTally { yes: 500, no: 0, abstain: 0 });
+        }
+    }
+
+    /// Escrowed proposal deposits and how each outcome settles them
+    mod deposits {
+        use super::*;
+        use crate::governance::deposits::DepositStatus;
+
+        #[test]
+        fn test_deposits_refund_at_quorum_and_burn_otherwise() {
+            let fixture = governance("deposits").with_balance("alice", 300).with_deposit(100).build();
+            let engine = &fixture.engine;
+
+            // Quorum reached but rejected: refunded
+            let refunded = submit(engine, 1);
+            engine.cast_vote(refunded, "full", VoteChoice::No).unwrap();
+            end_voting(engine, refunded);
+            assert_eq!(engine.finalize_proposal(refunded).unwrap(), ProposalState::Rejected);
+            assert_eq!(fixture.balance("alice"), 300);
+
+            // Nobody voted: burned
+            let ignored = submit(engine, 2);
+            end_voting(engine, ignored);
+            engine.finalize_proposal(ignored).unwrap();
+            assert_eq!(fixture.balance("alice"), 200);
+
+            // Cancelled by a validator supermajority: burned
+            let vetoed = queued(engine, 3);
+            engine.vote_to_cancel(vetoed, "full").unwrap();
+            assert!(engine.vote_to_cancel(vetoed, "half").unwrap());
+            let deposit = engine.get_proposal(vetoed).unwrap().deposit.unwrap();
+            assert_eq!(deposit.status, DepositStatus::Burned);
+            assert_eq!(fixture.balance("alice"), 100);
+
+            // Without enough gBTCZ the proposal is not recorded
+            let escrowed = vec![ProposalAction::new("reward_rate", 4)];
+            engine.submit_proposal("alice".into(), "Escrowed".into(), escrowed).unwrap();
+            assert!(engine
+                .submit_proposal("alice".into(), "Broke".into(), vec![ProposalAction::new("reward_rate", 5)])
+                .unwrap_err()
+                .contains("Insufficient"));
+            assert_eq!(engine.list_proposals(None).unwrap().len(), 4);
+            assert_eq!(fixture.token.lock().unwrap().total_supply, 100);
+        }
+
+        #[test]
+        fn test_withdrawal_refunds_drafts_and_burns_open_proposals() {
+            let fixture = governance("withdrawals").with_balance("alice", 300).with_deposit(100).build();
+            let engine = &fixture.engine;
+
+            let draft = engine
+                .submit_proposal("alice".into(), "Draft".into(), vec![ProposalAction::new("reward_rate", 1)])
+                .unwrap();
+            engine.cancel_proposal(draft, "alice").unwrap();
+            assert_eq!(engine.get_proposal(draft).unwrap().deposit.unwrap().status, DepositStatus::Refunded);
+            assert_eq!(fixture.balance("alice"), 300);
+
+            // Withdrawing after voting opens cannot dodge the quorum penalty
+            let open = submit(engine, 2);
+            engine.cancel_proposal(open, "alice").unwrap();
+            assert_eq!(engine.get_proposal(open).unwrap().deposit.unwrap().status, DepositStatus::Burned);
+            assert_eq!(fixture.balance("alice"), 200);
+            assert_eq!(fixture.token.lock().unwrap().total_supply, 200);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::governance::proposal_engine::{ProposalTally, VotingRules};
+use crate::governance::validator_policies::{GovernanceValidatorPolicies, VotingPowerAdjustment};
+use crate::validator::node_registration::ValidatorNode;
+
+pub const SIMPLE_MAJORITY: &str = "simple-majority";
+pub const SUPERMAJORITY: &str = "supermajority";
+pub const COMPLIANCE_WEIGHTED: &str = "compliance-weighted";
+pub const QUADRATIC: &str = "quadratic";
+
+/// Whether a closed vote reached quorum and whether it passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TallyDecision {
+    pub quorum_met: bool,
+    pub approved: bool,
+}
+
+impl TallyDecision {
+    pub fn passed(&self) -> bool {
+        self.quorum_met && self.approved
+    }
+}
+
+/// How votes are weighted and how a closed vote is decided. Validator votes weigh
+/// `validator_weight` plus `token_weight` of each delegator's gBTCZ; delegator votes weigh
+/// `token_weight` of their own balance. Total voting power for quorum is weighed the same way.
+pub trait TallyStrategy: Send + Sync {
+    /// Name proposals refer to the strategy by
+    fn name(&self) -> &'static str;
+
+    fn validator_weight(&self, node: &ValidatorNode, policies: &GovernanceValidatorPolicies) -> u64 {
+        policies.adjust_voting_power(node)
+    }
+
+    fn token_weight(&self, balance: u64) -> u64 {
+        balance
+    }
+
+    fn decide(&self, tally: &ProposalTally, total_power: u64, rules: &VotingRules) -> TallyDecision {
+        TallyDecision {
+            quorum_met: quorum_met(tally, total_power, rules.quorum_percent),
+            approved: share_met(tally, rules.approval_percent),
+        }
+    }
+}
+
+/// Turnout, abstentions included, reaches `quorum_percent` of `total_power`
+pub fn quorum_met(tally: &ProposalTally, total_power: u64, quorum_percent: u64) -> bool {
+    total_power > 0 && tally.turnout() * 100 >= quorum_percent * total_power
+}
+
+/// Yes votes make up at least `percent` of yes + no
+fn share_met(tally: &ProposalTally, percent: u64) -> bool {
+    tally.yes > 0 && tally.yes * 100 >= percent * (tally.yes + tally.no)
+}
+
+/// Quorum and the configured approval share, weighted by policy-adjusted validator power
+/// and gBTCZ one for one
+pub struct SimpleMajority;
+
+impl TallyStrategy for SimpleMajority {
+    fn name(&self) -> &'static str {
+        SIMPLE_MAJORITY
+    }
+}
+
+/// Quorum and the supermajority share of yes + no votes
+pub struct Supermajority;
+
+impl TallyStrategy for Supermajority {
+    fn name(&self) -> &'static str {
+        SUPERMAJORITY
+    }
+
+    fn decide(&self, tally: &ProposalTally, total_power: u64, rules: &VotingRules) -> TallyDecision {
+        TallyDecision {
+            quorum_met: quorum_met(tally, total_power, rules.quorum_percent),
+            approved: share_met(tally, rules.supermajority_percent),
+        }
+    }
+}
+
+/// Like `SimpleMajority`, but validators below the compliance threshold are further scaled
+/// down by their compliance through `VotingPowerAdjustment`
+pub struct ComplianceWeighted;
+
+impl TallyStrategy for ComplianceWeighted {
+    fn name(&self) -> &'static str {
+        COMPLIANCE_WEIGHTED
+    }
+
+    fn validator_weight(&self, node: &ValidatorNode, policies: &GovernanceValidatorPolicies) -> u64 {
+        let threshold = policies.get_policy().map_or(100, |policy| policy.compliance_threshold);
+        let adjusted = ValidatorNode {
+            voting_power: policies.adjust_voting_power(node),
+            ..node.clone()
+        };
+        VotingPowerAdjustment::adjust(&adjusted, threshold)
+    }
+}
+
+/// Like `SimpleMajority`, but each holder's gBTCZ counts as its square root, so large
+/// holders gain influence more slowly than their balance
+pub struct Quadratic;
+
+impl TallyStrategy for Quadratic {
+    fn name(&self) -> &'static str {
+        QUADRATIC
+    }
+
+    fn token_weight(&self, balance: u64) -> u64 {
+        balance.isqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn node(compliance_percentage: u64) -> ValidatorNode {
+        ValidatorNode {
+            address: "validator".to_string(),
+            staked_btcz: 1_000_000,
+            registered_at: Utc::now(),
+            active: true,
+            activity_percentage: 100,
+            compliance_percentage,
+            voting_power: 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_strategies_weigh_and_decide_differently() {
+        let policies = GovernanceValidatorPolicies::new(1_000_000, 80, 80);
+        let rules = VotingRules::default();
+
+        // 40% compliance halves policy-adjusted power; the compliance rule scales it again
+        assert_eq!(SimpleMajority.validator_weight(&node(40), &policies), 50);
+        assert_eq!(ComplianceWeighted.validator_weight(&node(40), &policies), 20);
+        assert_eq!(ComplianceWeighted.validator_weight(&node(90), &policies), 100);
+        assert_eq!(SimpleMajority.token_weight(10_000), 10_000);
+        assert_eq!(Quadratic.token_weight(10_000), 100);
+
+        let tally = ProposalTally { yes: 60, no: 40, abstain: 0 };
+        assert!(SimpleMajority.decide(&tally, 200, &rules).passed());
+        assert!(!Supermajority.decide(&tally, 200, &rules).passed());
+        let decision = SimpleMajority.decide(&tally, 1_000, &rules);
+        assert_eq!(decision, TallyDecision { quorum_met: false, approved: true });
+    }
+}
@@ -1,14 +1,31 @@
 // File: src/governance/token.rs
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// An account's balance as of a snapshot, recorded lazily before its first change after
+/// the snapshot was taken
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Checkpoint {
+    snapshot_id: u64,
+    balance: u64,
+}
 
 /// Governance Token (gBTCZ)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GovernanceToken {
     pub symbol: String,
     pub total_supply: u64,
     pub balances: HashMap<String, u64>,
     pub rewards_schedule: u64, // Fixed emission rate
+    #[serde(default)]
+    checkpoints: HashMap<String, Vec<Checkpoint>>, // Ordered by snapshot id
+    #[serde(default)]
+    current_snapshot: u64,
+    #[serde(skip)]
+    storage_path: Option<PathBuf>, // Balances are only kept in memory without one
 }
 
 impl GovernanceToken {
@@ -19,16 +36,89 @@ impl GovernanceToken {
             total_supply: initial_supply,
             balances: HashMap::new(),
             rewards_schedule: 0,
+            checkpoints: HashMap::new(),
+            current_snapshot: 0,
+            storage_path: None,
+        }
+    }
+
+    /// Loads the stored balances, or seeds them from `genesis` the first time.
+    /// Fails if stored balances exist but cannot be read.
+    pub fn load(symbol: &str, genesis: &HashMap<String, u64>, storage_path: PathBuf) -> Result<Self, String> {
+        let mut token = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable {} balances in {}: {}", symbol, storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut token = Self::new(symbol, 0);
+                for (account, &amount) in genesis.iter().filter(|(_, &amount)| amount > 0) {
+                    token.balances.insert(account.clone(), amount);
+                    token.total_supply += amount;
+                }
+                token
+            }
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+        token.storage_path = Some(storage_path);
+        // Genesis is only applied once, so later config edits cannot re-seed balances
+        token.persist()?;
+        Ok(token)
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let Some(storage_path) = &self.storage_path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(self).map_err(|_| "Serialization failed")?;
+        fs::write(storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+
+    /// Records the current balances under a new snapshot id. Costs nothing up front; each
+    /// account's balance is checkpointed the first time it changes afterwards.
+    pub fn snapshot(&mut self) -> Result<u64, String> {
+        self.current_snapshot += 1;
+        self.persist()?;
+        Ok(self.current_snapshot)
+    }
+
+    /// Balance `account` held when snapshot `snapshot_id` was taken
+    pub fn balance_at(&self, account: &str, snapshot_id: u64) -> Result<u64, String> {
+        if snapshot_id == 0 || snapshot_id > self.current_snapshot {
+            return Err(format!("Unknown snapshot {}", snapshot_id));
+        }
+        let checkpoints = self.checkpoints.get(account).map(Vec::as_slice).unwrap_or_default();
+        // The first checkpoint at or after the snapshot holds the balance it saw; without one
+        // the balance has not changed since
+        let index = checkpoints.partition_point(|checkpoint| checkpoint.snapshot_id < snapshot_id);
+        Ok(checkpoints
+            .get(index)
+            .map_or_else(|| self.get_balance(account), |checkpoint| checkpoint.balance))
+    }
+
+    /// Saves `account`'s balance for the current snapshot before it is modified
+    fn checkpoint(&mut self, account: &str) {
+        if self.current_snapshot == 0 {
+            return;
+        }
+        let balance = self.get_balance(account);
+        let checkpoints = self.checkpoints.entry(account.to_string()).or_default();
+        if checkpoints.last().is_none_or(|last| last.snapshot_id < self.current_snapshot) {
+            checkpoints.push(Checkpoint {
+                snapshot_id: self.current_snapshot,
+                balance,
+            });
         }
     }
 
     /// Mints new tokens to the specified account
-    pub fn mint(&mut self, account: &str, amount: u64) {
+    pub fn mint(&mut self, account: &str, amount: u64) -> Result<(), String> {
         if amount == 0 {
-            panic!("Cannot mint zero tokens");
+            return Err("Cannot mint zero tokens".to_string());
         }
+        self.checkpoint(account);
         *self.balances.entry(account.to_string()).or_insert(0) += amount;
         self.total_supply += amount;
+        self.persist()
     }
 
     /// Burns tokens from the specified account
@@ -37,13 +127,28 @@ impl GovernanceToken {
             return Err("Cannot burn zero tokens".to_string());
         }
 
-        let balance = self.balances.entry(account.to_string()).or_insert(0);
-        if *balance < amount {
+        if self.get_balance(account) < amount {
             return Err("Insufficient balance to burn".to_string());
         }
-        *balance -= amount;
+        self.checkpoint(account);
+        *self.balances.entry(account.to_string()).or_insert(0) -= amount;
         self.total_supply -= amount;
-        Ok(())
+        self.persist()
+    }
+
+    /// Moves tokens between accounts
+    pub fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<(), String> {
+        if amount == 0 {
+            return Err("Cannot transfer zero tokens".to_string());
+        }
+        if self.get_balance(from) < amount {
+            return Err("Insufficient balance to transfer".to_string());
+        }
+        self.checkpoint(from);
+        self.checkpoint(to);
+        *self.balances.entry(from.to_string()).or_insert(0) -= amount;
+        *self.balances.entry(to.to_string()).or_insert(0) += amount;
+        self.persist()
     }
 
     /// Retrieves the balance of a specific account
@@ -59,3 +164,64 @@ impl GovernanceToken {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balances_at_snapshots() {
+        let mut token = GovernanceToken::new("gBTCZ", 0);
+        token.mint("alice", 100).unwrap();
+        let first = token.snapshot().unwrap();
+        token.transfer("alice", "bob", 40).unwrap();
+        token.transfer("alice", "bob", 10).unwrap();
+        let second = token.snapshot().unwrap();
+        token.burn("bob", 50).unwrap();
+
+        assert_eq!(token.balance_at("alice", first).unwrap(), 100);
+        assert_eq!(token.balance_at("bob", first).unwrap(), 0);
+        assert_eq!(token.balance_at("alice", second).unwrap(), 50);
+        assert_eq!(token.balance_at("bob", second).unwrap(), 50);
+        assert_eq!(token.get_balance("bob"), 0);
+        assert!(token.balance_at("alice", 3).is_err());
+    }
+
+    #[test]
+    fn test_balances_survive_a_restart() {
+        let path = std::env::temp_dir().join(format!("zook_token_{}_restart.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let genesis = HashMap::from([("alice".to_string(), 100)]);
+
+        let mut token = GovernanceToken::load("gBTCZ", &genesis, path.clone()).unwrap();
+        token.transfer("alice", "bob", 30).unwrap();
+
+        // Genesis is not applied on top of stored balances
+        let reloaded = GovernanceToken::load("gBTCZ", &genesis, path.clone()).unwrap();
+        assert_eq!(reloaded.get_balance("alice"), 70);
+        assert_eq!(reloaded.get_balance("bob"), 30);
+        assert_eq!(reloaded.total_supply, 100);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(GovernanceToken::load("gBTCZ", &genesis, path.clone()).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_snapshots_survive_a_restart() {
+        let path = std::env::temp_dir().join(format!("zook_token_{}_snapshots.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let genesis = HashMap::from([("alice".to_string(), 100)]);
+
+        let mut token = GovernanceToken::load("gBTCZ", &genesis, path.clone()).unwrap();
+        let snapshot = token.snapshot().unwrap();
+        token.transfer("alice", "bob", 60).unwrap();
+
+        let mut reloaded = GovernanceToken::load("gBTCZ", &genesis, path.clone()).unwrap();
+        assert_eq!(reloaded.balance_at("alice", snapshot).unwrap(), 100);
+        assert_eq!(reloaded.balance_at("bob", snapshot).unwrap(), 0);
+        // Snapshot ids keep counting from where they left off
+        assert_eq!(reloaded.snapshot().unwrap(), snapshot + 1);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,434 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::bridge::state_anchoring::StateAnchoring;
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
+use crate::governance::token::GovernanceToken;
+
+/// Token account holding the treasury's gBTCZ
+pub const TREASURY_ACCOUNT: &str = "governance-treasury";
+
+/// Treasury holdings, committed into every anchored state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasuryBalance {
+    pub gbtcz: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowKind {
+    Slashing,  // Share of rewards slashed from a validator
+    Grant,     // Payout of an approved spending proposal
+}
+
+/// One movement into or out of the treasury
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryFlow {
+    pub timestamp: DateTime<Utc>,
+    pub kind: FlowKind,
+    pub amount: u64, // gBTCZ
+    pub counterparty: String, // Payer for inflows, recipient for outflows
+    pub reference: String, // Slashed validator or grant id
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GrantStatus {
+    Requested, // Awaiting a spending proposal
+    Approved,  // Reserved and paying out as it vests
+    Completed, // Fully paid
+}
+
+/// A gBTCZ payout from the treasury, released linearly over `vesting_secs` once approved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Grant {
+    pub id: u64,
+    pub recipient: String,
+    pub amount: u64,
+    pub vesting_secs: i64, // 0 pays out in full on approval
+    pub description: String,
+    pub status: GrantStatus,
+    pub requested_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub released: u64,
+}
+
+impl Grant {
+    /// Part of the grant vested by `now`
+    fn vested(&self, now: DateTime<Utc>) -> u64 {
+        let Some(approved_at) = self.approved_at else {
+            return 0;
+        };
+        let elapsed = (now - approved_at).num_seconds().max(0);
+        if elapsed >= self.vesting_secs {
+            return self.amount;
+        }
+        (self.amount as u128 * elapsed as u128 / self.vesting_secs as u128) as u64
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TreasuryStore {
+    slash_share_percent: u64,
+    reserved: u64, // gBTCZ itself is held in the token under TREASURY_ACCOUNT // gBTCZ approved but not yet released
+    next_grant_id: u64,
+    last_approved: u64,
+    grants: BTreeMap<u64, Grant>,
+    flows: Vec<TreasuryFlow>,
+}
+
+/// Protocol revenue: a governable share of slashed rewards, minted as gBTCZ and paid out
+/// through spending proposals that approve grants via the `treasury_spend` parameter.
+/// Bridge fees stay locked as BTCZ on the bridge, so they are not treasury revenue.
+#[derive(Debug)]
+pub struct Treasury {
+    pub token: Arc<Mutex<GovernanceToken>>,
+    store: Mutex<TreasuryStore>,
+    pub anchoring: Option<Arc<StateAnchoring>>, // Receives the balance after every change
+    pub storage_path: PathBuf,
+}
+
+impl Treasury {
+    /// Fails if a stored treasury exists but cannot be read
+    pub fn new(
+        token: Arc<Mutex<GovernanceToken>>,
+        slash_share_percent: u64,
+        storage_path: PathBuf,
+    ) -> Result<Self, String> {
+        let fresh = || TreasuryStore {
+            slash_share_percent: slash_share_percent.min(100),
+            reserved: 0,
+            next_grant_id: 1,
+            last_approved: 0,
+            grants: BTreeMap::new(),
+            flows: Vec::new(),
+        };
+        let store = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable treasury in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => fresh(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+        Ok(Self {
+            token,
+            store: Mutex::new(store),
+            anchoring: None,
+            storage_path,
+        })
+    }
+
+    /// Commits the treasury balance into anchored L2 state, starting with the current one
+    pub fn with_anchoring(mut self, anchoring: Arc<StateAnchoring>) -> Self {
+        self.anchoring = Some(anchoring);
+        if let Err(e) = self.commit_balance() {
+            eprintln!("Failed to commit treasury balance: {}", e);
+        }
+        self
+    }
+
+    pub fn balance(&self) -> Result<TreasuryBalance, String> {
+        let token = self.token.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(TreasuryBalance {
+            gbtcz: token.get_balance(TREASURY_ACCOUNT),
+        })
+    }
+
+    /// gBTCZ already promised to approved grants
+    pub fn reserved(&self) -> Result<u64, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.reserved)
+    }
+
+    /// Collects the treasury's share of rewards slashed from `validator`, minting it as
+    /// gBTCZ. Returns the amount collected.
+    pub fn collect_slashing(&self, validator: &str, slashed: u64) -> Result<u64, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let share = slashed * store.slash_share_percent / 100;
+        if share == 0 {
+            return Ok(0);
+        }
+        self.token.lock().map_err(|_| "Mutex lock failed")?.mint(TREASURY_ACCOUNT, share)?;
+        self.record_flow(&mut store, FlowKind::Slashing, share, validator, validator)?;
+        Ok(share)
+    }
+
+    /// Records a grant for a spending proposal to approve by setting `treasury_spend` to its id
+    pub fn request_grant(
+        &self,
+        recipient: String,
+        amount: u64,
+        vesting_secs: i64,
+        description: String,
+    ) -> Result<Grant, String> {
+        if amount == 0 {
+            return Err("Amount must be greater than zero".to_string());
+        }
+        if recipient.is_empty() {
+            return Err("Grant needs a recipient".to_string());
+        }
+        if vesting_secs < 0 {
+            return Err("Vesting period cannot be negative".to_string());
+        }
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let grant = Grant {
+            id: store.next_grant_id,
+            recipient,
+            amount,
+            vesting_secs,
+            description,
+            status: GrantStatus::Requested,
+            requested_at: Utc::now(),
+            approved_at: None,
+            released: 0,
+        };
+        store.grants.insert(grant.id, grant.clone());
+        store.next_grant_id += 1;
+        self.persist(&store)?;
+        Ok(grant)
+    }
+
+    /// Checks that grant `id` awaits approval and fits in the unreserved gBTCZ
+    fn check_approvable(&self, store: &TreasuryStore, id: u64) -> Result<(), String> {
+        let held = self.balance()?.gbtcz;
+        // Only reachable if the token balances and the treasury store have drifted apart
+        let unreserved = held
+            .checked_sub(store.reserved)
+            .ok_or_else(|| format!("Treasury holds {} gBTCZ but has {} reserved", held, store.reserved))?;
+        let grant = store.grants.get(&id).ok_or("Grant not found")?;
+        if grant.status != GrantStatus::Requested {
+            return Err(format!("Grant {} is {:?}, not awaiting approval", id, grant.status));
+        }
+        if grant.amount > unreserved {
+            return Err(format!("Treasury has {} unreserved gBTCZ, grant {} needs {}", unreserved, id, grant.amount));
+        }
+        Ok(())
+    }
+
+    /// Reserves a requested grant against the unreserved gBTCZ and pays out what has vested
+    fn approve_grant(&self, id: u64) -> Result<(), String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        self.check_approvable(&store, id)?;
+        let grant = store.grants.get_mut(&id).ok_or("Grant not found")?;
+        grant.status = GrantStatus::Approved;
+        grant.approved_at = Some(Utc::now());
+        let amount = grant.amount;
+        store.reserved += amount;
+        store.last_approved = id;
+        println!("Treasury grant {} approved: {} gBTCZ", id, amount);
+        self.release(&mut store, Utc::now())?;
+        self.persist(&store)
+    }
+
+    /// Pays out every approved grant's newly vested amount. Returns the payouts made.
+    pub fn release_vested(&self) -> Result<Vec<TreasuryFlow>, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let released = self.release(&mut store, Utc::now())?;
+        if !released.is_empty() {
+            self.persist(&store)?;
+        }
+        Ok(released)
+    }
+
+    fn release(&self, store: &mut TreasuryStore, now: DateTime<Utc>) -> Result<Vec<TreasuryFlow>, String> {
+        let due: Vec<(u64, u64)> = store
+            .grants
+            .values()
+            .filter(|grant| grant.status == GrantStatus::Approved)
+            .map(|grant| (grant.id, grant.vested(now) - grant.released))
+            .filter(|&(_, amount)| amount > 0)
+            .collect();
+        let mut released = Vec::new();
+        for (id, amount) in due {
+            let grant = store.grants.get_mut(&id).ok_or("Grant not found")?;
+            let recipient = grant.recipient.clone();
+            self.token
+                .lock()
+                .map_err(|_| "Mutex lock failed")?
+                .transfer(TREASURY_ACCOUNT, &recipient, amount)?;
+            grant.released += amount;
+            if grant.released == grant.amount {
+                grant.status = GrantStatus::Completed;
+            }
+            store.reserved -= amount;
+            let flow = self.record_flow(store, FlowKind::Grant, amount, &recipient, &id.to_string())?;
+            released.push(flow);
+        }
+        Ok(released)
+    }
+
+    /// Appends a flow, persists and commits the new balance to anchored state
+    fn record_flow(
+        &self,
+        store: &mut TreasuryStore,
+        kind: FlowKind,
+        amount: u64,
+        counterparty: &str,
+        reference: &str,
+    ) -> Result<TreasuryFlow, String> {
+        let flow = TreasuryFlow {
+            timestamp: Utc::now(),
+            kind,
+            amount,
+            counterparty: counterparty.to_string(),
+            reference: reference.to_string(),
+        };
+        store.flows.push(flow.clone());
+        self.persist(store)?;
+        if let Some(anchoring) = &self.anchoring {
+            anchoring.commit_treasury_balance(Some(self.balance()?))?;
+        }
+        println!("Treasury {:?}: {} gBTCZ ({})", kind, amount, counterparty);
+        Ok(flow)
+    }
+
+    fn commit_balance(&self) -> Result<(), String> {
+        let Some(anchoring) = &self.anchoring else {
+            return Ok(());
+        };
+        anchoring.commit_treasury_balance(Some(self.balance()?))
+    }
+
+    /// Inflows and outflows in order, optionally of one kind
+    pub fn flows(&self, kind: Option<FlowKind>) -> Result<Vec<TreasuryFlow>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store
+            .flows
+            .iter()
+            .filter(|flow| kind.is_none_or(|kind| flow.kind == kind))
+            .cloned()
+            .collect())
+    }
+
+    /// Every grant in id order
+    pub fn grants(&self) -> Result<Vec<Grant>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.grants.values().cloned().collect())
+    }
+
+    fn persist(&self, store: &TreasuryStore) -> Result<(), String> {
+        let serialized = serde_json::to_string(store).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+}
+
+impl GovernableModule for Treasury {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(vec![
+            ParameterSpec {
+                name: "treasury_slash_share",
+                module: "treasury",
+                kind: ParameterType::Percent,
+                min: 0,
+                max: 100,
+                unit: "percent",
+                description: "Share of slashed rewards paid into the treasury",
+                value: store.slash_share_percent,
+            },
+            ParameterSpec {
+                name: "treasury_spend",
+                module: "treasury",
+                kind: ParameterType::RecordId,
+                min: 1,
+                max: store.next_grant_id - 1,
+                unit: "grant id",
+                description: "Approves a requested treasury grant",
+                value: store.last_approved,
+            },
+        ])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        if name == "treasury_spend" {
+            return self.approve_grant(value);
+        }
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        match name {
+            "treasury_slash_share" => store.slash_share_percent = value,
+            _ => return Err(format!("Unknown treasury parameter: {}", name)),
+        }
+        self.persist(&store)
+    }
+
+    fn check_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        if name != "treasury_spend" {
+            return Ok(());
+        }
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        self.check_approvable(&store, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn treasury(name: &str) -> Treasury {
+        let path = std::env::temp_dir().join(format!("zook_treasury_{}_{}.json", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        Treasury::new(Arc::new(Mutex::new(GovernanceToken::new("gBTCZ", 0))), 100, path)
+            .unwrap()
+            .with_anchoring(Arc::new(StateAnchoring::new()))
+    }
+
+    #[test]
+    fn test_collects_revenue_and_pays_approved_grants() {
+        let treasury = treasury("grants");
+        assert_eq!(treasury.collect_slashing("validator", 300).unwrap(), 300);
+        let anchoring = treasury.anchoring.clone().unwrap();
+        assert_eq!(*anchoring.treasury_balance.lock().unwrap(), Some(TreasuryBalance { gbtcz: 300 }));
+
+        let paid = treasury.request_grant("bob".into(), 100, 0, "Audit".into()).unwrap();
+        let vested = treasury.request_grant("carol".into(), 200, 3_600, "Tooling".into()).unwrap();
+        let too_large = treasury.request_grant("dave".into(), 200, 0, "Too much".into()).unwrap();
+
+        treasury.set_governance_parameter("treasury_spend", paid.id).unwrap();
+        assert_eq!(treasury.token.lock().unwrap().get_balance("bob"), 100);
+        assert!(treasury.set_governance_parameter("treasury_spend", paid.id).is_err());
+
+        // Half of a one-hour vesting has elapsed
+        treasury.set_governance_parameter("treasury_spend", vested.id).unwrap();
+        {
+            let mut store = treasury.store.lock().unwrap();
+            let grant = store.grants.get_mut(&vested.id).unwrap();
+            grant.approved_at = Some(Utc::now() - Duration::seconds(1_800));
+        }
+        // The vesting grant's reserve leaves nothing for a third one
+        assert!(treasury.set_governance_parameter("treasury_spend", too_large.id).unwrap_err().contains("unreserved"));
+        let released = treasury.release_vested().unwrap();
+        assert_eq!(released[0].amount, 100);
+        assert_eq!(treasury.token.lock().unwrap().get_balance("carol"), 100);
+        assert_eq!(treasury.balance().unwrap(), TreasuryBalance { gbtcz: 100 });
+        assert_eq!(treasury.reserved().unwrap(), 100);
+        assert_eq!(treasury.flows(Some(FlowKind::Grant)).unwrap().len(), 2);
+        assert_eq!(anchoring.treasury_balance.lock().unwrap().unwrap().gbtcz, 100);
+    }
+
+    #[test]
+    fn test_unreadable_store_is_refused() {
+        let treasury = treasury("unreadable");
+        treasury.collect_slashing("validator", 10).unwrap();
+        fs::write(&treasury.storage_path, "[]").unwrap();
+        let reopened = Treasury::new(treasury.token.clone(), 100, treasury.storage_path.clone());
+        assert!(reopened.err().unwrap().starts_with("Unreadable treasury"));
+    }
+
+    #[test]
+    fn test_reserve_above_holdings_is_an_error() {
+        let treasury = treasury("drift");
+        treasury.collect_slashing("validator", 100).unwrap();
+        let vesting = treasury.request_grant("bob".into(), 100, 3_600, "Vesting".into()).unwrap();
+        let next = treasury.request_grant("carol".into(), 1, 0, "Next".into()).unwrap();
+        treasury.set_governance_parameter("treasury_spend", vesting.id).unwrap();
+
+        // Token balances lost while the treasury store kept its reservation
+        treasury.token.lock().unwrap().burn(TREASURY_ACCOUNT, 50).unwrap();
+        let err = treasury.check_governance_parameter("treasury_spend", next.id).unwrap_err();
+        assert_eq!(err, "Treasury holds 50 gBTCZ but has 100 reserved");
+    }
+}
@@ -0,0 +1,325 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
+use crate::validator::node_registration::ValidatorRegistry;
+
+/// Who decides which validators join and leave the set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdmissionMode {
+    Permissionless, // Anyone with the minimum stake joins; any API-key holder can deactivate
+    Governed,       // Joining and ejection wait for a passed governance proposal
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AdmissionChange {
+    Join { staked_btcz: u64 },
+    Eject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdmissionStatus {
+    Pending,  // Awaiting an admit_validator or eject_validator proposal
+    Approved, // Applied to the validator registry
+}
+
+/// A join or ejection queued in governed mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdmissionRequest {
+    pub id: u64,
+    pub address: String,
+    pub change: AdmissionChange,
+    pub status: AdmissionStatus,
+    pub requested_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AdmissionStore {
+    mode: AdmissionMode,
+    next_id: u64,
+    last_admitted: u64,
+    last_ejected: u64,
+    requests: BTreeMap<u64, AdmissionRequest>,
+}
+
+/// Gate in front of `ValidatorRegistry` for joining and leaving the validator set. In
+/// governed mode requests wait in a queue until a proposal approves them.
+pub struct ValidatorAdmission {
+    pub registry: Arc<ValidatorRegistry>,
+    store: Mutex<AdmissionStore>,
+    pub storage_path: PathBuf,
+}
+
+impl ValidatorAdmission {
+    /// `mode` applies until governance changes it; a stored mode takes precedence. Fails if
+    /// the stored requests exist but cannot be read.
+    pub fn new(registry: Arc<ValidatorRegistry>, mode: AdmissionMode, storage_path: PathBuf) -> Result<Self, String> {
+        let fresh = || AdmissionStore {
+            mode,
+            next_id: 1,
+            last_admitted: 0,
+            last_ejected: 0,
+            requests: BTreeMap::new(),
+        };
+        let store = match fs::read_to_string(&storage_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Unreadable validator admissions in {}: {}", storage_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => fresh(),
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
+        };
+        Ok(Self {
+            registry,
+            store: Mutex::new(store),
+            storage_path,
+        })
+    }
+
+    pub fn mode(&self) -> Result<AdmissionMode, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store.mode)
+    }
+
+    /// Registers the validator outright when permissionless. In governed mode the request is
+    /// queued and returned.
+    pub fn register(&self, address: String, staked_btcz: u64) -> Result<Option<AdmissionRequest>, String> {
+        if staked_btcz < self.registry.minimum_stake {
+            return Err(format!(
+                "Insufficient stake. Minimum required is {} BTCZ.",
+                self.registry.minimum_stake
+            ));
+        }
+        if self.registry.get_node(&address).is_ok() {
+            return Err("Validator already registered".to_string());
+        }
+        self.submit(address, AdmissionChange::Join { staked_btcz })
+    }
+
+    /// Deactivates the validator outright when permissionless. In governed mode the ejection
+    /// is queued and returned.
+    pub fn deactivate(&self, address: String) -> Result<Option<AdmissionRequest>, String> {
+        if !self.registry.get_node(&address)?.active {
+            return Err("Validator is already inactive".to_string());
+        }
+        self.submit(address, AdmissionChange::Eject)
+    }
+
+    fn submit(&self, address: String, change: AdmissionChange) -> Result<Option<AdmissionRequest>, String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        if store.mode == AdmissionMode::Permissionless {
+            drop(store);
+            return self.apply(&address, change).map(|_| None);
+        }
+        if store
+            .requests
+            .values()
+            .any(|request| request.status == AdmissionStatus::Pending && request.address == address)
+        {
+            return Err(format!("A request for {} is already pending", address));
+        }
+        let request = AdmissionRequest {
+            id: store.next_id,
+            address,
+            change,
+            status: AdmissionStatus::Pending,
+            requested_at: Utc::now(),
+            approved_at: None,
+        };
+        store.requests.insert(request.id, request.clone());
+        store.next_id += 1;
+        self.persist(&store)?;
+        println!("Validator admission request {} queued for {}", request.id, request.address);
+        Ok(Some(request))
+    }
+
+    fn apply(&self, address: &str, change: AdmissionChange) -> Result<(), String> {
+        match change {
+            AdmissionChange::Join { staked_btcz } => self.registry.register_node(address.to_string(), staked_btcz),
+            AdmissionChange::Eject => self.registry.deactivate_node(address),
+        }
+    }
+
+    /// Checks that request `id` is pending, is a join when `join` is set and an ejection
+    /// otherwise, and can still be applied to the registry
+    fn check_approvable(&self, store: &AdmissionStore, id: u64, join: bool) -> Result<AdmissionRequest, String> {
+        let request = store.requests.get(&id).ok_or("Admission request not found")?;
+        if request.status != AdmissionStatus::Pending {
+            return Err(format!("Admission request {} is already approved", id));
+        }
+        if matches!(request.change, AdmissionChange::Join { .. }) != join {
+            return Err(format!("Admission request {} is not {}", id, if join { "a join" } else { "an ejection" }));
+        }
+        let node = self.registry.get_node(&request.address);
+        match request.change {
+            AdmissionChange::Join { .. } if node.is_ok() => Err(format!("{} is already registered", request.address)),
+            AdmissionChange::Eject if !node?.active => Err(format!("{} is already inactive", request.address)),
+            _ => Ok(request.clone()),
+        }
+    }
+
+    /// Applies pending request `id`, which must be a join when `join` is set and an ejection
+    /// otherwise
+    fn approve(&self, id: u64, join: bool) -> Result<(), String> {
+        let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let request = self.check_approvable(&store, id, join)?;
+        self.apply(&request.address, request.change)?;
+        let request = store.requests.get_mut(&id).ok_or("Admission request not found")?;
+        request.status = AdmissionStatus::Approved;
+        request.approved_at = Some(Utc::now());
+        if join {
+            store.last_admitted = id;
+        } else {
+            store.last_ejected = id;
+        }
+        self.persist(&store)
+    }
+
+    /// Requests awaiting a governance decision, in id order
+    pub fn pending(&self) -> Result<Vec<AdmissionRequest>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(store
+            .requests
+            .values()
+            .filter(|request| request.status == AdmissionStatus::Pending)
+            .cloned()
+            .collect())
+    }
+
+    fn persist(&self, store: &AdmissionStore) -> Result<(), String> {
+        let serialized = serde_json::to_string(store).map_err(|_| "Serialization failed")?;
+        fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
+    }
+}
+
+impl GovernableModule for ValidatorAdmission {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        let record = |name, description, value| ParameterSpec {
+            name,
+            module: "validator_admission",
+            kind: ParameterType::RecordId,
+            min: 1,
+            max: store.next_id - 1,
+            unit: "request id",
+            description,
+            value,
+        };
+        Ok(vec![
+            ParameterSpec {
+                name: "validator_admission_governed",
+                module: "validator_admission",
+                kind: ParameterType::Flag,
+                min: 0,
+                max: 1,
+                unit: "flag",
+                description: "Requires a governance proposal to join or eject validators",
+                value: (store.mode == AdmissionMode::Governed) as u64,
+            },
+            record("admit_validator", "Admits a pending validator registration", store.last_admitted),
+            record("eject_validator", "Deactivates a validator with a pending ejection", store.last_ejected),
+        ])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        match name {
+            "admit_validator" => self.approve(value, true),
+            "eject_validator" => self.approve(value, false),
+            "validator_admission_governed" => {
+                let mut store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+                store.mode = if value == 1 { AdmissionMode::Governed } else { AdmissionMode::Permissionless };
+                println!("Validator admission is now {:?}", store.mode);
+                self.persist(&store)
+            }
+            _ => Err(format!("Unknown governance parameter: {}", name)),
+        }
+    }
+
+    fn check_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        let store = self.store.lock().map_err(|_| "Mutex lock failed")?;
+        match name {
+            "admit_validator" => self.check_approvable(&store, value, true).map(|_| ()),
+            "eject_validator" => self.check_approvable(&store, value, false).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admission(name: &str, mode: AdmissionMode) -> ValidatorAdmission {
+        let path = |kind: &str| std::env::temp_dir().join(format!("zook_admission_{}_{}_{}.json", std::process::id(), name, kind));
+        let _ = fs::remove_file(path("registry"));
+        let _ = fs::remove_file(path("requests"));
+        let registry = Arc::new(ValidatorRegistry::new(1_000, path("registry")));
+        ValidatorAdmission::new(registry, mode, path("requests")).unwrap()
+    }
+
+    #[test]
+    fn test_governed_mode_queues_joins_and_ejections() {
+        let admission = admission("governed", AdmissionMode::Governed);
+        let join = admission.register("v1".into(), 5_000).unwrap().unwrap();
+        assert!(admission.registry.get_node("v1").is_err());
+        assert!(admission.register("v1".into(), 5_000).unwrap_err().contains("already pending"));
+        assert!(admission.register("v2".into(), 10).is_err());
+
+        assert!(admission.set_governance_parameter("eject_validator", join.id).is_err());
+        admission.set_governance_parameter("admit_validator", join.id).unwrap();
+        assert!(admission.registry.get_node("v1").unwrap().active);
+        assert!(admission.set_governance_parameter("admit_validator", join.id).is_err());
+
+        let eject = admission.deactivate("v1".into()).unwrap().unwrap();
+        assert!(admission.registry.get_node("v1").unwrap().active);
+        assert_eq!(admission.pending().unwrap().len(), 1);
+        admission.set_governance_parameter("eject_validator", eject.id).unwrap();
+        assert!(!admission.registry.get_node("v1").unwrap().active);
+        assert!(admission.pending().unwrap().is_empty());
+
+        // Back to permissionless, registrations take effect immediately
+        admission.set_governance_parameter("validator_admission_governed", 0).unwrap();
+        assert!(admission.register("v2".into(), 5_000).unwrap().is_none());
+        assert!(admission.registry.get_node("v2").unwrap().active);
+    }
+
+    #[test]
+    fn test_unreadable_requests_are_refused() {
+        let admission = admission("unreadable", AdmissionMode::Governed);
+        admission.register("v1".into(), 5_000).unwrap();
+        fs::write(&admission.storage_path, "not json").unwrap();
+        let reopened = ValidatorAdmission::new(admission.registry.clone(), AdmissionMode::Governed, admission.storage_path.clone());
+        assert!(reopened.err().unwrap().starts_with("Unreadable validator admissions"));
+    }
+
+    #[test]
+    fn test_admission_is_not_combined_with_other_commands() {
+        use crate::governance::parameters::ParameterRegistry;
+        use crate::governance::token::GovernanceToken;
+        use crate::governance::treasury::Treasury;
+
+        let admission = Arc::new(admission("unfunded", AdmissionMode::Governed));
+        let path = std::env::temp_dir().join(format!("zook_admission_{}_unfunded_treasury.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let treasury = Arc::new(Treasury::new(Arc::new(Mutex::new(GovernanceToken::new("gBTCZ", 0))), 0, path).unwrap());
+        let registry = ParameterRegistry::new().register(admission.clone()).register(treasury.clone());
+
+        let join = admission.register("v1".into(), 5_000).unwrap().unwrap();
+        let grant = treasury.request_grant("bob".into(), 100, 0, "Unfunded".into()).unwrap();
+        // Admitting cannot be undone if the unfunded grant then fails, so the pair is refused
+        let err = registry.apply_all(&[("admit_validator", join.id), ("treasury_spend", grant.id)]).unwrap_err();
+        assert!(err.contains("only change"));
+        assert!(admission.registry.get_node("v1").is_err());
+        assert_eq!(admission.pending().unwrap().len(), 1);
+
+        assert!(registry.apply_all(&[("treasury_spend", grant.id)]).unwrap_err().contains("unreserved"));
+        registry.apply_all(&[("admit_validator", join.id)]).unwrap();
+        assert!(admission.registry.get_node("v1").unwrap().active);
+    }
+}
@@ -1,21 +1,45 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
 use crate::validator::node_registration::{ValidatorRegistry, ValidatorNode};
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
 
 /// Defines the governance rules for validator policies
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct ValidatorPolicy {
     pub minimum_stake: u64,
     pub activity_threshold: u64,    // Minimum activity percentage required
     pub compliance_threshold: u64, // Minimum compliance percentage for voting power
 }
 
+/// A policy as decided by governance, in force from the start of `effective_epoch`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyVersion {
+    pub version: u64,
+    pub policy: ValidatorPolicy,
+    pub effective_epoch: u64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PolicyHistory {
+    notice_epochs: u64, // Epochs between a policy change passing and taking effect
+    versions: Vec<PolicyVersion>, // Ascending by version and effective epoch
+}
+
+/// Validator policy thresholds, changed only through governance parameters. Every change
+/// is kept as a new version taking effect `notice_epochs` after the current epoch.
 pub struct GovernanceValidatorPolicies {
-    pub policy: Arc<Mutex<ValidatorPolicy>>,
+    history: Mutex<PolicyHistory>,
+    pub epoch_secs: u64, // Length of a policy epoch; epoch 0 starts at the Unix epoch
+    pub storage_path: Option<PathBuf>,
 }
 
 impl GovernanceValidatorPolicies {
-    /// Creates a new governance policy with default thresholds
+    /// Creates a new governance policy with default thresholds, in force from epoch 0.
+    /// Epochs last a day and later changes take effect immediately.
     pub fn new(minimum_stake: u64, activity_threshold: u64, compliance_threshold: u64) -> Self {
         let policy = ValidatorPolicy {
             minimum_stake,
@@ -24,34 +48,97 @@ impl GovernanceValidatorPolicies {
         };
 
         Self {
-            policy: Arc::new(Mutex::new(policy)),
+            history: Mutex::new(PolicyHistory {
+                notice_epochs: 0,
+                versions: vec![PolicyVersion {
+                    version: 1,
+                    policy,
+                    effective_epoch: 0,
+                    recorded_at: Utc::now(),
+                }],
+            }),
+            epoch_secs: 24 * 60 * 60,
+            storage_path: None,
         }
     }
 
-    /// Retrieves the current governance policy
-    pub fn get_policy(&self) -> Option<ValidatorPolicy> {
-        self.policy.lock().ok().map(|policy| policy.clone())
+    /// Sets the epoch length and how many epochs of notice validators get before a change
+    pub fn with_epochs(mut self, epoch_secs: u64, notice_epochs: u64) -> Self {
+        self.epoch_secs = epoch_secs.max(1);
+        if let Ok(history) = self.history.get_mut() {
+            history.notice_epochs = notice_epochs;
+        }
+        self
     }
 
-    /// Updates the governance policy thresholds
-    pub fn update_policy(&self, minimum_stake: u64, activity_threshold: u64, compliance_threshold: u64) {
-        if let Ok(mut policy) = self.policy.lock() {
-            policy.minimum_stake = minimum_stake;
-            policy.activity_threshold = activity_threshold;
-            policy.compliance_threshold = compliance_threshold;
+    /// Keeps the version history in `storage_path`, loading any history stored there. Fails
+    /// if a stored history exists but is unreadable or empty.
+    pub fn with_storage(mut self, storage_path: PathBuf) -> Result<Self, String> {
+        match fs::read_to_string(&storage_path) {
+            Ok(content) => match serde_json::from_str::<PolicyHistory>(&content) {
+                Ok(history) if !history.versions.is_empty() => self.history = Mutex::new(history),
+                Ok(_) => return Err(format!("Validator policy history in {} is empty", storage_path.display())),
+                Err(e) => {
+                    return Err(format!("Unreadable validator policy history in {}: {}", storage_path.display(), e))
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read {}: {}", storage_path.display(), e)),
         }
+        self.storage_path = Some(storage_path);
+        Ok(self)
+    }
+
+    pub fn epoch_at(&self, time: DateTime<Utc>) -> u64 {
+        time.timestamp().max(0) as u64 / self.epoch_secs
+    }
+
+    /// Retrieves the governance policy in force now
+    pub fn get_policy(&self) -> Option<ValidatorPolicy> {
+        self.policy_at(Utc::now()).ok().map(|version| version.policy)
+    }
+
+    /// The version in force at `time`
+    pub fn policy_at(&self, time: DateTime<Utc>) -> Result<PolicyVersion, String> {
+        let epoch = self.epoch_at(time);
+        let history = self.history.lock().map_err(|_| "Mutex lock failed")?;
+        history
+            .versions
+            .iter()
+            .rev()
+            .find(|version| version.effective_epoch <= epoch)
+            .cloned()
+            .ok_or_else(|| format!("No validator policy was in force at {}", time))
+    }
+
+    /// Every version, including those not yet in force
+    pub fn history(&self) -> Result<Vec<PolicyVersion>, String> {
+        let history = self.history.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(history.versions.clone())
+    }
+
+    fn persist(&self, history: &PolicyHistory) -> Result<(), String> {
+        let Some(storage_path) = &self.storage_path else {
+            return Ok(());
+        };
+        let serialized = serde_json::to_string(history).map_err(|_| "Serialization failed")?;
+        fs::write(storage_path, serialized).map_err(|_| "Failed to write to disk")?;
+        Ok(())
     }
 
     /// Validates a node based on its stake and activity
+    #[allow(dead_code)] // Callers holding the node already use `meets_policy`
     pub fn validate_node(&self, registry: &ValidatorRegistry, node_id: &str) -> bool {
-        if let Some(policy) = self.get_policy() {
-            if let Ok(node) = registry.get_node(node_id) {
-                return node.staked_btcz >= policy.minimum_stake
-                    && node.active
-                    && node.activity_percentage >= policy.activity_threshold;
-            }
-        }
-        false
+        registry.get_node(node_id).is_ok_and(|node| self.meets_policy(&node))
+    }
+
+    /// Whether an active node has the minimum stake and activity
+    pub fn meets_policy(&self, node: &ValidatorNode) -> bool {
+        self.get_policy().is_some_and(|policy| {
+            node.staked_btcz >= policy.minimum_stake
+                && node.active
+                && node.activity_percentage >= policy.activity_threshold
+        })
     }
 
     /// Adjusts the voting power of a validator node based on compliance and stake
@@ -77,6 +164,90 @@ impl GovernanceValidatorPolicies {
     }
 }
 
+impl GovernableModule for GovernanceValidatorPolicies {
+    /// Values are the latest decided by governance, which may not be in force yet
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        let history = self.history.lock().map_err(|_| "Mutex lock failed")?;
+        let policy = &history.versions.last().ok_or("No validator policy recorded")?.policy;
+        Ok(vec![
+            ParameterSpec {
+                name: "minimum_stake",
+                module: "validator",
+                kind: ParameterType::Amount,
+                min: 1,
+                max: 2_100_000_000_000_000_000,
+                unit: "BTCZ base units",
+                description: "Stake a validator needs for full voting power",
+                value: policy.minimum_stake,
+            },
+            ParameterSpec {
+                name: "activity_threshold",
+                module: "validator",
+                kind: ParameterType::Percent,
+                min: 0,
+                max: 100,
+                unit: "percent",
+                description: "Minimum activity for a validator to be considered valid",
+                value: policy.activity_threshold,
+            },
+            ParameterSpec {
+                name: "compliance_threshold",
+                module: "validator",
+                kind: ParameterType::Percent,
+                min: 1,
+                max: 100,
+                unit: "percent",
+                description: "Compliance at which a validator reaches full voting power",
+                value: policy.compliance_threshold,
+            },
+            ParameterSpec {
+                name: "policy_notice_epochs",
+                module: "validator",
+                kind: ParameterType::Count,
+                min: 0,
+                max: 52,
+                unit: "epochs",
+                description: "Epochs before a validator policy change takes effect",
+                value: history.notice_epochs,
+            },
+        ])
+    }
+
+    /// Records the change as a new version effective after the notice period, and no earlier
+    /// than a version already scheduled. Changes taking effect in the same epoch amend that
+    /// epoch's version.
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        let mut history = self.history.lock().map_err(|_| "Mutex lock failed")?;
+        let latest = history.versions.last().cloned().ok_or("No validator policy recorded")?;
+        let mut policy = latest.policy.clone();
+        match name {
+            "minimum_stake" => policy.minimum_stake = value,
+            "activity_threshold" => policy.activity_threshold = value,
+            "compliance_threshold" => policy.compliance_threshold = value,
+            "policy_notice_epochs" => {
+                history.notice_epochs = value;
+                return self.persist(&history);
+            }
+            _ => return Err(format!("Unknown validator parameter: {}", name)),
+        }
+        let effective_epoch = (self.epoch_at(Utc::now()) + history.notice_epochs).max(latest.effective_epoch);
+        let version = if latest.effective_epoch == effective_epoch {
+            history.versions.pop();
+            latest.version
+        } else {
+            latest.version + 1
+        };
+        history.versions.push(PolicyVersion {
+            version,
+            policy,
+            effective_epoch,
+            recorded_at: Utc::now(),
+        });
+        println!("Validator policy version {} takes effect in epoch {}", version, effective_epoch);
+        self.persist(&history)
+    }
+}
+
 /// A helper struct for adjusting voting power explicitly
 pub struct VotingPowerAdjustment;
 
@@ -93,3 +264,42 @@ impl VotingPowerAdjustment {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_policy_changes_are_versioned_with_notice() {
+        let path = std::env::temp_dir().join(format!("zook_policies_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let policies = GovernanceValidatorPolicies::new(1_000, 80, 70).with_epochs(3_600, 2).with_storage(path.clone()).unwrap();
+        policies.set_governance_parameter("minimum_stake", 2_000).unwrap();
+        policies.set_governance_parameter("activity_threshold", 90).unwrap();
+        // Lowering the notice cannot move a change ahead of one already scheduled
+        policies.set_governance_parameter("policy_notice_epochs", 0).unwrap();
+        policies.set_governance_parameter("compliance_threshold", 50).unwrap();
+
+        let now = Utc::now();
+        let history = policies.history().unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[1].effective_epoch > policies.epoch_at(now));
+        assert_eq!(history[1].policy, ValidatorPolicy { minimum_stake: 2_000, activity_threshold: 90, compliance_threshold: 50 });
+
+        assert_eq!(policies.get_policy().unwrap().minimum_stake, 1_000);
+        assert_eq!(policies.policy_at(now + Duration::hours(2)).unwrap().version, 2);
+        assert_eq!(policies.policy_at(now - Duration::days(365)).unwrap().version, 1);
+        let specs = policies.governance_parameters().unwrap();
+        assert_eq!(specs[0].value, 2_000);
+
+        let reloaded = GovernanceValidatorPolicies::new(1, 1, 1).with_storage(path.clone()).unwrap();
+        assert_eq!(reloaded.history().unwrap(), history);
+
+        // A damaged history is refused rather than replaced by the defaults
+        fs::write(&path, "{\"notice_epochs\":2,\"versions\":[]}").unwrap();
+        assert!(GovernanceValidatorPolicies::new(1, 1, 1).with_storage(path.clone()).err().unwrap().contains("is empty"));
+        fs::write(&path, "not json").unwrap();
+        assert!(GovernanceValidatorPolicies::new(1, 1, 1).with_storage(path).err().unwrap().starts_with("Unreadable"));
+    }
+}
@@ -2,49 +2,72 @@
 
 use warp::{Filter, Rejection, Reply};
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use crate::governance::validator_policies::GovernanceValidatorPolicies;
-use crate::validator::node_registration::ValidatorRegistry;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct UpdatePolicyRequest {
-    minimum_stake: Option<u64>,
-    activity_threshold: Option<u64>,
+struct PolicyAtQuery {
+    time: DateTime<Utc>,
 }
 
+/// Read-only views of the validator policy; changes go through governance proposals
 pub fn policy_api_routes(
     policies: Arc<GovernanceValidatorPolicies>,
-    registry: Arc<ValidatorRegistry>,
-) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    let update_policy = warp::path("update")
-        .and(warp::post())
-        .and(warp::body::json())
-        .and_then(move |request: UpdatePolicyRequest| {
-            let policies = policies.clone();
-            let registry = registry.clone();
+) -> warp::filters::BoxedFilter<(impl Reply,)> {
+    let current_policies = policies.clone();
+    let history_policies = policies.clone();
+
+    let get_policy = warp::path("get")
+        .and(warp::get())
+        .and_then(move || {
+            let policies = current_policies.clone();
             async move {
-                match policies.integrate_governance_proposal(
-                    &registry,
-                    request.minimum_stake,
-                    request.activity_threshold,
-                ) {
-                    Ok(_) => Ok(warp::reply::json(&{"status": "success", "message": "Policy updated successfully"})),
-                    Err(e) => Ok(warp::reply::json(&{"status": "error", "message": e})),
+                let now = Utc::now();
+                match policies.policy_at(now) {
+                    Ok(version) => Ok::<_, Rejection>(warp::reply::json(&json!({
+                        "status": "success",
+                        "epoch": policies.epoch_at(now),
+                        "policy": version,
+                    }))),
+                    Err(e) => Ok(warp::reply::json(&json!({ "status": "error", "message": e }))),
                 }
             }
         });
 
-    let get_policy = warp::path("get")
+    let get_history = warp::path("history")
         .and(warp::get())
         .and_then(move || {
+            let policies = history_policies.clone();
+            async move {
+                match policies.history() {
+                    Ok(versions) => Ok::<_, Rejection>(warp::reply::json(&json!({
+                        "status": "success",
+                        "epoch": policies.epoch_at(Utc::now()),
+                        "versions": versions,
+                    }))),
+                    Err(e) => Ok(warp::reply::json(&json!({ "status": "error", "message": e }))),
+                }
+            }
+        });
+
+    let get_policy_at = warp::path("at")
+        .and(warp::get())
+        .and(warp::query::<PolicyAtQuery>())
+        .and_then(move |query: PolicyAtQuery| {
             let policies = policies.clone();
             async move {
-                match policies.get_policy() {
-                    Ok(policy) => Ok(warp::reply::json(&{"status": "success", "policy": policy})),
-                    Err(e) => Ok(warp::reply::json(&{"status": "error", "message": e})),
+                match policies.policy_at(query.time) {
+                    Ok(version) => Ok::<_, Rejection>(warp::reply::json(&json!({
+                        "status": "success",
+                        "epoch": policies.epoch_at(query.time),
+                        "policy": version,
+                    }))),
+                    Err(e) => Ok(warp::reply::json(&json!({ "status": "error", "message": e }))),
                 }
             }
         });
 
-    warp::path("policy").and(update_policy.or(get_policy))
+    warp::path("policy").and(get_policy.or(get_history).or(get_policy_at)).boxed()
 }
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use chrono::{DateTime, Utc};
+use crate::governance::parameters::{GovernableModule, ParameterSpec, ParameterType};
+use crate::governance::treasury::Treasury;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorRewardRecord {
@@ -20,6 +22,7 @@ pub struct RewardsDistribution {
 pub struct ValidatorRewards {
     pub reward_rate: u64, // Reward rate per cycle in gBTCZ
     pub validator_records: Arc<Mutex<HashMap<String, ValidatorRewardRecord>>>,
+    pub treasury: Option<Arc<Treasury>>, // Receives its share of slashed rewards
 }
 
 impl ValidatorRewards {
@@ -27,9 +30,15 @@ impl ValidatorRewards {
         Self {
             reward_rate,
             validator_records: Arc::new(Mutex::new(HashMap::new())),
+            treasury: None,
         }
     }
 
+    pub fn with_treasury(mut self, treasury: Arc<Treasury>) -> Self {
+        self.treasury = Some(treasury);
+        self
+    }
+
     pub fn register_validator(&self, address: String, _locked_btcz: u64) -> Result<(), String> {
         let mut records = self.validator_records.lock().map_err(|_| "Mutex lock failed")?;
         if records.contains_key(&address) {
@@ -70,10 +79,15 @@ impl ValidatorRewards {
     pub fn slash_validator(&self, address: String, penalty: u64) -> Result<(), String> {
         let mut records = self.validator_records.lock().map_err(|_| "Mutex lock failed")?;
         if let Some(record) = records.get_mut(&address) {
-            record.rewards_earned = record.rewards_earned.saturating_sub(penalty);
+            let slashed = penalty.min(record.rewards_earned);
+            // Collect first so a failing treasury leaves the rewards untouched
+            if let Some(treasury) = &self.treasury {
+                treasury.collect_slashing(&address, slashed)?;
+            }
+            record.rewards_earned -= slashed;
             println!(
                 "Validator {} slashed by {} gBTCZ. Remaining rewards: {}",
-                address, penalty, record.rewards_earned
+                address, slashed, record.rewards_earned
             );
             Ok(())
         } else {
@@ -87,15 +101,55 @@ impl ValidatorRewards {
         Ok(())
     }
 
-    pub fn apply_governance_adjustment(&mut self, param: &str, value: u64) -> Result<(), String> {
-        match param {
-            "reward_rate" => self.adjust_reward_rate(value),
-            _ => Err("Unsupported governance parameter".to_string()),
-        }
-    }
-
+    #[allow(dead_code)] // No rewards query API yet
     pub fn get_validator_rewards(&self, address: &str) -> Option<ValidatorRewardRecord> {
         let records = self.validator_records.lock().ok()?;
         records.get(address).cloned()
     }
 }
+
+impl GovernableModule for Mutex<ValidatorRewards> {
+    fn governance_parameters(&self) -> Result<Vec<ParameterSpec>, String> {
+        let rewards = self.lock().map_err(|_| "Mutex lock failed")?;
+        Ok(vec![ParameterSpec {
+            name: "reward_rate",
+            module: "rewards",
+            kind: ParameterType::Amount,
+            min: 0,
+            max: 1_000_000,
+            unit: "gBTCZ per cycle",
+            description: "Reward paid to each validator per distribution cycle",
+            value: rewards.reward_rate,
+        }])
+    }
+
+    fn set_governance_parameter(&self, name: &str, value: u64) -> Result<(), String> {
+        let mut rewards = self.lock().map_err(|_| "Mutex lock failed")?;
+        match name {
+            "reward_rate" => rewards.adjust_reward_rate(value),
+            _ => Err(format!("Unknown rewards parameter: {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::token::GovernanceToken;
+
+    #[test]
+    fn test_failed_treasury_collection_keeps_rewards() {
+        let path = std::env::temp_dir().join(format!("zook_rewards_{}_treasury_dir", std::process::id()));
+        let _ = std::fs::remove_dir(&path);
+        let token = Arc::new(Mutex::new(GovernanceToken::new("gBTCZ", 0)));
+        let treasury = Arc::new(Treasury::new(token, 100, path.clone()).unwrap());
+        // A directory in place of the treasury file makes every write fail
+        std::fs::create_dir_all(&path).unwrap();
+        let rewards = ValidatorRewards::new(50, std::path::PathBuf::new()).with_treasury(treasury.clone());
+        rewards.register_validator("v1".into(), 0).unwrap();
+        rewards.distribute_rewards(&[]).unwrap();
+
+        assert!(rewards.slash_validator("v1".into(), 20).is_err());
+        assert_eq!(rewards.get_validator_rewards("v1").unwrap().rewards_earned, 50);
+    }
+}
@@ -0,0 +1,361 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+/// Timeouts, retries and circuit-breaker limits shared by every outbound HTTP client
+#[derive(Debug, Clone)]
+pub struct HttpSettings {
+    pub request_timeout: Duration,
+    pub retry_attempts: u32,
+    pub retry_delay: Duration,
+    pub max_retry_delay: Duration,
+    pub failure_threshold: u32, // Consecutive failures before an endpoint's circuit opens
+    pub cooldown: Duration,     // How long an open circuit waits before a trial request
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(10),
+            retry_attempts: 3,
+            retry_delay: Duration::from_millis(500),
+            max_retry_delay: Duration::from_secs(30),
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Reads the `[settings]` table of `config.toml`, keeping defaults for missing keys
+    pub fn from_config(config: &toml::Value) -> Self {
+        let defaults = Self::default();
+        let settings = config.get("settings");
+        let millis = |key: &str, default: Duration| {
+            settings
+                .and_then(|table| table.get(key))
+                .and_then(toml::Value::as_integer)
+                .map(|ms| Duration::from_millis(ms.max(0) as u64))
+                .unwrap_or(default)
+        };
+        let count = |key: &str, default: u32| {
+            settings
+                .and_then(|table| table.get(key))
+                .and_then(toml::Value::as_integer)
+                .map(|n| n.clamp(1, u32::MAX as i64) as u32)
+                .unwrap_or(default)
+        };
+
+        Self {
+            request_timeout: millis("request-timeout", defaults.request_timeout),
+            retry_attempts: count("retry-attempts", defaults.retry_attempts),
+            retry_delay: millis("retry-delay", defaults.retry_delay),
+            max_retry_delay: millis("max-retry-delay", defaults.max_retry_delay),
+            failure_threshold: count("circuit-failure-threshold", defaults.failure_threshold),
+            cooldown: millis("circuit-cooldown", defaults.cooldown),
+        }
+    }
+
+    /// Delay before retry `attempt` (1-based): exponential, capped, with equal jitter
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let base = self.retry_delay.as_millis() as u64;
+        let cap = self.max_retry_delay.as_millis() as u64;
+        let delay = base.saturating_mul(1 << attempt.saturating_sub(1).min(32)).min(cap);
+        Duration::from_millis(delay / 2 + rand::thread_rng().gen_range(0..=delay - delay / 2))
+    }
+}
+
+/// Loads `config.toml`
+pub fn load_config(path: &Path) -> Result<toml::Value, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    contents
+        .parse::<toml::Value>()
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Endpoint URLs listed under `[<section>] rpc-urls`
+pub fn config_endpoints(config: &toml::Value, section: &str) -> Vec<String> {
+    config
+        .get(section)
+        .and_then(|table| table.get("rpc-urls"))
+        .and_then(toml::Value::as_array)
+        .map(|urls| {
+            urls.iter()
+                .filter_map(toml::Value::as_str)
+                .map(|url| url.trim_end_matches('/').to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub requests: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+    pub last_success: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    opened_at: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            requests: 0,
+            failures: 0,
+            last_error: None,
+            last_success: None,
+            opened_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointReport {
+    pub name: String,
+    pub healthy: bool, // At least one endpoint accepts requests
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+/// Ordered set of interchangeable endpoints behind one pooled client. Requests go to the
+/// first endpoint whose circuit is closed and fail over to the next on transport errors,
+/// timeouts, 429 or 5xx responses.
+#[derive(Debug, Clone)]
+pub struct EndpointPool {
+    name: String,
+    client: Client,
+    settings: HttpSettings,
+    endpoints: Arc<Mutex<Vec<EndpointHealth>>>,
+}
+
+impl EndpointPool {
+    pub fn new(name: &str, urls: Vec<String>, settings: HttpSettings) -> Self {
+        let client = Client::builder()
+            .timeout(settings.request_timeout)
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self {
+            name: name.to_string(),
+            client,
+            settings,
+            endpoints: Arc::new(Mutex::new(urls.into_iter().map(EndpointHealth::new).collect())),
+        }
+    }
+
+    /// Single-endpoint pool with default settings
+    pub fn single(name: &str, url: &str) -> Self {
+        Self::new(name, vec![url.to_string()], HttpSettings::default())
+    }
+
+    /// Sends the request built by `build` for each endpoint URL until one answers
+    pub async fn send<F>(&self, build: F) -> Result<Response, String>
+    where
+        F: Fn(&Client, &str) -> RequestBuilder,
+    {
+        let mut last_error = "No endpoints configured".to_string();
+
+        for attempt in 0..self.settings.retry_attempts {
+            if attempt > 0 {
+                sleep(self.settings.backoff(attempt)).await;
+            }
+
+            let available = self.available()?;
+            if available.is_empty() {
+                last_error = "All endpoint circuits are open".to_string();
+                continue;
+            }
+
+            for url in available {
+                match build(&self.client, &url).send().await {
+                    Ok(resp) if Self::is_retryable(resp.status()) => {
+                        last_error = format!("{} returned {}", url, resp.status());
+                        self.record_failure(&url, &last_error)?;
+                    }
+                    Ok(resp) => {
+                        self.record_success(&url)?;
+                        return Ok(resp);
+                    }
+                    Err(err) => {
+                        last_error = format!("{}: {}", url, err);
+                        self.record_failure(&url, &last_error)?;
+                    }
+                }
+            }
+        }
+
+        Err(format!(
+            "{} request failed after {} attempts: {}",
+            self.name, self.settings.retry_attempts, last_error
+        ))
+    }
+
+    pub fn report(&self) -> Result<EndpointReport, String> {
+        let endpoints = self.endpoints.lock().map_err(|_| "Mutex lock failed")?.clone();
+        Ok(EndpointReport {
+            name: self.name.clone(),
+            healthy: endpoints.iter().any(|e| e.state != CircuitState::Open),
+            endpoints,
+        })
+    }
+
+    fn is_retryable(status: StatusCode) -> bool {
+        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// Endpoints to try this round; open circuits past their cooldown get one trial request
+    fn available(&self) -> Result<Vec<String>, String> {
+        let mut endpoints = self.endpoints.lock().map_err(|_| "Mutex lock failed")?;
+        let cooldown = self.settings.cooldown;
+        Ok(endpoints
+            .iter_mut()
+            .filter_map(|endpoint| {
+                if endpoint.state == CircuitState::Open
+                    && endpoint.opened_at.is_none_or(|at| at.elapsed() >= cooldown)
+                {
+                    endpoint.state = CircuitState::HalfOpen;
+                }
+                (endpoint.state != CircuitState::Open).then(|| endpoint.url.clone())
+            })
+            .collect())
+    }
+
+    fn record_success(&self, url: &str) -> Result<(), String> {
+        let mut endpoints = self.endpoints.lock().map_err(|_| "Mutex lock failed")?;
+        if let Some(endpoint) = endpoints.iter_mut().find(|e| e.url == url) {
+            endpoint.requests += 1;
+            endpoint.consecutive_failures = 0;
+            endpoint.state = CircuitState::Closed;
+            endpoint.opened_at = None;
+            endpoint.last_success = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    fn record_failure(&self, url: &str, error: &str) -> Result<(), String> {
+        let mut endpoints = self.endpoints.lock().map_err(|_| "Mutex lock failed")?;
+        if let Some(endpoint) = endpoints.iter_mut().find(|e| e.url == url) {
+            endpoint.requests += 1;
+            endpoint.failures += 1;
+            endpoint.consecutive_failures += 1;
+            endpoint.last_error = Some(error.to_string());
+            if endpoint.state == CircuitState::HalfOpen
+                || endpoint.consecutive_failures >= self.settings.failure_threshold
+            {
+                if endpoint.state != CircuitState::Open {
+                    eprintln!("Opening circuit for {} endpoint {}: {}", self.name, url, error);
+                }
+                endpoint.state = CircuitState::Open;
+                endpoint.opened_at = Some(Instant::now());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp::Filter;
+
+    fn settings() -> HttpSettings {
+        HttpSettings {
+            request_timeout: Duration::from_secs(2),
+            retry_attempts: 2,
+            retry_delay: Duration::from_millis(1),
+            max_retry_delay: Duration::from_millis(5),
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    async fn live_endpoint() -> String {
+        let route = warp::path("ping").map(|| "pong");
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn fails_over_and_opens_circuit_on_dead_endpoint() {
+        let dead = "http://127.0.0.1:9".to_string();
+        let live = live_endpoint().await;
+        let pool = EndpointPool::new("stacks", vec![dead.clone(), live.clone()], settings());
+
+        for _ in 0..2 {
+            let resp = pool
+                .send(|client, url| client.get(format!("{}/ping", url)))
+                .await
+                .unwrap();
+            assert_eq!(resp.text().await.unwrap(), "pong");
+        }
+
+        let report = pool.report().unwrap();
+        assert!(report.healthy);
+        assert_eq!(report.endpoints[0].state, CircuitState::Open);
+        assert_eq!(report.endpoints[0].failures, 2);
+        assert_eq!(report.endpoints[1].state, CircuitState::Closed);
+        assert_eq!(report.endpoints[1].requests, 2);
+
+        // With its circuit open, the dead endpoint is skipped entirely
+        pool.send(|client, url| client.get(format!("{}/ping", url))).await.unwrap();
+        assert_eq!(pool.report().unwrap().endpoints[0].requests, 2);
+    }
+
+    #[tokio::test]
+    async fn reports_failure_once_retries_are_exhausted() {
+        let pool = EndpointPool::new("btcz", vec!["http://127.0.0.1:9".to_string()], settings());
+        let err = pool.send(|client, url| client.get(url)).await.unwrap_err();
+        assert!(err.starts_with("btcz request failed after 2 attempts"));
+
+        let report = pool.report().unwrap();
+        assert!(!report.healthy);
+        assert!(report.endpoints[0].last_error.is_some());
+    }
+
+    #[test]
+    fn backoff_is_capped_and_jittered() {
+        let settings = HttpSettings {
+            retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_millis(1_000),
+            ..HttpSettings::default()
+        };
+        for attempt in 1..10 {
+            let expected = (100u64 << (attempt - 1)).min(1_000);
+            let delay = settings.backoff(attempt).as_millis() as u64;
+            assert!(delay >= expected / 2 && delay <= expected);
+        }
+    }
+
+    #[test]
+    fn reads_settings_and_endpoints_from_config() {
+        let config: toml::Value = "[settings]\nretry-attempts = 10\nretry-delay = 5000\n\n[btcz]\nrpc-urls = [\"http://a/\", \"http://b\"]\n"
+            .parse()
+            .unwrap();
+        let settings = HttpSettings::from_config(&config);
+        assert_eq!(settings.retry_attempts, 10);
+        assert_eq!(settings.retry_delay, Duration::from_millis(5000));
+        assert_eq!(settings.request_timeout, HttpSettings::default().request_timeout);
+        assert_eq!(config_endpoints(&config, "btcz"), vec!["http://a", "http://b"]);
+        assert!(config_endpoints(&config, "stacks").is_empty());
+    }
+}
@@ -11,6 +11,7 @@ mod api;
 mod bridge;
 mod clarity;
 mod governance;
+mod http_client;
 mod validator;
 
 #[path = "../interaction.rs"]
@@ -18,17 +19,37 @@ mod interaction;
 
 use api::anchoring_api::AnchoringAPI;
 use api::bridge_api::{BridgeAPI, MutexAdapter};
+use api::delegation_api::DelegationAPI;
+use api::endpoint_health_api::EndpointHealthAPI;
+use api::event_observer_api::EventObserverAPI;
 use api::governance_api::GovernanceAPI;
+use api::guardian_api::GuardianAPI;
+use api::treasury_api::TreasuryAPI;
 use api::validator_rewards_api::ValidatorRewardsAPI;
-use bridge::bridge_logic::BridgeLedger;
+use bridge::bridge_logic::{BridgeLedger, BridgeLimits};
 use bridge::cross_layer_sync::CrossLayerSync;
+use bridge::event_observer::StacksEventObserver;
+use bridge::sponsorship::{SponsorQuota, SponsorshipService};
 use bridge::btcz_integration::BTCZIntegration;
 use bridge::state_anchoring::StateAnchoring;
+use clarity::transaction::{StacksNetwork, StacksSigner};
 use clarity::ClarityInteractor;
 use governance::validator_rewards_tokenomics::ValidatorRewards;
-use governance::cross_layer_governance::CrossLayerGovernance;
+use governance::audit_log::GovernanceAuditLog;
+use governance::contract_sync::GovernanceContractSync;
+use governance::delegation::DelegationRegistry;
+use governance::deposits::ProposalDeposits;
+use governance::guardians::GuardianCouncil;
+use governance::parameters::ParameterRegistry;
+use governance::token::GovernanceToken;
+use governance::treasury::Treasury;
+use governance::validator_admission::{AdmissionMode, ValidatorAdmission};
+use governance::proposal_engine::{ProposalEngine, VotingRules};
 use governance::validator_policies::GovernanceValidatorPolicies;
-use api::security::RateLimiter;
+use governance::validator_policy_api::policy_api_routes;
+use http_client::{config_endpoints, load_config, EndpointPool, HttpSettings};
+use api::security::{ApiKey, RateLimiter};
+use validator::node_api::node_api_routes;
 use validator::node_registration::ValidatorRegistry;
 
 #[tokio::main]
@@ -38,61 +59,403 @@ async fn main() {
     let zbtcz_address = "SP3K2K92ZXAN5DZ96FEAB5A6NNG2MX74E9KPW0XJ7.zbtcz";
     let gbtcz_address = "SP3K2K92ZXAN5DZ96FEAB5A6NNG2MX74E9KPW0XJ7.gbtcz";
     let governance_address = "SP3K2K92ZXAN5DZ96FEAB5A6NNG2MX74E9KPW0XJ7.governance";
+    let bridge_address = "SP3K2K92ZXAN5DZ96FEAB5A6NNG2MX74E9KPW0XJ7.bridge";
+    let rewards_address = "SP3K2K92ZXAN5DZ96FEAB5A6NNG2MX74E9KPW0XJ7.validator-rewards";
 
-    let clarity = Arc::new(ClarityInteractor::new(
+    let config = load_config(std::path::Path::new("config.toml")).unwrap_or_else(|e| {
+        eprintln!("{}; using default HTTP settings", e);
+        toml::Value::Table(Default::default())
+    });
+    let http_settings = HttpSettings::from_config(&config);
+    let endpoint_pool = |section: &str, fallback: &str| {
+        let mut urls = config_endpoints(&config, section);
+        if urls.is_empty() {
+            urls.push(fallback.to_string());
+        }
+        EndpointPool::new(section, urls, http_settings.clone())
+    };
+    let stacks_endpoints = endpoint_pool("stacks", &api_url);
+    let btcz_endpoints = endpoint_pool("btcz", "http://btcz_node_rpc_url");
+
+    let mut interactor = ClarityInteractor::new(
         &api_url,
         interaction::Principal::new(sender.to_string()),
         zbtcz_address,
         gbtcz_address,
         governance_address,
-    ));
+        bridge_address,
+        rewards_address,
+    )
+    .with_endpoints(stacks_endpoints.clone());
+    let network = match std::env::var("STACKS_NETWORK").as_deref() {
+        Ok("testnet") => StacksNetwork::Testnet,
+        _ => StacksNetwork::Mainnet,
+    };
+    match std::env::var("STACKS_SIGNER_KEY").map(|key| StacksSigner::from_hex(&key, network)) {
+        Ok(Ok(signer)) => interactor = interactor.with_signer(signer),
+        Ok(Err(e)) => eprintln!("Ignoring STACKS_SIGNER_KEY: {}", e),
+        Err(_) => println!("No STACKS_SIGNER_KEY set; contract calls will be read-only"),
+    }
+    match std::env::var("STACKS_SPONSOR_KEY").map(|key| StacksSigner::from_hex(&key, network)) {
+        Ok(Ok(sponsor)) => interactor = interactor.with_sponsor(sponsor),
+        Ok(Err(e)) => eprintln!("Ignoring STACKS_SPONSOR_KEY: {}", e),
+        Err(_) => println!("No STACKS_SPONSOR_KEY set; sponsored transactions are disabled"),
+    }
+    let clarity = Arc::new(interactor);
 
     let state_anchoring = Arc::new(StateAnchoring::new());
     let validator_registry = Arc::new(ValidatorRegistry::new(
         1_000_000,
         PathBuf::from("validator_registry.json"),
     ));
+    let admission_mode = match config.get("validator").and_then(|table| table.get("admission")).and_then(toml::Value::as_str) {
+        Some("governed") => AdmissionMode::Governed,
+        _ => AdmissionMode::Permissionless,
+    };
+    let validator_admission = match ValidatorAdmission::new(
+        validator_registry.clone(),
+        admission_mode,
+        PathBuf::from("validator_admission.json"),
+    ) {
+        Ok(validator_admission) => Arc::new(validator_admission),
+        Err(e) => {
+            eprintln!("Refusing to start validator admission: {}", e);
+            return;
+        }
+    };
 
-    let governance_policies = Arc::new(GovernanceValidatorPolicies::new(1_000_000, 80, 70));
-    let btcz_integration = Arc::new(BTCZIntegration::new("http://btcz_node_rpc_url".to_string()));
+    // Policy changes take effect after `policy-notice` epochs and are kept as versions
+    let validator_setting = |key: &str| config.get("validator").and_then(|table| table.get(key)).and_then(toml::Value::as_integer);
+    let governance_policies = GovernanceValidatorPolicies::new(1_000_000, 80, 70)
+        .with_epochs(
+            validator_setting("policy-epoch").map_or(24 * 60 * 60, |v| v as u64),
+            validator_setting("policy-notice").map_or(1, |v| v as u64),
+        )
+        .with_storage(PathBuf::from("validator_policies.json"));
+    let governance_policies = match governance_policies {
+        Ok(governance_policies) => Arc::new(governance_policies),
+        Err(e) => {
+            eprintln!("Refusing to start validator policies: {}", e);
+            return;
+        }
+    };
+    let btcz_integration = Arc::new(BTCZIntegration::with_endpoints(btcz_endpoints.clone()));
     let cross_layer_sync = Arc::new(CrossLayerSync::new(btcz_integration.clone()));
-    let bridge_ledger = MutexAdapter::new_tokio(BridgeLedger::new(
-        state_anchoring.clone(),
-        clarity.clone(),
-        btcz_integration.clone(),
-    ));
+    let bridge_limits = Arc::new(std::sync::Mutex::new(BridgeLimits::default()));
+
+    // M-of-N guardians who can pause the bridge, freeze rewards or veto queued proposals
+    let governance_setting = |key: &str| config.get("governance").and_then(|table| table.get(key)).and_then(toml::Value::as_integer);
+    let guardian_addresses: Vec<String> = config
+        .get("governance")
+        .and_then(|table| table.get("guardians"))
+        .and_then(toml::Value::as_array)
+        .map(|values| values.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    let ratification_window = governance_setting("guardian-ratification-window").unwrap_or(7 * 24 * 60 * 60);
+    let guardians = match GuardianCouncil::new(
+        guardian_addresses,
+        governance_setting("guardian-threshold").map_or(1, |v| v as usize),
+        ratification_window,
+        PathBuf::from("guardian_actions.json"),
+    ) {
+        Ok(guardians) => Arc::new(guardians),
+        // Running without guardians would drop any pause, freeze or veto in force
+        Err(e) => {
+            eprintln!("Refusing to start guardians: {}", e);
+            return;
+        }
+    };
 
-    let validator_rewards = Arc::new(std::sync::Mutex::new(ValidatorRewards::new(
-        100,
-        PathBuf::from("validator_rewards.json"),
-    )));
+    // Protocol treasury funded by slashing, spent through governance
+    // gBTCZ balances backing deposits, delegations and the treasury, seeded from genesis on first start
+    let genesis_balances: HashMap<String, u64> = config
+        .get("governance")
+        .and_then(|table| table.get("genesis"))
+        .and_then(toml::Value::as_table)
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(account, amount)| amount.as_integer().map(|amount| (account.clone(), amount as u64)))
+                .collect()
+        })
+        .unwrap_or_default();
+    let governance_token = match GovernanceToken::load("gBTCZ", &genesis_balances, PathBuf::from("governance_token.json")) {
+        Ok(token) => Arc::new(std::sync::Mutex::new(token)),
+        Err(e) => {
+            eprintln!("Refusing to start gBTCZ balances: {}", e);
+            return;
+        }
+    };
+    let treasury = match Treasury::new(
+        governance_token.clone(),
+        governance_setting("treasury-slash-share").map_or(0, |v| v as u64),
+        PathBuf::from("treasury.json"),
+    ) {
+        Ok(treasury) => Arc::new(treasury.with_anchoring(state_anchoring.clone())),
+        Err(e) => {
+            eprintln!("Refusing to start treasury: {}", e);
+            return;
+        }
+    };
+
+    let bridge_ledger = MutexAdapter::new_tokio(
+        BridgeLedger::new(state_anchoring.clone(), clarity.clone(), btcz_integration.clone())
+            .with_limits(bridge_limits.clone())
+            .with_guardians(guardians.clone()),
+    );
+
+    // Apply bridge operations once their Stacks transactions confirm
+    if let Some(ledger) = bridge_ledger.as_tokio() {
+        let poll_interval = std::env::var("ZOOK_BRIDGE_POLL_INTERVAL")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .unwrap_or(10);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(poll_interval));
+            loop {
+                interval.tick().await;
+                BridgeLedger::sync_pending(&ledger).await;
+            }
+        });
+    }
+
+    let validator_rewards = Arc::new(std::sync::Mutex::new(
+        ValidatorRewards::new(100, PathBuf::from("validator_rewards.json")).with_treasury(treasury.clone()),
+    ));
 
     let governance_storage_path = PathBuf::from("governance_proposals.json");
-    let cross_layer_governance = Arc::new(CrossLayerGovernance::new(
-        state_anchoring.clone(),
+    let default_rules = VotingRules::default();
+    let voting_rules = VotingRules {
+        voting_period_secs: governance_setting("voting-period").unwrap_or(default_rules.voting_period_secs),
+        quorum_percent: governance_setting("quorum-percent").map_or(default_rules.quorum_percent, |v| v as u64),
+        approval_percent: governance_setting("approval-percent").map_or(default_rules.approval_percent, |v| v as u64),
+        execution_window_secs: governance_setting("execution-window").unwrap_or(default_rules.execution_window_secs),
+        timelock_delay_secs: governance_setting("timelock-delay").unwrap_or(default_rules.timelock_delay_secs),
+        supermajority_percent: governance_setting("supermajority-percent").map_or(default_rules.supermajority_percent, |v| v as u64),
+        tally_strategy: config
+            .get("governance")
+            .and_then(|table| table.get("tally-strategy"))
+            .and_then(toml::Value::as_str)
+            .map_or(default_rules.tally_strategy, String::from),
+    };
+    let deposits = Arc::new(ProposalDeposits::new(
+        governance_token.clone(),
+        governance_setting("proposal-deposit").map_or(0, |v| v as u64),
+    ));
+    // Every setting governance proposals may change, with its bounds
+    let parameters = Arc::new(
+        ParameterRegistry::new()
+            .register(state_anchoring.clone())
+            .register(validator_rewards.clone())
+            .register(governance_policies.clone())
+            .register(bridge_limits)
+            .register(deposits.clone())
+            .register(guardians.clone())
+            .register(treasury.clone())
+            .register(validator_admission.clone()),
+    );
+    let delegations = match DelegationRegistry::new(
+        governance_token,
+        validator_registry.clone(),
+        PathBuf::from("delegations.json"),
+    ) {
+        Ok(delegations) => Arc::new(delegations),
+        Err(e) => {
+            eprintln!("Refusing to start delegations: {}", e);
+            return;
+        }
+    };
+    // Appending to a damaged log would fork the chain, so refuse to start instead
+    let audit_log = match GovernanceAuditLog::new(PathBuf::from("governance_audit.jsonl")) {
+        Ok(audit_log) => Arc::new(audit_log),
+        Err(e) => {
+            eprintln!("Governance audit log failed verification: {}", e);
+            return;
+        }
+    };
+    let proposal_engine = ProposalEngine::new(
+        parameters,
         validator_registry.clone(),
         governance_policies.clone(),
-        validator_rewards.clone(),
         governance_storage_path,
-    ));
+    );
+    let mut proposal_engine = match proposal_engine {
+        Ok(proposal_engine) => proposal_engine,
+        Err(e) => {
+            eprintln!("Refusing to start governance: {}", e);
+            return;
+        }
+    }
+    .with_voting_rules(voting_rules)
+    .with_guardians(guardians.clone())
+    .with_delegations(delegations.clone())
+    .with_deposits(deposits)
+    .with_audit_log(audit_log);
+    // Parameters whose proposals must be tallied by a particular strategy
+    if let Some(required) = config
+        .get("governance")
+        .and_then(|table| table.get("required-strategies"))
+        .and_then(toml::Value::as_table)
+    {
+        for (param, strategy) in required {
+            if let Some(strategy) = strategy.as_str() {
+                proposal_engine = proposal_engine.with_required_strategy(param.clone(), strategy);
+            }
+        }
+    }
+    let proposal_engine = Arc::new(proposal_engine.with_anchoring(state_anchoring.clone()));
+
+    // Execute proposals whose timelock has expired and expire those that keep failing.
+    // With a signing key, proposals are also mirrored into the governance contract.
+    {
+        let proposal_engine = proposal_engine.clone();
+        let treasury = treasury.clone();
+        let contract_sync = clarity
+            .signer
+            .is_some()
+            .then(|| GovernanceContractSync::new(proposal_engine.clone(), clarity.clone()));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = proposal_engine.execute_due_proposals() {
+                    eprintln!("Failed to execute governance proposals: {}", e);
+                }
+                if let Err(e) = proposal_engine.expire_stale_proposals() {
+                    eprintln!("Failed to expire governance proposals: {}", e);
+                }
+                if let Some(guardians) = &proposal_engine.guardians {
+                    if let Err(e) = guardians.expire_lapsed() {
+                        eprintln!("Failed to expire guardian actions: {}", e);
+                    }
+                }
+                if let Err(e) = proposal_engine.apply_guardian_vetoes() {
+                    eprintln!("Failed to apply guardian vetoes: {}", e);
+                }
+                if let Err(e) = treasury.release_vested() {
+                    eprintln!("Failed to release treasury grants: {}", e);
+                }
+                if let Some(contract_sync) = &contract_sync {
+                    if let Err(e) = contract_sync.sync().await {
+                        eprintln!("Failed to sync governance contract: {}", e);
+                    }
+                }
+            }
+        });
+    }
 
-    let api_keys = Arc::new(HashMap::new());
+    let ledger_handle = bridge_ledger.as_tokio().expect("Bridge ledger uses a Tokio mutex");
+    let event_observer = StacksEventObserver::new(
+        ledger_handle,
+        cross_layer_sync.clone(),
+        proposal_engine.clone(),
+        zbtcz_address,
+        governance_address,
+        PathBuf::from("event_observer.json"),
+    );
+    let mut event_observer = match event_observer {
+        Ok(event_observer) => event_observer,
+        Err(e) => {
+            eprintln!("Refusing to start the event observer: {}", e);
+            return;
+        }
+    };
+    if let Ok(record_path) = std::env::var("ZOOK_OBSERVER_RECORD_PATH") {
+        event_observer = event_observer.with_recording(PathBuf::from(record_path));
+    }
+    if let Ok(replay_path) = std::env::var("ZOOK_OBSERVER_REPLAY_PATH") {
+        match event_observer.replay(&PathBuf::from(&replay_path)).await {
+            Ok(count) => println!("Replayed {} Stacks deliveries from {}", count, replay_path),
+            Err(e) => eprintln!("Failed to replay {}: {}", replay_path, e),
+        }
+    }
+
+    // Sponsor user-signed bridge calls and pause when the sponsor runs low on STX
+    let sponsorship = clarity.sponsor.as_ref().and_then(|_| {
+        let mut quota = SponsorQuota::default();
+        if let Some(max) = std::env::var("ZOOK_SPONSOR_QUOTA").ok().and_then(|value| value.parse().ok()) {
+            quota.max_transactions = max;
+        }
+        let min_balance = std::env::var("ZOOK_SPONSOR_MIN_BALANCE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(1_000_000);
+        match SponsorshipService::new(clarity.clone(), quota, min_balance, PathBuf::from("sponsorship.json")) {
+            Ok(service) => Some(Arc::new(service)),
+            Err(e) => {
+                eprintln!("Sponsorship disabled: {}", e);
+                None
+            }
+        }
+    });
+    if let Some(service) = sponsorship.clone() {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = service.check_balance().await {
+                    eprintln!("Sponsor balance check failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Each key may only vote, propose or delegate as the address it is bound to
+    let api_keys: HashMap<String, ApiKey> = config
+        .get("api")
+        .and_then(|table| table.get("keys"))
+        .and_then(toml::Value::as_table)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(|(key, principal)| {
+                    let api_key = ApiKey {
+                        key: key.clone(),
+                        permissions: Vec::new(),
+                        principal: Some(principal.as_str()?.to_string()),
+                    };
+                    Some((key.clone(), api_key))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let api_keys = Arc::new(api_keys);
     let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(60)));
 
     let governance_api = GovernanceAPI::new(
-        cross_layer_governance.clone(),
+        proposal_engine.clone(),
         api_keys.clone(),
         rate_limiter.clone(),
     );
-    let bridge_api = BridgeAPI::new(HashSet::new(), bridge_ledger);
-    let validator_rewards_api = ValidatorRewardsAPI::new(clarity.clone());
+    let delegation_api = DelegationAPI::new(delegations, api_keys.clone(), rate_limiter.clone());
+    let guardian_api = GuardianAPI::new(proposal_engine.clone(), api_keys.clone(), rate_limiter.clone());
+    let treasury_api = TreasuryAPI::new(treasury, api_keys.clone(), rate_limiter.clone());
+    let mut bridge_api = BridgeAPI::new(HashSet::new(), bridge_ledger);
+    if let Some(service) = sponsorship {
+        bridge_api = bridge_api.with_sponsorship(service);
+    }
+    let validator_rewards_api = ValidatorRewardsAPI::new(clarity.clone()).with_guardians(guardians);
     let anchoring_api = AnchoringAPI::new(cross_layer_sync.clone());
+    let event_observer_api = EventObserverAPI::new(Arc::new(event_observer));
+    let endpoint_health_api = EndpointHealthAPI::new(vec![stacks_endpoints, btcz_endpoints]);
 
-    let routes = governance_api
+    // Each module's routes come boxed, and are boxed again in groups, so the combined filter
+    // type stays shallow as APIs are added
+    let governance_routes = governance_api
+        .routes()
+        .or(delegation_api.routes())
+        .or(guardian_api.routes())
+        .or(treasury_api.routes())
+        .or(node_api_routes(validator_admission, api_keys.clone()))
+        .or(policy_api_routes(governance_policies))
+        .boxed();
+    let bridge_routes = bridge_api
         .routes()
-        .or(bridge_api.routes())
         .or(validator_rewards_api.routes())
-        .or(anchoring_api.routes());
+        .or(anchoring_api.routes())
+        .or(event_observer_api.routes())
+        .or(endpoint_health_api.routes())
+        .boxed();
+    let routes = governance_routes.or(bridge_routes);
 
     println!("Starting server at http://0.0.0.0:3030");
     warp::serve(routes.with(warp::log("zook_api")))
@@ -28,4 +28,6 @@ pub struct BurnRequest {
 pub struct MintRequest {
     pub address: String,
     pub amount: u64,
+    pub block_height: u64,   // Finalized BTCZ state the lock is proven against
+    pub merkle_root: String, // Hex-encoded merkle root of that state
 }
@@ -1,7 +1,7 @@
 use warp::Filter;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use crate::validator::node_registration::ValidatorRegistry;
+use crate::governance::validator_admission::{AdmissionRequest, ValidatorAdmission};
 use crate::api::security::{with_auth, ApiKey};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -10,13 +10,22 @@ struct NodeRegisterRequest {
     staked_btcz: u64,
 }
 
+/// Message for a change applied outright, or for one queued until governance approves it
+fn admission_message(queued: Option<AdmissionRequest>, parameter: &str, done: &str) -> String {
+    match queued {
+        Some(request) => format!("Request {} awaits a proposal setting {} to {}", request.id, parameter, request.id),
+        None => done.to_string(),
+    }
+}
+
 pub fn node_api_routes(
-    registry: Arc<ValidatorRegistry>,
+    admission: Arc<ValidatorAdmission>,
     api_keys: Arc<std::collections::HashMap<String, ApiKey>>,
-) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    let register_registry = registry.clone();
-    let list_registry = registry.clone();
-    let deactivate_registry = registry.clone();
+) -> warp::filters::BoxedFilter<(impl warp::Reply,)> {
+    let register_admission = admission.clone();
+    let list_admission = admission.clone();
+    let pending_admission = admission.clone();
+    let deactivate_admission = admission.clone();
 
     let register_node = warp::post()
         .and(warp::path("node"))
@@ -24,12 +33,12 @@ pub fn node_api_routes(
         .and(warp::body::json())
         .and(with_auth(api_keys.clone()))
         .and_then(move |request: NodeRegisterRequest, _auth: ApiKey| {
-            let registry = register_registry.clone();
+            let admission = register_admission.clone();
             async move {
-                match registry.register_node(request.address, request.staked_btcz) {
-                    Ok(_) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
-                        "status": "success",
-                        "message": "Node registered successfully",
+                match admission.register(request.address, request.staked_btcz) {
+                    Ok(queued) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                        "status": if queued.is_some() { "pending" } else { "success" },
+                        "message": admission_message(queued, "admit_validator", "Node registered successfully"),
                     }))),
                     Err(e) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
                         "status": "error",
@@ -43,9 +52,9 @@ pub fn node_api_routes(
         .and(warp::path("node"))
         .and(warp::path("list"))
         .and_then(move || {
-            let registry = list_registry.clone();
+            let admission = list_admission.clone();
             async move {
-                match registry.list_nodes() {
+                match admission.registry.list_nodes() {
                     Ok(nodes) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
                         "status": "success",
                         "nodes": nodes,
@@ -58,18 +67,38 @@ pub fn node_api_routes(
             }
         });
 
+    let pending_requests = warp::get()
+        .and(warp::path("node"))
+        .and(warp::path("pending"))
+        .and_then(move || {
+            let admission = pending_admission.clone();
+            async move {
+                match admission.mode().and_then(|mode| Ok((mode, admission.pending()?))) {
+                    Ok((mode, requests)) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                        "status": "success",
+                        "mode": mode,
+                        "requests": requests,
+                    }))),
+                    Err(e) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                        "status": "error",
+                        "message": e,
+                    }))),
+                }
+            }
+        });
+
     let deactivate_node = warp::post()
         .and(warp::path("node"))
         .and(warp::path("deactivate"))
         .and(warp::body::json())
         .and(with_auth(api_keys.clone()))
         .and_then(move |request: NodeRegisterRequest, _auth: ApiKey| {
-            let registry = deactivate_registry.clone();
+            let admission = deactivate_admission.clone();
             async move {
-                match registry.deactivate_node(&request.address) {
-                    Ok(_) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
-                        "status": "success",
-                        "message": "Node deactivated successfully",
+                match admission.deactivate(request.address) {
+                    Ok(queued) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                        "status": if queued.is_some() { "pending" } else { "success" },
+                        "message": admission_message(queued, "eject_validator", "Node deactivated successfully"),
                     }))),
                     Err(e) => Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
                         "status": "error",
@@ -79,5 +108,5 @@ pub fn node_api_routes(
             }
         });
 
-    register_node.or(list_nodes).or(deactivate_node)
+    register_node.or(list_nodes).or(pending_requests).or(deactivate_node).boxed()
 }
@@ -41,9 +41,15 @@ impl ValidatorRegistry {
         }
     }
 
+    #[allow(dead_code)] // Every mutation already persists
     pub fn save_to_disk(&self) -> Result<(), String> {
         let nodes = self.nodes.lock().map_err(|_| "Mutex lock failed")?;
-        let serialized = serde_json::to_string(&*nodes).map_err(|_| "Serialization failed")?;
+        self.persist(&nodes)
+    }
+
+    /// Writes nodes the caller already holds the lock for
+    fn persist(&self, nodes: &HashMap<String, ValidatorNode>) -> Result<(), String> {
+        let serialized = serde_json::to_string(nodes).map_err(|_| "Serialization failed")?;
         fs::write(&self.storage_path, serialized).map_err(|_| "Failed to write to disk")?;
         Ok(())
     }
@@ -72,7 +78,7 @@ impl ValidatorRegistry {
         };
 
         nodes.insert(address.clone(), node);
-        self.save_to_disk()?;
+        self.persist(&nodes)?;
         println!("Validator registered: {} with {} BTCZ", address, staked_btcz);
         Ok(())
     }
@@ -81,18 +87,20 @@ impl ValidatorRegistry {
         let mut nodes = self.nodes.lock().map_err(|_| "Mutex lock failed")?;
         let node = nodes.get_mut(address).ok_or("Validator not found")?;
         node.active = false;
-        self.save_to_disk()?;
+        self.persist(&nodes)?;
         println!("Validator deactivated: {}", address);
         Ok(())
     }
 
+    #[allow(dead_code)] // Stake changes are not exposed by any API yet
     pub fn update_stake(&self, address: &str, additional_stake: u64) -> Result<(), String> {
         let mut nodes = self.nodes.lock().map_err(|_| "Mutex lock failed")?;
         let node = nodes.get_mut(address).ok_or("Validator not found")?;
         node.staked_btcz += additional_stake;
         node.voting_power += additional_stake; // Update voting power with additional stake
-        self.save_to_disk()?;
-        println!("Updated stake for {}: {} BTCZ", address, node.staked_btcz);
+        let staked_btcz = node.staked_btcz;
+        self.persist(&nodes)?;
+        println!("Updated stake for {}: {} BTCZ", address, staked_btcz);
         Ok(())
     }
 
@@ -106,6 +114,7 @@ impl ValidatorRegistry {
         nodes.get(address).cloned().ok_or("Validator not found".to_string())
     }
 
+    #[allow(dead_code)]
     pub fn update_activity_and_compliance(
         &self,
         address: &str,
@@ -116,7 +125,7 @@ impl ValidatorRegistry {
         let node = nodes.get_mut(address).ok_or("Validator not found")?;
         node.activity_percentage = activity_percentage;
         node.compliance_percentage = compliance_percentage;
-        self.save_to_disk()?;
+        self.persist(&nodes)?;
         println!(
             "Updated activity and compliance for {}: Activity {}%, Compliance {}%",
             address, activity_percentage, compliance_percentage